@@ -0,0 +1,54 @@
+//! Feeds arbitrary bytes to every parsing entry point, none of which may
+//! panic. Run with `cargo fuzz run parse`, seeding `corpus/parse` with
+//! real EDIDs helps it find the interesting paths.
+
+#![no_main]
+
+use edid_rs::{EdidRef, EdidVisitor, EDID, Reader};
+use libfuzzer_sys::fuzz_target;
+
+struct Visitor;
+
+impl EdidVisitor for Visitor {}
+
+fuzz_target!(|data: &[u8]| {
+    let _ = EDID::parse(&mut Reader::new(&mut &data[..]));
+    let _ = EDID::parse_lenient(&mut Reader::new(&mut &data[..]));
+    let _ = EDID::parse_partial(&mut Reader::new(&mut &data[..]));
+    let _ = edid_rs::parse_with_visitor(&mut Reader::new(&mut &data[..]), &mut Visitor);
+    let _ = edid_rs::find_edids(data);
+    let _ = edid_rs::unparsed(data);
+    let _ = edid_rs::check_reserved(data);
+    let _ = edid_rs::anonymize(&mut data.to_vec(), true);
+
+    // Decode what can be decoded from every EDID and extension block.
+    for (_, record) in edid_rs::parse_stream(data) {
+        if let Ok((edid, extensions)) = record {
+            let _ = edid.modes_or_fallback();
+            let _ = edid.vrr_range(&extensions);
+            let _ = edid.to_eld(&extensions, &Default::default());
+            for timing in &edid.timings.detailed_timings {
+                let _ = timing.refresh_ratio();
+                let _ = timing.reduced_blanking();
+                let _ = edid_rs::patch::encode_detailed_timing(timing);
+            }
+            for timing in &edid.timings.standard_timings {
+                let _ = timing.to_detailed_timing();
+            }
+            for block in &extensions {
+                let _ = block.hdmi_vics();
+                let _ = block.adaptive_sync();
+                let _ = block.dolby_vision();
+            }
+        }
+    }
+
+    if data.len() >= 128 {
+        let mut block = [0u8; 128];
+        block.copy_from_slice(&data[..128]);
+        if let Ok(view) = EdidRef::new(&block) {
+            let _ = view.monitor_name();
+            let _ = view.detailed_timings().count();
+        }
+    }
+});
@@ -0,0 +1,53 @@
+/* C interface to edid-rs, built with `--features ffi`. Keep in sync with src/ffi.rs. */
+
+#ifndef EDID_RS_H
+#define EDID_RS_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define EDID_OK 0
+/* A required pointer argument was null. */
+#define EDID_ERROR_NULL_POINTER 1
+/* The data is not a valid EDID. */
+#define EDID_ERROR_PARSE 2
+/* The EDID does not contain the requested information. */
+#define EDID_ERROR_NOT_PRESENT 3
+/* The caller's buffer cannot hold the result and its NUL terminator. */
+#define EDID_ERROR_BUFFER_TOO_SMALL 4
+
+/* A parsed EDID, owned by the caller until passed to edid_free. */
+typedef struct EdidHandle EdidHandle;
+
+/* Describe one of the EDID_* return codes. The string is static. */
+const char *edid_error_message(int code);
+
+/* Parse `len` bytes at `data`, storing a new handle in `*out` on success. */
+int edid_parse(const uint8_t *data, size_t len, EdidHandle **out);
+
+/* Free a handle returned by edid_parse. Passing NULL does nothing. */
+void edid_free(EdidHandle *handle);
+
+/* Write the three letter manufacturer ID, `buf` needs room for 4 bytes. */
+int edid_manufacturer(const EdidHandle *handle, char *buf, size_t len);
+
+int edid_product_code(const EdidHandle *handle, uint16_t *out);
+
+/* Active size and pixel clock of the preferred timing. Outputs may be NULL. */
+int edid_preferred_mode(const EdidHandle *handle, uint16_t *width, uint16_t *height, uint32_t *pixel_clock_hz);
+
+/* Maximum image size in centimetres. */
+int edid_physical_size(const EdidHandle *handle, float *width_cm, float *height_cm);
+
+/* Write the monitor name as a NUL terminated UTF-8 string. */
+int edid_monitor_name(const EdidHandle *handle, char *buf, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,186 @@
+extern crate edid_rs;
+
+use std::io::Read;
+use std::process::exit;
+
+const USAGE: &str = "\
+Usage: edid-rs [OPTIONS] [PATH]
+
+Decodes a binary EDID read from PATH, or from standard input
+when PATH is omitted or is `-`, and prints the parsed structure.
+
+Options:
+  --hex TEXT          decode the EDID from a hex string or dump instead
+  --lenient           read past spec violations and report them
+  --checksum POLICY   what to do about bad block checksums, one of
+                      `strict`, `warn` (the default) or `ignore`
+  --extensions        also dump the extension blocks
+  -h, --help          print this message
+
+Exit codes:
+  0  the EDID was parsed successfully
+  1  bad usage or the input could not be read
+  2  the input was read but is not a valid EDID
+  3  the EDID was parsed, but with warnings";
+
+// Exit codes, so scripts can tell a missing file from a bad EDID.
+const EXIT_USAGE: i32 = 1;
+const EXIT_PARSE: i32 = 2;
+const EXIT_WARNINGS: i32 = 3;
+
+#[derive(Clone, Copy, PartialEq)]
+enum ChecksumPolicy {
+    Strict,
+    Warn,
+    Ignore
+}
+
+struct Options {
+    path: Option<String>,
+    hex: Option<String>,
+    lenient: bool,
+    checksum: ChecksumPolicy,
+    extensions: bool
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    exit(EXIT_USAGE);
+}
+
+fn parse_args(args: Vec<String>) -> Options {
+    let mut options = Options {
+        path: None,
+        hex: None,
+        lenient: false,
+        checksum: ChecksumPolicy::Warn,
+        extensions: false
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                exit(0);
+            },
+            "--hex" => options.hex = Some(args.next().unwrap_or_else(|| usage())),
+            "--lenient" => options.lenient = true,
+            "--checksum" => options.checksum = match args.next().as_deref() {
+                Some("strict") => ChecksumPolicy::Strict,
+                Some("warn") => ChecksumPolicy::Warn,
+                Some("ignore") => ChecksumPolicy::Ignore,
+                _ => usage()
+            },
+            "--extensions" => options.extensions = true,
+            "-" if options.path.is_none() => options.path = Some(arg),
+            _ if !arg.starts_with('-') && options.path.is_none() => options.path = Some(arg),
+            _ => usage()
+        }
+    }
+
+    // A hex string and a path would be two inputs.
+    if options.hex.is_some() && options.path.is_some() {
+        usage();
+    }
+
+    options
+}
+
+fn read_input(options: &Options) -> Vec<u8> {
+    if let Some(hex) = &options.hex {
+        return edid_rs::decode_hex(hex).unwrap_or_else(|e| {
+            eprintln!("edid-rs: --hex: {}", e);
+            exit(EXIT_USAGE);
+        });
+    }
+
+    let path = options.path.as_deref().filter(|path| *path != "-");
+    let mut bytes = Vec::new();
+    let read = match path {
+        Some(path) => std::fs::File::open(path).and_then(|mut f| f.read_to_end(&mut bytes)),
+        None => std::io::stdin().read_to_end(&mut bytes)
+    };
+
+    if let Err(e) = read {
+        eprintln!("edid-rs: {}: {}", path.unwrap_or("<stdin>"), e);
+        exit(EXIT_USAGE);
+    }
+
+    bytes
+}
+
+fn dump_block(index: usize, block: &[u8]) {
+    println!("Extension block {}, tag {:#04x}:", index, block[0]);
+    for line in block.chunks(16) {
+        let hex: Vec<String> = line.iter().map(|b| format!("{:02x}", b)).collect();
+        println!("  {}", hex.join(" "));
+    }
+}
+
+fn main() {
+    let options = parse_args(std::env::args().skip(1).collect());
+    let bytes = read_input(&options);
+
+    let mut base = std::io::Cursor::new(bytes.clone());
+    let (edid, warnings) = if options.lenient {
+        edid_rs::parse_lenient(&mut base)
+    } else {
+        edid_rs::parse(&mut base).map(|edid| (edid, Vec::new()))
+    }.unwrap_or_else(|e| {
+        eprintln!("edid-rs: {}", e);
+        exit(EXIT_PARSE);
+    });
+
+    let mut warned = !warnings.is_empty();
+    for warning in &warnings {
+        eprintln!("edid-rs: warning: {}", warning);
+    }
+
+    // The base block is checked by the parser, the extensions here.
+    let extensions: Vec<&[u8]> = bytes.get(128..).unwrap_or_default()
+        .chunks(128)
+        .take(edid.extensions as usize)
+        .collect();
+    let mut bad_checksums: Vec<usize> = Vec::new();
+    if !edid.checksum_ok {
+        bad_checksums.push(0);
+    }
+    for (i, block) in extensions.iter().enumerate() {
+        if block.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)) != 0 {
+            bad_checksums.push(i + 1);
+        }
+    }
+
+    for &block in &bad_checksums {
+        match options.checksum {
+            ChecksumPolicy::Strict => {
+                eprintln!("edid-rs: {}", edid_rs::Error::InvalidChecksum { offset: block * 128 });
+                exit(EXIT_PARSE);
+            },
+            ChecksumPolicy::Warn => {
+                eprintln!("edid-rs: warning: bad checksum in block {}", block);
+                warned = true;
+            },
+            ChecksumPolicy::Ignore => ()
+        }
+    }
+
+    println!("{:#?}", edid);
+
+    if options.extensions {
+        let complete = extensions.iter().take_while(|block| block.len() == 128).count();
+        for (i, block) in extensions[..complete].iter().enumerate() {
+            dump_block(i + 1, block);
+        }
+        if complete < edid.extensions as usize {
+            eprintln!("edid-rs: warning: {} extension blocks announced, {} present",
+                edid.extensions, complete);
+            warned = true;
+        }
+    }
+
+    if warned {
+        exit(EXIT_WARNINGS);
+    }
+}
@@ -0,0 +1,1416 @@
+//! Decoding the data blocks of CTA-861 extensions, and what the
+//! EDID says when they are taken into account.
+
+use crate::{
+    DetailedTiming, DisplayColorInfo, EDID, ExtensionBlock, FrequencySupport, MonitorDescriptor, SyncPolarity, cp437_encode,
+    progressive_timing
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+impl EDID {
+    /// Whether the display is something like a VR headset which should not be
+    /// part of the desktop, either because the Microsoft data block in the
+    /// `extensions` says so or because it is on a list of known headsets. As
+    /// in the kernel, the data block cannot clear what the list sets.
+    pub fn non_desktop(&self, extensions: &[ExtensionBlock]) -> bool {
+        let id = self.product.manufacturer_id;
+        let code = self.product.product_code;
+        let quirk = NON_DESKTOP_QUIRKS.iter().any(|&(quirk, first, last)| {
+            quirk == (id.0, id.1, id.2) && (first..=last).contains(&code)
+        });
+
+        quirk || extensions.iter().filter_map(|block| block.microsoft_vsdb()).any(|vsdb| match vsdb.version {
+            1 | 2 => true,
+            3 => !vsdb.desktop_usage,
+            _ => false
+        })
+    }
+
+    /// Build the ELD (EDID-like data) which HD audio drivers expect, in the
+    /// version 2 layout the Linux kernel uses. The audio capabilities come from
+    /// the CTA-861 `extensions`, and `None` is returned if there are none.
+    pub fn to_eld(&self, extensions: &[ExtensionBlock], port: &EldPort) -> Option<Vec<u8>> {
+        let first = extensions.iter().find(|block| block.tag() == 0x02)?;
+
+        let name = self.descriptors.0.iter().find_map(|d| match d {
+            MonitorDescriptor::MonitorName(name) => Some(name),
+            _ => None
+        });
+        let name: Vec<u8> = name.into_iter().flat_map(|name| name.chars()).map(cp437_encode).take(16).collect();
+
+        let id = self.product.manufacturer_id.raw();
+
+        let mut eld = vec![0u8; 20];
+        eld[0] = 2 << 3;
+        eld[4] = (first.0[1] << 5) | name.len() as u8;
+        eld[5] = match port.connection {
+            EldConnection::Hdmi => 0,
+            EldConnection::DisplayPort => 1 << 2
+        };
+        eld[7] = port.audio_sync_delay;
+        eld[8..16].copy_from_slice(&port.port_id.to_le_bytes());
+        eld[16..18].copy_from_slice(&id.to_be_bytes());
+        eld[18..20].copy_from_slice(&self.product.product_code.to_le_bytes());
+        eld.extend_from_slice(&name);
+
+        let mut sad_count = 0;
+        for block in extensions.iter().filter(|block| block.tag() == 0x02) {
+            for (tag, payload) in block.data_blocks() {
+                match tag {
+                    // Audio, made of three byte short audio descriptors.
+                    1 => {
+                        let count = core::cmp::min(payload.len() / 3, 15 - sad_count);
+                        eld.extend_from_slice(&payload[..count * 3]);
+                        sad_count += count;
+                    },
+                    // Speaker allocation, of which the ELD takes the first byte.
+                    4 => if let Some(allocation) = SpeakerAllocation::parse(payload) {
+                        eld[6] = allocation.0[0];
+                    },
+                    // The HDMI vendor block, which says if ACP, ISRC1
+                    // and ISRC2 packets are supported.
+                    3 if payload.len() >= 6 && payload[..3] == [0x03, 0x0c, 0x00] && payload[5] & (1 << 7) > 0 => {
+                        eld[5] |= 1 << 1;
+                    },
+                    _ => ()
+                }
+            }
+        }
+        eld[5] |= (sad_count as u8) << 4;
+
+        // The length after the header is given in four byte words.
+        let words = (eld.len() - 4).div_ceil(4);
+        eld[2] = words as u8;
+        eld.resize(4 + words * 4, 0);
+        Some(eld)
+    }
+
+    /// Whether the display can play `format` with `channels` channels at
+    /// `sample_rate_hz`, according to the short audio descriptors of the
+    /// CTA-861 `extensions`. A `bit_depth` is only checked for L-PCM, as the
+    /// descriptors of other formats do not give one. The basic audio flag
+    /// counts as 2 channel 16-bit L-PCM at 32, 44.1 and 48kHz.
+    pub fn supports_audio_format(&self, extensions: &[ExtensionBlock], format: AudioFormat, channels: u8, sample_rate_hz: u32, bit_depth: Option<u8>) -> bool {
+        let basic = format == AudioFormat::Lpcm
+            && channels <= 2
+            && BASIC_AUDIO_RATES.contains(&sample_rate_hz)
+            && bit_depth.is_none_or(|depth| depth == 16)
+            && extensions.iter().any(|block| block.basic_audio());
+
+        basic || extensions.iter().flat_map(|block| block.short_audio_descriptors()).any(|sad| {
+            sad.format == format
+                && channels <= sad.max_channels
+                && sad.supports_sample_rate(sample_rate_hz)
+                && (format != AudioFormat::Lpcm || bit_depth.is_none_or(|depth| sad.lpcm_bit_depths().contains(&depth)))
+        })
+    }
+
+    /// The most L-PCM channels the display can play, or `None` if the
+    /// `extensions` give it no audio.
+    pub fn max_lpcm_channels(&self, extensions: &[ExtensionBlock]) -> Option<u8> {
+        let basic = Some(2).filter(|_| extensions.iter().any(|block| block.basic_audio()));
+        extensions.iter().flat_map(|block| block.short_audio_descriptors())
+            .filter(|sad| sad.format == AudioFormat::Lpcm)
+            .map(|sad| sad.max_channels)
+            .chain(basic)
+            .max()
+    }
+
+    /// The L-PCM sample rates the display can play in Hz, lowest first.
+    pub fn lpcm_sample_rates(&self, extensions: &[ExtensionBlock]) -> Vec<u32> {
+        let mut rates: Vec<u32> = extensions.iter().flat_map(|block| block.short_audio_descriptors())
+            .filter(|sad| sad.format == AudioFormat::Lpcm)
+            .flat_map(|sad| sad.sample_rates_hz())
+            .collect();
+        if extensions.iter().any(|block| block.basic_audio()) {
+            rates.extend_from_slice(&BASIC_AUDIO_RATES);
+        }
+        rates.sort_unstable();
+        rates.dedup();
+        rates
+    }
+
+    /// Whether the display takes HDR10 video: the SMPTE ST 2084 (PQ) transfer
+    /// function with static metadata type 1, and BT.2020 colorimetry, according
+    /// to the HDR static metadata and colorimetry data blocks of the `extensions`.
+    pub fn hdr10_capable(&self, extensions: &[ExtensionBlock]) -> bool {
+        let hdr = extensions.iter().filter_map(|block| block.hdr_static_metadata())
+            .any(|hdr| hdr.supports_pq() && hdr.static_metadata_types & 1 > 0);
+        let bt2020 = extensions.iter().filter_map(|block| block.colorimetry())
+            .any(|colorimetry| colorimetry & 0xe0 > 0);
+        hdr && bt2020
+    }
+
+    /// Check the CTA-861 `extensions` against each other and the base block,
+    /// for things such as audio descriptors without the basic audio flag or
+    /// video descriptors with VIC 0. See `CtaRule` for what is checked.
+    pub fn check_cta(&self, extensions: &[ExtensionBlock]) -> Vec<CtaIssue> {
+        let mut issues = Vec::new();
+        let cta: Vec<(usize, &ExtensionBlock)> = extensions.iter().enumerate()
+            .filter(|(_, block)| block.tag() == 0x02)
+            .map(|(i, block)| (i + 1, block))
+            .collect();
+
+        let dtds = self.timings.detailed_timings.len()
+            + cta.iter().map(|(_, block)| block.detailed_timing_count()).sum::<usize>();
+        let hdmi_vsdb = cta.iter().any(|(_, block)| {
+            block.data_blocks().any(|(tag, payload)| tag == 3 && payload.len() >= 3 && payload[..3] == [0x03, 0x0c, 0x00])
+        });
+
+        for &(index, block) in &cta {
+            let mut issue = |rule| issues.push(CtaIssue { rule, block: index });
+            let flags = block.0[3];
+
+            let sads = !block.short_audio_descriptors().is_empty();
+            if block.basic_audio() && !sads {
+                issue(CtaRule::BasicAudioWithoutSads);
+            }
+            if sads && !block.basic_audio() {
+                issue(CtaRule::SadsWithoutBasicAudio);
+            }
+
+            if (flags & 0x0f) as usize > dtds {
+                issue(CtaRule::NativeDtdCount);
+            }
+
+            if let DisplayColorInfo::Encodings(encodings) = self.display.dpms.display_color {
+                if (flags & (1 << 5) > 0 && !encodings.ycbcr444) || (flags & (1 << 4) > 0 && !encodings.ycbcr422) {
+                    issue(CtaRule::YcbcrOnRgbSink);
+                }
+            }
+
+            let hdmi_forum = block.data_blocks().any(|(tag, payload)| {
+                (tag == 3 && payload.len() >= 3 && payload[..3] == [0xd8, 0x5d, 0xc4]) || (tag == 7 && payload.first() == Some(&0x79))
+            });
+            if hdmi_forum && !hdmi_vsdb {
+                issue(CtaRule::MissingHdmiVsdb);
+            }
+
+            let svds: Vec<u8> = block.data_blocks().filter(|&(tag, _)| tag == 2).flat_map(|(_, payload)| payload.iter().copied()).collect();
+            if svds.iter().any(|&svd| svd & 0x7f == 0) {
+                issue(CtaRule::SvdVicZero);
+            }
+
+            // The YCbCr 4:2:0 capability map has a bit for each SVD in turn.
+            let past_svds = block.data_blocks()
+                .filter(|&(tag, payload)| tag == 7 && payload.first() == Some(&0x0f))
+                .flat_map(|(_, payload)| payload[1..].iter().enumerate())
+                .any(|(i, &bits)| (0..8).any(|bit| bits & (1 << bit) > 0 && i * 8 + bit >= svds.len()));
+            if past_svds {
+                issue(CtaRule::Y420CmdbPastSvds);
+            }
+        }
+
+        issues
+    }
+
+    /// The variable refresh range of the display, looking at the HDMI Forum
+    /// data blocks, then the native panel range of an Adaptive-Sync block,
+    /// then the AMD FreeSync block in the `extensions`, and then the range
+    /// limits of a 1.4 EDID which claims continuous frequency support.
+    ///
+    /// If the first of these and the FreeSync block disagree, the range is
+    /// where they overlap and `conflict` is set. If they do not overlap at
+    /// all, the first range is used. Range limits only fill in an HDMI Forum
+    /// maximum which is not given.
+    pub fn vrr_range(&self, extensions: &[ExtensionBlock]) -> Option<VrrRange> {
+        let limits = match self.range_limits() {
+            Some(MonitorDescriptor::RangeLimits { vertical_rate, .. }) => Some(*vertical_rate),
+            _ => None
+        };
+
+        let hdmi = extensions.iter().find_map(|block| block.hdmi_forum_vrr()).and_then(|(min, max)| match max {
+            0 => limits.map(|(_, max)| (min, max)),
+            _ => Some((min, max))
+        }).map(|range| (range, VrrSource::HdmiForum));
+        let adaptive_sync = extensions.iter().flat_map(|block| block.adaptive_sync()).find_map(|descriptor| match descriptor {
+            AdaptiveSyncDescriptor::Range { native_panel: true, min_refresh, max_refresh, .. } if min_refresh > 0 && max_refresh > min_refresh as u16 => {
+                Some(((min_refresh as u16, max_refresh), VrrSource::AdaptiveSync))
+            },
+            _ => None
+        });
+        let first = hdmi.or(adaptive_sync);
+        let freesync = extensions.iter().find_map(|block| block.amd_vsdb())
+            .and_then(|vsdb| vsdb.refresh_range())
+            .filter(|&(min, max)| min > 0 && max > min)
+            .map(|(min, max)| (min as u16, max as u16));
+
+        let range = |(min, max), source, conflict| VrrRange { min, max, source, conflict };
+        match (first, freesync) {
+            (Some((first, source)), Some(freesync)) if first != freesync => {
+                let overlap = (core::cmp::max(first.0, freesync.0), core::cmp::min(first.1, freesync.1));
+                if overlap.0 < overlap.1 {
+                    Some(range(overlap, source, true))
+                } else {
+                    Some(range(first, source, true))
+                }
+            },
+            (Some((first, source)), _) => Some(range(first, source, false)),
+            (None, Some(freesync)) => Some(range(freesync, VrrSource::FreeSync, false)),
+            (None, None) => {
+                let continuous = self.display.dpms.frequency_support == FrequencySupport::Continuous;
+                limits.filter(|&(min, max)| continuous && min > 0 && max > min)
+                    .map(|limits| range(limits, VrrSource::RangeLimits, false))
+            }
+        }
+    }
+
+    /// Whether the display takes the video format `vic` in the 3D `structure`,
+    /// according to the HDMI vendor specific data blocks of the `extensions`.
+    /// See `three_d_modes`.
+    pub fn supports_3d(&self, extensions: &[ExtensionBlock], vic: u8, structure: ThreeDStructure) -> bool {
+        self.three_d_modes(extensions).contains(&(vic, structure))
+    }
+
+    /// The 3D formats the display takes, as VICs with a 3D structure. These
+    /// come from the HDMI vendor specific data block of each CTA-861 extension
+    /// in the `extensions`, following the rules of HDMI 1.4b:
+    ///
+    /// - With 3D_present, the mandatory formats for those of 1080p24, 720p60,
+    ///   720p50, 1080i60 and 1080i50 which are among the short video descriptors.
+    /// - With 3D_Multi_present, the structures of 3D_Structure_ALL for the first
+    ///   16 short video descriptors, or only those picked by 3D_MASK.
+    /// - The structure of each 3D_Structure entry for the short video
+    ///   descriptor at its 2D_VIC_order.
+    pub fn three_d_modes(&self, extensions: &[ExtensionBlock]) -> Vec<(u8, ThreeDStructure)> {
+        let mut modes = Vec::new();
+        let mut add = |mode| if !modes.contains(&mode) {
+            modes.push(mode);
+        };
+
+        for block in extensions.iter().filter(|block| block.tag() == 0x02) {
+            let (flags, _, data) = match block.hdmi_video() {
+                Some(video) => video,
+                None => continue
+            };
+            let vics = block.video_codes();
+
+            let mut i = 0;
+            if flags & (1 << 7) > 0 {
+                for &(vic, structure) in MANDATORY_3D.iter().filter(|(vic, _)| vics.contains(vic)) {
+                    add((vic, structure));
+                }
+
+                // The structures all apply to the first 16 SVDs, or
+                // to those whose bits are set in the mask after them.
+                let multi = ((flags >> 5) & 0x03) as usize;
+                if multi == 1 || multi == 2 {
+                    i = 2 * multi;
+                }
+                if i > 0 && data.len() >= i {
+                    let structures = u16::from_be_bytes([data[0], data[1]]);
+                    let mask = if multi == 2 { u16::from_be_bytes([data[2], data[3]]) } else { 0xffff };
+
+                    for (_, &vic) in vics.iter().enumerate().take(16).filter(|&(index, _)| mask & (1 << index) > 0) {
+                        for &structure in ThreeDStructure::ALL.iter().filter(|s| structures & (1 << s.code()) > 0) {
+                            add((vic, structure));
+                        }
+                    }
+                }
+            }
+
+            // Each entry is the index of an SVD and a structure, with
+            // a byte of 3D_Detail after those from side-by-side on.
+            while i < data.len() {
+                let (order, code) = ((data[i] >> 4) as usize, data[i] & 0x0f);
+                i += if code >= 8 { 2 } else { 1 };
+                if let (Some(&vic), Some(structure)) = (vics.get(order), ThreeDStructure::from_code(code)) {
+                    add((vic, structure));
+                }
+            }
+        }
+
+        modes
+    }
+}
+
+impl ExtensionBlock {
+    // The tag and payload of each data block in a CTA-861 extension.
+    fn data_blocks(&self) -> impl Iterator<Item = (u8, &[u8])> {
+        self.data_blocks_at().map(|(_, tag, payload)| (tag, payload))
+    }
+
+    // The same, along with the offset of each data block's header.
+    pub(crate) fn data_blocks_at(&self) -> impl Iterator<Item = (usize, u8, &[u8])> {
+        let block = &self.0;
+
+        // Data blocks run from byte 4 up to the first detailed timing.
+        let end = if block[0] == 0x02 && (4..=127).contains(&block[2]) {
+            block[2] as usize
+        } else {
+            4
+        };
+
+        let mut i = 4;
+        core::iter::from_fn(move || {
+            let len = (*block.get(i)? & 0x1f) as usize;
+            if i >= end || i + 1 + len > end {
+                return None;
+            }
+
+            let (offset, tag) = (i, block[i] >> 5);
+            let payload = &block[i + 1..i + 1 + len];
+            i += 1 + len;
+            Some((offset, tag, payload))
+        })
+    }
+
+    /// Whether a CTA-861 extension says the display supports basic audio.
+    pub fn basic_audio(&self) -> bool {
+        self.tag() == 0x02 && self.0[1] >= 2 && self.0[3] & (1 << 6) > 0
+    }
+
+    /// The short audio descriptors of the audio data blocks.
+    pub fn short_audio_descriptors(&self) -> Vec<ShortAudioDescriptor> {
+        if self.tag() != 0x02 {
+            return Vec::new();
+        }
+
+        self.data_blocks()
+            .filter(|&(tag, _)| tag == 1)
+            .flat_map(|(_, payload)| payload.chunks_exact(3))
+            .map(ShortAudioDescriptor::parse)
+            .collect()
+    }
+
+    /// The HDR static metadata data block.
+    pub fn hdr_static_metadata(&self) -> Option<HdrStaticMetadata> {
+        // Extended tag 0x06, the luminance bytes being optional.
+        let (_, payload) = self.data_blocks()
+            .find(|&(tag, payload)| tag == 7 && payload.len() >= 3 && payload[0] == 0x06)?;
+
+        let luminance = |i: usize| payload.get(i).copied();
+        Some(HdrStaticMetadata {
+            eotfs: payload[1] & 0x3f,
+            static_metadata_types: payload[2],
+            max_luminance: luminance(3),
+            max_frame_avg: luminance(4),
+            min_luminance: luminance(5)
+        })
+    }
+
+    /// The supported colorimetries from the colorimetry data block. Bits 0
+    /// to 4 are xvYCC601, xvYCC709, sYCC601, opYCC601 and opRGB, and bits 5
+    /// to 7 are BT.2020 cYCC, YCC and RGB.
+    pub fn colorimetry(&self) -> Option<u8> {
+        // Extended tag 0x05.
+        self.data_blocks()
+            .find(|&(tag, payload)| tag == 7 && payload.len() >= 3 && payload[0] == 0x05)
+            .map(|(_, payload)| payload[1])
+    }
+
+    /// The speaker allocation data block.
+    pub fn speaker_allocation(&self) -> Option<SpeakerAllocation> {
+        self.data_blocks().find(|&(tag, _)| tag == 4).and_then(|(_, payload)| SpeakerAllocation::parse(payload))
+    }
+
+    // The number of detailed timings after the data blocks.
+    fn detailed_timing_count(&self) -> usize {
+        let start = self.0[2] as usize;
+        if self.tag() != 0x02 || start < 4 {
+            return 0;
+        }
+
+        (start..=127 - 18).step_by(18).take_while(|&i| self.0[i..i + 2] != [0, 0]).count()
+    }
+
+    /// The number of extension blocks given by an HDMI Forum EDID Extension
+    /// Override Data Block, which replaces byte 126 of the base block for
+    /// EDIDs with more than 255 extensions or a mix of types. It is only
+    /// valid as the first data block of the first extension.
+    pub fn eeodb_extension_count(&self) -> Option<u8> {
+        if self.tag() != 0x02 || self.0[1] < 3 {
+            return None;
+        }
+
+        match self.data_blocks().next()? {
+            (7, payload) if payload.len() >= 2 && payload[0] == 0x78 => Some(payload[1]),
+            _ => None
+        }
+    }
+
+    /// The VRRmin and VRRmax fields of the HDMI Forum vendor specific or
+    /// sink capability data block, in Hz. A maximum of zero means it is
+    /// not given, and `None` means there is no variable refresh support.
+    pub fn hdmi_forum_vrr(&self) -> Option<(u16, u16)> {
+        // The HF-VSDB has the OUI C4-5D-D8, and the HF-SCDB has the extended
+        // tag 0x79 and two reserved bytes instead, so the fields line up.
+        let (_, payload) = self.data_blocks().find(|&(tag, payload)| {
+            payload.len() >= 10 && ((tag == 3 && payload[..3] == [0xd8, 0x5d, 0xc4]) || (tag == 7 && payload[0] == 0x79))
+        })?;
+
+        let min = (payload[8] & 0x3f) as u16;
+        let max = (((payload[8] & 0xc0) as u16) << 2) | payload[9] as u16;
+        if min == 0 {
+            None
+        } else {
+            Some((min, max))
+        }
+    }
+
+    /// The HDMI_VIC codes listed in the HDMI vendor specific data block,
+    /// which HDMI 1.4 displays use for their 4K modes instead of short video
+    /// descriptors. `hdmi_vic_timing` gives the timings for them.
+    pub fn hdmi_vics(&self) -> Vec<u8> {
+        self.hdmi_video().map_or(Vec::new(), |(_, vics, _)| vics.to_vec())
+    }
+
+    // The video section of the HDMI vendor specific data block: the byte of
+    // 3D flags, the HDMI_VIC codes and the 3D fields after them.
+    fn hdmi_video(&self) -> Option<(u8, &[u8], &[u8])> {
+        // The HDMI VSDB has the OUI 00-0C-03, stored little-endian.
+        let (_, payload) = self.data_blocks()
+            .find(|&(tag, payload)| tag == 3 && payload.len() >= 8 && payload[..3] == [0x03, 0x0c, 0x00])?;
+
+        // Skip the optional latency fields to reach the video section.
+        let flags = payload[7];
+        if flags & (1 << 5) == 0 {
+            return None;
+        }
+        let mut offset = 8;
+        if flags & (1 << 7) > 0 {
+            offset += 2;
+        }
+        if flags & (1 << 6) > 0 {
+            offset += 2;
+        }
+
+        // After a byte of 3D flags is a byte with the lengths of the lists.
+        let (three_d, lens) = (*payload.get(offset)?, *payload.get(offset + 1)?);
+        let vics_end = offset + 2 + (lens >> 5) as usize;
+        let vics = payload.get(offset + 2..vics_end)?;
+        let three_d_end = core::cmp::min(vics_end + (lens & 0x1f) as usize, payload.len());
+        Some((three_d, vics, &payload[vics_end..three_d_end]))
+    }
+
+    // The VICs of the short video descriptors, in order. VICs up to 64 share
+    // the byte with a native flag, larger ones take all of it.
+    fn video_codes(&self) -> Vec<u8> {
+        self.data_blocks()
+            .filter(|&(tag, _)| tag == 2)
+            .flat_map(|(_, payload)| payload.iter())
+            .map(|&svd| if (129..=192).contains(&svd) { svd & 0x7f } else { svd })
+            .collect()
+    }
+
+    /// The descriptors of the Adaptive-Sync data block of a DisplayID 2.x
+    /// extension, which DisplayPort Adaptive-Sync and newer HDMI displays use
+    /// to describe their refresh ranges. Empty if there is no such block.
+    pub fn adaptive_sync(&self) -> Vec<AdaptiveSyncDescriptor> {
+        let block = &self.0;
+        // The section is the tag, the version, its length, the product
+        // type and the extension count, then data blocks and a checksum.
+        if block[0] != 0x70 || block[1] >> 4 != 2 {
+            return Vec::new();
+        }
+        let end = core::cmp::min(5 + block[2] as usize, 127);
+
+        let mut i = 5;
+        while i + 3 <= end {
+            let (tag, revision, len) = (block[i], block[i + 1], block[i + 2] as usize);
+            let payload = match block.get(i + 3..i + 3 + len) {
+                Some(payload) if i + 3 + len <= end => payload,
+                _ => break
+            };
+
+            if tag == 0x2b {
+                // Revisions may add bytes to each descriptor.
+                let size = 6 + ((revision >> 4) & 0x07) as usize;
+                return payload.chunks_exact(size).map(AdaptiveSyncDescriptor::parse).collect();
+            }
+            i += 3 + len;
+        }
+
+        Vec::new()
+    }
+
+    /// AMD's vendor specific data block, which FreeSync displays
+    /// carry in a CTA-861 extension.
+    pub fn amd_vsdb(&self) -> Option<AmdVsdb> {
+        // Vendor specific with the OUI 00-00-1A, stored little-endian.
+        let (_, payload) = self.data_blocks()
+            .find(|&(tag, payload)| tag == 3 && payload.len() >= 4 && payload[..3] == [0x1a, 0x00, 0x00])?;
+
+        let data = &payload[3..];
+        if !(1..=3).contains(&data[0]) || data.len() < 5 {
+            return Some(AmdVsdb::Unknown { version: data[0], data: data.to_vec() });
+        }
+
+        Some(AmdVsdb::FreeSync {
+            version: (data[0], data[1]),
+            min_refresh: data[2],
+            max_refresh: data[3],
+            flags: data[4],
+            flags_2: data.get(5).copied()
+        })
+    }
+
+    /// The Dolby Vision vendor specific video data block.
+    pub fn dolby_vision(&self) -> Option<DolbyVision> {
+        // Extended tag 0x01 with the OUI 00-D0-46, stored little-endian.
+        let (_, payload) = self.data_blocks()
+            .find(|&(tag, payload)| tag == 7 && payload.len() >= 5 && payload[..4] == [0x01, 0x46, 0xd0, 0x00])?;
+
+        let x = &payload[4..];
+        let version = x[0] >> 5;
+        let mut dv = DolbyVisionCapability {
+            version,
+            dm_version: (0, 0),
+            yuv422_12bit: x[0] & 1 > 0,
+            supports_2160p60: false,
+            global_dimming: false,
+            backlight_control: false,
+            target_luminance: (0.0, 0.0),
+            interface: DolbyVisionInterface { standard: true, low_latency: false, low_latency_hdmi: false },
+            yuv444_bits: None,
+            primaries: None,
+            data: x.to_vec()
+        };
+
+        // Chromaticities in the older versions are in 1/4096ths and 1/256ths.
+        let twelve_bit = |high: u8, low: u8| (((high as u16) << 4) | low as u16) as f32 / 4096.0;
+        match version {
+            0 if x.len() >= 17 => {
+                dv.supports_2160p60 = x[0] & (1 << 1) > 0;
+                dv.global_dimming = x[0] & (1 << 2) > 0;
+                dv.dm_version = (x[16] >> 4, x[16] & 0x0f);
+                dv.target_luminance = (
+                    pq_to_nits(((x[14] as u16) << 4) | (x[13] >> 4) as u16),
+                    pq_to_nits(((x[15] as u16) << 4) | (x[13] & 0x0f) as u16)
+                );
+                dv.primaries = Some([
+                    (twelve_bit(x[2], x[1] >> 4), twelve_bit(x[3], x[1] & 0x0f)),
+                    (twelve_bit(x[5], x[4] >> 4), twelve_bit(x[6], x[4] & 0x0f)),
+                    (twelve_bit(x[8], x[7] >> 4), twelve_bit(x[9], x[7] & 0x0f))
+                ]);
+            },
+            1 if x.len() >= 4 => {
+                dv.supports_2160p60 = x[0] & (1 << 1) > 0;
+                dv.global_dimming = x[1] & 1 > 0;
+                dv.dm_version = (((x[0] >> 2) & 0x07) + 2, 0);
+                let min = (x[2] >> 1) as f32 / 127.0;
+                dv.target_luminance = (min * min, 100.0 + (x[1] >> 1) as f32 * 50.0);
+                dv.interface.low_latency = x[3] & 1 > 0;
+                if x.len() == 10 {
+                    let byte = |i: usize| x[i] as f32 / 256.0;
+                    dv.primaries = Some([(byte(4), byte(5)), (byte(6), byte(7)), (byte(8), byte(9))]);
+                }
+            },
+            2 if x.len() >= 5 => {
+                dv.backlight_control = x[0] & (1 << 1) > 0;
+                dv.global_dimming = x[1] & (1 << 2) > 0;
+                dv.dm_version = (((x[0] >> 2) & 0x07) + 2, 0);
+                dv.target_luminance = (pq_to_nits(20 * (x[1] >> 3) as u16), pq_to_nits(2055 + 65 * (x[2] >> 3) as u16));
+                dv.interface = DolbyVisionInterface {
+                    standard: x[2] & (1 << 1) > 0,
+                    low_latency: true,
+                    low_latency_hdmi: x[2] & 1 > 0
+                };
+                dv.yuv444_bits = match (x[3] & 1, x[4] & 1) {
+                    (0, 1) => Some(10),
+                    (1, 0) => Some(12),
+                    _ => None
+                };
+            },
+            _ => return Some(DolbyVision::Unknown { version, data: x.to_vec() })
+        }
+
+        Some(DolbyVision::Capability(dv))
+    }
+
+    /// The HDR10+ vendor specific video data block.
+    pub fn hdr10_plus(&self) -> Option<Hdr10PlusCapability> {
+        // Extended tag 0x01 with the OUI 90-84-8B, stored little-endian.
+        let (_, payload) = self.data_blocks()
+            .find(|&(tag, payload)| tag == 7 && payload.len() >= 5 && payload[..4] == [0x01, 0x8b, 0x84, 0x90])?;
+
+        Some(Hdr10PlusCapability {
+            application_version: payload[4] & 0x03,
+            data: payload[4..].to_vec()
+        })
+    }
+
+    /// The Microsoft vendor specific data block, which head mounted displays
+    /// and other specialized displays carry in a CTA-861 extension.
+    pub fn microsoft_vsdb(&self) -> Option<MicrosoftVsdb> {
+        // Vendor specific with the OUI CA-12-5C, which is stored
+        // little-endian. The kernel also insists on this length.
+        let (_, payload) = self.data_blocks()
+            .find(|&(tag, payload)| tag == 3 && payload.len() == 21 && payload[..3] == [0x5c, 0x12, 0xca])?;
+
+        let mut container_id = [0u8; 16];
+        container_id.copy_from_slice(&payload[5..21]);
+        Some(MicrosoftVsdb {
+            version: payload[3],
+            desktop_usage: payload[4] & (1 << 6) > 0,
+            primary_use_case: payload[4] & 0x1f,
+            container_id
+        })
+    }
+}
+
+/// The timing for one of the HDMI_VIC codes from an HDMI vendor specific
+/// data block, see `ExtensionBlock::hdmi_vics`. These are the 4K modes of
+/// HDMI 1.4, which have to be signalled with the HDMI vendor InfoFrame
+/// rather than as a VIC in the AVI InfoFrame.
+pub fn hdmi_vic_timing(code: u8) -> Option<DetailedTiming> {
+    use SyncPolarity::*;
+
+    let mode = match code {
+        // 3840x2160 at 30, 25 and 24Hz, the same as VICs 95, 94 and 93.
+        1 => (297000000, (3840, 2160), (176, 8), (88, 10), (296, 72), Positive, Positive),
+        2 => (297000000, (3840, 2160), (1056, 8), (88, 10), (296, 72), Positive, Positive),
+        3 => (297000000, (3840, 2160), (1276, 8), (88, 10), (296, 72), Positive, Positive),
+        // 4096x2160 at 24Hz, the same as VIC 98.
+        4 => (297000000, (4096, 2160), (1020, 8), (88, 10), (296, 72), Positive, Positive),
+        _ => return None
+    };
+
+    Some(progressive_timing(mode))
+}
+
+/// The ways of packing the two views of 3D video into one frame, from the
+/// 3D_Structure fields of the HDMI vendor specific data block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreeDStructure {
+    FramePacking,
+    FieldAlternative,
+    LineAlternative,
+    SideBySideFull,
+    /// 2D video with a depth map.
+    LDepth,
+    /// 2D video and graphics, both with depth maps.
+    LDepthGraphicsDepth,
+    TopAndBottom,
+    /// Side-by-side with half the horizontal resolution for each view.
+    SideBySideHalf
+}
+
+impl ThreeDStructure {
+    /// Every structure, in the order of their codes.
+    pub const ALL: [ThreeDStructure; 8] = [
+        ThreeDStructure::FramePacking,
+        ThreeDStructure::FieldAlternative,
+        ThreeDStructure::LineAlternative,
+        ThreeDStructure::SideBySideFull,
+        ThreeDStructure::LDepth,
+        ThreeDStructure::LDepthGraphicsDepth,
+        ThreeDStructure::TopAndBottom,
+        ThreeDStructure::SideBySideHalf
+    ];
+
+    /// The structure's code, which is also its bit in 3D_Structure_ALL.
+    pub fn code(self) -> u8 {
+        match self {
+            ThreeDStructure::FramePacking => 0,
+            ThreeDStructure::FieldAlternative => 1,
+            ThreeDStructure::LineAlternative => 2,
+            ThreeDStructure::SideBySideFull => 3,
+            ThreeDStructure::LDepth => 4,
+            ThreeDStructure::LDepthGraphicsDepth => 5,
+            ThreeDStructure::TopAndBottom => 6,
+            ThreeDStructure::SideBySideHalf => 8
+        }
+    }
+
+    /// The structure with a code, or `None` for reserved codes.
+    pub fn from_code(code: u8) -> Option<ThreeDStructure> {
+        ThreeDStructure::ALL.iter().copied().find(|structure| structure.code() == code)
+    }
+}
+
+// The formats a display with 3D_present has to take in 3D, if it takes them
+// at all: frame packing and top-and-bottom for 1080p24, 720p60 and 720p50,
+// and side-by-side (half) for 1080i60 and 1080i50.
+const MANDATORY_3D: [(u8, ThreeDStructure); 8] = [
+    (32, ThreeDStructure::FramePacking),
+    (32, ThreeDStructure::TopAndBottom),
+    (4, ThreeDStructure::FramePacking),
+    (4, ThreeDStructure::TopAndBottom),
+    (19, ThreeDStructure::FramePacking),
+    (19, ThreeDStructure::TopAndBottom),
+    (5, ThreeDStructure::SideBySideHalf),
+    (20, ThreeDStructure::SideBySideHalf)
+];
+
+// The sample rates in Hz of the bits of a short audio descriptor.
+const SAMPLE_RATES: [u32; 7] = [32000, 44100, 48000, 88200, 96000, 176400, 192000];
+
+// The sample rates the basic audio flag promises.
+const BASIC_AUDIO_RATES: [u32; 3] = [32000, 44100, 48000];
+
+/// The audio formats of short audio descriptors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Lpcm,
+    Ac3,
+    Mpeg1,
+    Mp3,
+    Mpeg2,
+    AacLc,
+    Dts,
+    Atrac,
+    OneBitAudio,
+    EnhancedAc3,
+    DtsHd,
+    /// Dolby TrueHD and MAT.
+    Mat,
+    Dst,
+    WmaPro,
+    /// A format given by an extension type code, such as 11 for MPEG-H 3D
+    /// Audio or 12 for AC-4.
+    Extended(u8),
+    /// The reserved format code 0.
+    Reserved
+}
+
+impl AudioFormat {
+    fn from_code(code: u8, extension: u8) -> AudioFormat {
+        use AudioFormat::*;
+
+        match code {
+            1 => Lpcm,
+            2 => Ac3,
+            3 => Mpeg1,
+            4 => Mp3,
+            5 => Mpeg2,
+            6 => AacLc,
+            7 => Dts,
+            8 => Atrac,
+            9 => OneBitAudio,
+            10 => EnhancedAc3,
+            11 => DtsHd,
+            12 => Mat,
+            13 => Dst,
+            14 => WmaPro,
+            15 => Extended(extension),
+            _ => Reserved
+        }
+    }
+}
+
+/// A short audio descriptor, one of the formats
+/// listed in an audio data block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShortAudioDescriptor {
+    pub format: AudioFormat,
+    pub max_channels: u8,
+    /// The supported sample rates, bit 0 for 32kHz up to bit 6
+    /// for 192kHz. See `sample_rates_hz`.
+    pub sample_rates: u8,
+    /// The third byte, whose meaning depends on the format. For L-PCM it
+    /// gives the bit depths, see `lpcm_bit_depths`, and for formats 2 to
+    /// 8 the maximum bit rate in units of 8kbit/s.
+    pub detail: u8
+}
+
+impl ShortAudioDescriptor {
+    fn parse(sad: &[u8]) -> ShortAudioDescriptor {
+        ShortAudioDescriptor {
+            format: AudioFormat::from_code((sad[0] >> 3) & 0x0f, sad[2] >> 3),
+            max_channels: (sad[0] & 0x07) + 1,
+            sample_rates: sad[1] & 0x7f,
+            detail: sad[2]
+        }
+    }
+
+    /// The supported sample rates in Hz, lowest first.
+    pub fn sample_rates_hz(&self) -> Vec<u32> {
+        SAMPLE_RATES.iter().enumerate()
+            .filter(|&(i, _)| self.sample_rates & (1 << i) > 0)
+            .map(|(_, &rate)| rate)
+            .collect()
+    }
+
+    /// Whether `hz` is one of the supported sample rates.
+    pub fn supports_sample_rate(&self, hz: u32) -> bool {
+        SAMPLE_RATES.iter().position(|&rate| rate == hz).is_some_and(|i| self.sample_rates & (1 << i) > 0)
+    }
+
+    /// The supported bit depths of an L-PCM descriptor, out of 16, 20 and 24.
+    pub fn lpcm_bit_depths(&self) -> Vec<u8> {
+        if self.format != AudioFormat::Lpcm {
+            return Vec::new();
+        }
+
+        [16, 20, 24].iter().enumerate()
+            .filter(|&(i, _)| self.detail & (1 << i) > 0)
+            .map(|(_, &depth)| depth)
+            .collect()
+    }
+}
+
+/// The speakers of a display or receiver, from the speaker allocation
+/// data block. The bits are in the order of `SpeakerAllocation::positions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpeakerAllocation(pub [u8; 3]);
+
+impl SpeakerAllocation {
+    // Some displays give only the first byte, the rest being zero.
+    fn parse(payload: &[u8]) -> Option<SpeakerAllocation> {
+        if payload.is_empty() {
+            return None;
+        }
+
+        let mut bytes = [0u8; 3];
+        let len = core::cmp::min(payload.len(), 3);
+        bytes[..len].copy_from_slice(&payload[..len]);
+        Some(SpeakerAllocation(bytes))
+    }
+
+    /// The speaker positions which are present, with pairs of speakers
+    /// given as their left and right positions.
+    pub fn positions(&self) -> Vec<SpeakerPosition> {
+        let bits = u32::from_le_bytes([self.0[0], self.0[1], self.0[2], 0]);
+        SPEAKER_BITS.iter().enumerate()
+            .filter(|&(i, _)| bits & (1 << i) > 0)
+            .flat_map(|(_, positions)| positions.iter().copied())
+            .collect()
+    }
+
+    /// The speakers counted in the usual channel notation.
+    pub fn layout(&self) -> SpeakerLayout {
+        let mut layout = SpeakerLayout { main: 0, lfe: 0, height: 0 };
+        for position in self.positions() {
+            if position.is_lfe() {
+                layout.lfe += 1;
+            } else if position.is_height() {
+                layout.height += 1;
+            } else {
+                layout.main += 1;
+            }
+        }
+        layout
+    }
+}
+
+/// A speaker count like 5.1 or 7.1.4: the speakers at ear level and
+/// below, the subwoofers and the overhead speakers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpeakerLayout {
+    pub main: u8,
+    pub lfe: u8,
+    pub height: u8
+}
+
+impl core::fmt::Display for SpeakerLayout {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if self.height > 0 {
+            write!(f, "{}.{}.{}", self.main, self.lfe, self.height)
+        } else {
+            write!(f, "{}.{}", self.main, self.lfe)
+        }
+    }
+}
+
+/// A speaker position from a speaker allocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeakerPosition {
+    FrontLeft,
+    FrontRight,
+    LowFrequencyEffects1,
+    FrontCenter,
+    BackLeft,
+    BackRight,
+    BackCenter,
+    FrontLeftOfCenter,
+    FrontRightOfCenter,
+    RearLeftOfCenter,
+    RearRightOfCenter,
+    FrontLeftWide,
+    FrontRightWide,
+    TopFrontLeft,
+    TopFrontRight,
+    TopCenter,
+    TopFrontCenter,
+    LeftSurround,
+    RightSurround,
+    LowFrequencyEffects2,
+    TopBackCenter,
+    SideLeft,
+    SideRight,
+    TopSideLeft,
+    TopSideRight,
+    TopBackLeft,
+    TopBackRight,
+    BottomFrontCenter,
+    BottomFrontLeft,
+    BottomFrontRight,
+    TopLeftSurround,
+    TopRightSurround,
+    LeftSurroundDirect,
+    RightSurroundDirect
+}
+
+impl SpeakerPosition {
+    /// The name of the position, such as "Top Front Left".
+    pub fn name(&self) -> &'static str {
+        use SpeakerPosition::*;
+
+        match self {
+            FrontLeft => "Front Left",
+            FrontRight => "Front Right",
+            LowFrequencyEffects1 => "Low Frequency Effects 1",
+            FrontCenter => "Front Center",
+            BackLeft => "Back Left",
+            BackRight => "Back Right",
+            BackCenter => "Back Center",
+            FrontLeftOfCenter => "Front Left of Center",
+            FrontRightOfCenter => "Front Right of Center",
+            RearLeftOfCenter => "Rear Left of Center",
+            RearRightOfCenter => "Rear Right of Center",
+            FrontLeftWide => "Front Left Wide",
+            FrontRightWide => "Front Right Wide",
+            TopFrontLeft => "Top Front Left",
+            TopFrontRight => "Top Front Right",
+            TopCenter => "Top Center",
+            TopFrontCenter => "Top Front Center",
+            LeftSurround => "Left Surround",
+            RightSurround => "Right Surround",
+            LowFrequencyEffects2 => "Low Frequency Effects 2",
+            TopBackCenter => "Top Back Center",
+            SideLeft => "Side Left",
+            SideRight => "Side Right",
+            TopSideLeft => "Top Side Left",
+            TopSideRight => "Top Side Right",
+            TopBackLeft => "Top Back Left",
+            TopBackRight => "Top Back Right",
+            BottomFrontCenter => "Bottom Front Center",
+            BottomFrontLeft => "Bottom Front Left",
+            BottomFrontRight => "Bottom Front Right",
+            TopLeftSurround => "Top Left Surround",
+            TopRightSurround => "Top Right Surround",
+            LeftSurroundDirect => "Left Surround Direct",
+            RightSurroundDirect => "Right Surround Direct"
+        }
+    }
+
+    /// Whether this is a subwoofer channel.
+    pub fn is_lfe(&self) -> bool {
+        matches!(self, SpeakerPosition::LowFrequencyEffects1 | SpeakerPosition::LowFrequencyEffects2)
+    }
+
+    /// Whether this is an overhead speaker.
+    pub fn is_height(&self) -> bool {
+        use SpeakerPosition::*;
+
+        matches!(self,
+            TopFrontLeft | TopFrontRight | TopCenter | TopFrontCenter | TopBackCenter |
+            TopSideLeft | TopSideRight | TopBackLeft | TopBackRight | TopLeftSurround | TopRightSurround)
+    }
+}
+
+// The positions of each bit of a speaker allocation, from CTA-861-H.
+const SPEAKER_BITS: [&[SpeakerPosition]; 21] = {
+    use SpeakerPosition::*;
+
+    [
+        &[FrontLeft, FrontRight],
+        &[LowFrequencyEffects1],
+        &[FrontCenter],
+        &[BackLeft, BackRight],
+        &[BackCenter],
+        &[FrontLeftOfCenter, FrontRightOfCenter],
+        &[RearLeftOfCenter, RearRightOfCenter],
+        &[FrontLeftWide, FrontRightWide],
+        &[TopFrontLeft, TopFrontRight],
+        &[TopCenter],
+        &[TopFrontCenter],
+        &[LeftSurround, RightSurround],
+        &[LowFrequencyEffects2],
+        &[TopBackCenter],
+        &[SideLeft, SideRight],
+        &[TopSideLeft, TopSideRight],
+        &[TopBackLeft, TopBackRight],
+        &[BottomFrontCenter],
+        &[BottomFrontLeft, BottomFrontRight],
+        &[TopLeftSurround, TopRightSurround],
+        &[LeftSurroundDirect, RightSurroundDirect]
+    ]
+};
+
+/// Something wrong with the CTA-861 extensions, from `EDID::check_cta`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CtaIssue {
+    pub rule: CtaRule,
+    /// The extension block it is in, 1 being the first.
+    pub block: usize
+}
+
+/// How much a `CtaIssue` matters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Allowed, but likely to confuse some sources.
+    Warning,
+    /// Against CTA-861 or HDMI.
+    Error
+}
+
+/// The checks made by `EDID::check_cta`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CtaRule {
+    /// Basic audio is set but no short audio descriptors are given.
+    BasicAudioWithoutSads,
+    /// Short audio descriptors are given but basic audio is not set.
+    SadsWithoutBasicAudio,
+    /// The count of native detailed timings is more than
+    /// there are detailed timings.
+    NativeDtdCount,
+    /// YCbCr is flagged in the extension but a 1.4 base block
+    /// says the input only takes RGB.
+    YcbcrOnRgbSink,
+    /// There is an HDMI Forum block but no HDMI vendor specific data block.
+    MissingHdmiVsdb,
+    /// A short video descriptor gives VIC 0.
+    SvdVicZero,
+    /// The YCbCr 4:2:0 capability map has bits past the short video descriptors.
+    Y420CmdbPastSvds
+}
+
+impl CtaRule {
+    /// A name for the rule which stays the same between versions.
+    pub fn id(&self) -> &'static str {
+        match self {
+            CtaRule::BasicAudioWithoutSads => "cta-basic-audio-without-sads",
+            CtaRule::SadsWithoutBasicAudio => "cta-sads-without-basic-audio",
+            CtaRule::NativeDtdCount => "cta-native-dtd-count",
+            CtaRule::YcbcrOnRgbSink => "cta-ycbcr-on-rgb-sink",
+            CtaRule::MissingHdmiVsdb => "cta-missing-hdmi-vsdb",
+            CtaRule::SvdVicZero => "cta-svd-vic-zero",
+            CtaRule::Y420CmdbPastSvds => "cta-y420cmdb-past-svds"
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            CtaRule::BasicAudioWithoutSads | CtaRule::Y420CmdbPastSvds => Severity::Warning,
+            _ => Severity::Error
+        }
+    }
+}
+
+/// Microsoft's vendor specific data block for specialized displays.
+#[derive(Debug, Clone, Copy)]
+pub struct MicrosoftVsdb {
+    /// 1 and 2 are head mounted displays, 3 adds `desktop_usage`.
+    pub version: u8,
+    /// Whether the display may be used as part of the desktop.
+    pub desktop_usage: bool,
+    /// What the display is meant for, such as 0x07 for
+    /// virtual reality headsets or 0x02 for generic displays.
+    pub primary_use_case: u8,
+    /// Identifies the display across all its connections.
+    pub container_id: [u8; 16]
+}
+
+/// AMD's vendor specific data block, which FreeSync displays use to give
+/// their variable refresh range. The layout is not public, this follows
+/// what has been worked out from real displays.
+#[derive(Debug, Clone)]
+pub enum AmdVsdb {
+    /// FreeSync 1.x and 2.x blocks.
+    FreeSync {
+        /// Major and minor version.
+        version: (u8, u8),
+        /// The variable refresh range in Hz.
+        min_refresh: u8,
+        max_refresh: u8,
+        /// FreeSync 1.x flags, some of which mean the range
+        /// is switched over MCCS.
+        flags: u8,
+        /// FreeSync 2.x flags, if the block is long enough to have them.
+        flags_2: Option<u8>
+    },
+    /// A version whose layout is not known, with the payload after the OUI.
+    Unknown {
+        version: u8,
+        data: Vec<u8>
+    }
+}
+
+impl AmdVsdb {
+    /// The variable refresh range in Hz, if the layout is known.
+    pub fn refresh_range(&self) -> Option<(u8, u8)> {
+        match *self {
+            AmdVsdb::FreeSync { min_refresh, max_refresh, .. } => Some((min_refresh, max_refresh)),
+            AmdVsdb::Unknown { .. } => None
+        }
+    }
+
+    /// Whether the range is wide enough for low framerate compensation,
+    /// which repeats frames below the minimum. This is not flagged in the
+    /// block, drivers enable it when the maximum is at least twice the minimum.
+    pub fn supports_lfc(&self) -> bool {
+        self.refresh_range().is_some_and(|(min, max)| min > 0 && max as u32 >= 2 * min as u32)
+    }
+}
+
+/// One descriptor of an Adaptive-Sync data block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdaptiveSyncDescriptor {
+    Range {
+        /// Whether this is the range of the panel itself, rather than one
+        /// the display reaches by other means such as frame doubling.
+        native_panel: bool,
+        /// Whether the vertical total may change from frame to frame, rather
+        /// than only being fixed on average.
+        adaptive_vtotal: bool,
+        /// Whether the display can move between refresh rates without
+        /// visible artifacts.
+        seamless_transition: bool,
+        /// How much longer and shorter one frame may be than the previous
+        /// one, in units of 0.25ms.
+        max_increase: u8,
+        max_decrease: u8,
+        /// The refresh range in Hz.
+        min_refresh: u8,
+        max_refresh: u16,
+        /// Bytes added to the descriptor by later revisions.
+        extra: Vec<u8>
+    },
+    /// A descriptor with a reserved vertical total type, kept as it is.
+    Unknown(Vec<u8>)
+}
+
+impl AdaptiveSyncDescriptor {
+    fn parse(data: &[u8]) -> AdaptiveSyncDescriptor {
+        let flags = data[0];
+        if (flags >> 2) & 0x03 > 1 {
+            return AdaptiveSyncDescriptor::Unknown(data.to_vec());
+        }
+
+        AdaptiveSyncDescriptor::Range {
+            native_panel: flags & 1 > 0,
+            adaptive_vtotal: (flags >> 2) & 0x03 == 1,
+            seamless_transition: flags & (1 << 4) > 0,
+            max_increase: data[1],
+            max_decrease: data[5],
+            min_refresh: data[2],
+            max_refresh: 1 + (((data[4] & 0x03) as u16) << 8 | data[3] as u16),
+            extra: data[6..].to_vec()
+        }
+    }
+}
+
+/// The Dolby Vision vendor specific video data block.
+#[derive(Debug, Clone)]
+pub enum DolbyVision {
+    /// Versions 0, 1 and 2.
+    Capability(DolbyVisionCapability),
+    /// A version whose layout is not known, with the payload after the OUI.
+    Unknown {
+        version: u8,
+        data: Vec<u8>
+    }
+}
+
+/// What a Dolby Vision display supports. Fields which a version of the
+/// block does not have are left false or empty.
+#[derive(Debug, Clone)]
+pub struct DolbyVisionCapability {
+    /// The version of the block, 0 to 2.
+    pub version: u8,
+    /// The display management version. Only version 0 gives the minor version.
+    pub dm_version: (u8, u8),
+    /// Whether 12-bit YCbCr 4:2:2 is supported, as well as the usual tunnelling.
+    pub yuv422_12bit: bool,
+    pub supports_2160p60: bool,
+    pub global_dimming: bool,
+    pub backlight_control: bool,
+    /// The minimum and maximum luminance in cd/m².
+    pub target_luminance: (f32, f32),
+    pub interface: DolbyVisionInterface,
+    /// Whether 10 or 12-bit YCbCr 4:4:4 is supported.
+    pub yuv444_bits: Option<u8>,
+    /// The red, green and blue primaries, if the block gives them plainly.
+    pub primaries: Option<[(f32, f32); 3]>,
+    /// The payload after the OUI.
+    pub data: Vec<u8>
+}
+
+/// The ways a Dolby Vision display can be sent video.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DolbyVisionInterface {
+    /// Tunnelled in 8-bit RGB, with the display doing the tone mapping.
+    pub standard: bool,
+    /// Already tone mapped by the source.
+    pub low_latency: bool,
+    /// Low latency, signalled with HDMI InfoFrames.
+    pub low_latency_hdmi: bool
+}
+
+// Convert a 12-bit SMPTE ST 2084 (PQ) code to cd/m².
+fn pq_to_nits(code: u16) -> f32 {
+    const M1: f64 = 2610.0 / 16384.0;
+    const M2: f64 = 2523.0 / 4096.0 * 128.0;
+    const C1: f64 = 3424.0 / 4096.0;
+    const C2: f64 = 2413.0 / 4096.0 * 32.0;
+    const C3: f64 = 2392.0 / 4096.0 * 32.0;
+
+    let e = powf(code as f64 / 4095.0, 1.0 / M2);
+    let numerator = if e > C1 { e - C1 } else { 0.0 };
+    (10000.0 * powf(numerator / (C2 - C3 * e), 1.0 / M1)) as f32
+}
+
+// `f64::powf` for a base from 0 to 1, which `core` does not have.
+fn powf(base: f64, exponent: f64) -> f64 {
+    if base <= 0.0 {
+        return 0.0;
+    }
+
+    // Split the base into a mantissa from 1 to 2 and a power of two,
+    // then use ln(m) = 2 atanh((m - 1) / (m + 1)).
+    let bits = base.to_bits();
+    let power = ((bits >> 52) & 0x7ff) as i64 - 1023;
+    let mantissa = f64::from_bits((bits & ((1 << 52) - 1)) | (1023 << 52));
+    let s = (mantissa - 1.0) / (mantissa + 1.0);
+    let mut ln = 0.0;
+    let mut term = s;
+    for n in 0..20 {
+        ln += term / (2 * n + 1) as f64;
+        term *= s * s;
+    }
+    let ln = 2.0 * ln + power as f64 * core::f64::consts::LN_2;
+
+    // And exp(x) = 2^k e^r with r small enough for the series.
+    let x = exponent * ln;
+    let k = (x / core::f64::consts::LN_2) as i64 - if x < 0.0 { 1 } else { 0 };
+    if k < -1022 {
+        return 0.0;
+    }
+    let r = x - k as f64 * core::f64::consts::LN_2;
+    let (mut exp, mut term) = (1.0, 1.0);
+    for n in 1..20 {
+        term *= r / n as f64;
+        exp += term;
+    }
+    exp * f64::from_bits(((k + 1023) as u64) << 52)
+}
+
+/// The HDR static metadata data block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HdrStaticMetadata {
+    /// The supported transfer functions: bit 0 is SDR, bit 1 traditional
+    /// HDR, bit 2 SMPTE ST 2084 (PQ) and bit 3 HLG.
+    pub eotfs: u8,
+    /// The supported static metadata types, bit 0 being type 1.
+    pub static_metadata_types: u8,
+    /// The desired content luminance code values, if the block gives them.
+    /// See `max_luminance_nits` and the like for their values in cd/m².
+    pub max_luminance: Option<u8>,
+    pub max_frame_avg: Option<u8>,
+    pub min_luminance: Option<u8>
+}
+
+impl HdrStaticMetadata {
+    /// Whether the SMPTE ST 2084 (PQ) transfer function is supported.
+    pub fn supports_pq(&self) -> bool {
+        self.eotfs & (1 << 2) > 0
+    }
+
+    /// The desired content maximum luminance in cd/m², 50·2^(CV/32).
+    pub fn max_luminance_nits(&self) -> Option<f32> {
+        self.max_luminance.map(luminance_to_nits)
+    }
+
+    /// The desired content maximum frame-average luminance in cd/m²,
+    /// coded like the maximum luminance.
+    pub fn max_frame_avg_nits(&self) -> Option<f32> {
+        self.max_frame_avg.map(luminance_to_nits)
+    }
+
+    /// The desired content minimum luminance in cd/m², which is given
+    /// relative to the maximum as max·(CV/255)²/100.
+    pub fn min_luminance_nits(&self) -> Option<f32> {
+        let max = self.max_luminance_nits()?;
+        let ratio = self.min_luminance? as f32 / 255.0;
+        Some(max * ratio * ratio / 100.0)
+    }
+}
+
+// Convert a maximum luminance code value to cd/m², 50·2^(CV/32).
+fn luminance_to_nits(code: u8) -> f32 {
+    (50.0 / powf(0.5, code as f64 / 32.0)) as f32
+}
+
+/// The HDR10+ vendor specific video data block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hdr10PlusCapability {
+    /// The version of the HDR10+ application the display supports.
+    pub application_version: u8,
+    /// The payload after the OUI, as later versions add fields.
+    pub data: Vec<u8>
+}
+
+/// A variable refresh range, from `EDID::vrr_range`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VrrRange {
+    /// Refresh rates in Hz.
+    pub min: u16,
+    pub max: u16,
+    /// Where the range came from.
+    pub source: VrrSource,
+    /// Whether the sources disagreed.
+    pub conflict: bool
+}
+
+/// The places a variable refresh range can come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VrrSource {
+    /// The HDMI Forum vendor specific or sink capability data block.
+    HdmiForum,
+    /// The Adaptive-Sync data block of a DisplayID extension.
+    AdaptiveSync,
+    /// The AMD FreeSync vendor specific data block.
+    FreeSync,
+    /// The range limits descriptor.
+    RangeLimits
+}
+
+/// Details of the audio connection which the EDID cannot know,
+/// used with `EDID::to_eld`.
+#[derive(Debug, Clone, Default)]
+pub struct EldPort {
+    pub connection: EldConnection,
+    /// Identifies the connection to the audio driver.
+    pub port_id: u64,
+    /// Extra audio delay on the source side in units of 2ms.
+    pub audio_sync_delay: u8
+}
+
+/// The kind of link carrying the audio.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EldConnection {
+    #[default]
+    Hdmi,
+    DisplayPort
+}
+
+// Head mounted displays with no Microsoft data block, as inclusive product
+// code ranges. This is the list the Linux kernel marks as non-desktop.
+const NON_DESKTOP_QUIRKS: &[((char, char, char), u16, u16)] = &[
+    // HTC Vive and Vive Pro.
+    (('H', 'V', 'R'), 0xaa01, 0xaa02),
+    // Oculus Rift DK1, DK2, CV1 and Rift S.
+    (('O', 'V', 'R'), 0x0001, 0x0001),
+    (('O', 'V', 'R'), 0x0003, 0x0004),
+    (('O', 'V', 'R'), 0x0012, 0x0012),
+    // Windows Mixed Reality headsets.
+    (('A', 'C', 'R'), 0x7fce, 0x7fce),
+    (('L', 'E', 'N'), 0x0408, 0x0408),
+    (('F', 'U', 'J'), 0x1970, 0x1970),
+    (('D', 'E', 'L'), 0x7fce, 0x7fce),
+    (('S', 'E', 'C'), 0x144a, 0x144a),
+    (('A', 'U', 'S'), 0xc102, 0xc102),
+    // Sony PlayStation VR.
+    (('S', 'N', 'Y'), 0x0704, 0x0704),
+    // Sensics, OSVR HDK and HDK2.
+    (('S', 'E', 'N'), 0x1019, 0x1019),
+    (('S', 'V', 'R'), 0x1019, 0x1019),
+    (('A', 'U', 'O'), 0x1111, 0x1111),
+    // Valve Index.
+    (('V', 'L', 'V'), 0x91a8, 0x91a8),
+    (('V', 'L', 'V'), 0x91b0, 0x91be)
+];
@@ -0,0 +1,155 @@
+//! Checking EDIDs at compile time, for firmware which embeds a fixed blob.
+//!
+//! Use the `edid!` macro, which runs the checks here in a constant so that
+//! an invalid blob fails the build:
+//!
+//! ```rust,ignore
+//! static EDID: [u8; 128] = edid_rs::edid!(include_bytes!("panel.bin"));
+//! static OTHER: [u8; 128] = edid_rs::edid!("00ffffffffffff00...");
+//! ```
+//!
+//! The checks cover the framing of the data rather than every field, since
+//! the parser itself cannot run in a constant.
+
+/// Check the framing of a raw EDID, panicking with a description of the
+/// first problem. In a constant the panic becomes a compile error.
+///
+/// This checks the length, the header, the version, the extension count
+/// and the checksum of every block.
+pub const fn check(bytes: &[u8]) {
+    if bytes.len() < 128 {
+        panic!("EDID is shorter than one 128-byte block");
+    }
+
+    let header = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+    let mut i = 0;
+    while i < 8 {
+        if bytes[i] != header[i] {
+            panic!("EDID header (bytes 0-7) is not 00 ff ff ff ff ff ff 00");
+        }
+        i += 1;
+    }
+
+    if bytes[18] != 1 {
+        panic!("EDID version (byte 18) is not 1");
+    }
+
+    if bytes.len() != 128 * (1 + bytes[126] as usize) {
+        panic!("EDID length does not match the extension count (byte 126)");
+    }
+
+    let mut block = 0;
+    while block * 128 < bytes.len() {
+        let mut sum = 0u8;
+        let mut i = 0;
+        while i < 128 {
+            sum = sum.wrapping_add(bytes[block * 128 + i]);
+            i += 1;
+        }
+
+        if sum != 0 {
+            if block == 0 {
+                panic!("EDID checksum (byte 127) of the base block is wrong");
+            } else {
+                panic!("EDID checksum (byte 127) of an extension block is wrong");
+            }
+        }
+        block += 1;
+    }
+}
+
+/// The active size of the preferred (first detailed) timing in pixels and
+/// lines, for use in constants. `(0, 0)` if the first descriptor is not a
+/// detailed timing.
+pub const fn preferred_active(bytes: &[u8]) -> (u16, u16) {
+    if bytes[54] == 0 && bytes[55] == 0 {
+        return (0, 0);
+    }
+
+    (
+        bytes[56] as u16 | (((bytes[58] & 0xf0) as u16) << 4),
+        bytes[59] as u16 | (((bytes[61] & 0xf0) as u16) << 4)
+    )
+}
+
+/// The number of bytes in a string of hex digits, which may be separated
+/// by whitespace.
+pub const fn hex_len(text: &str) -> usize {
+    let text = text.as_bytes();
+    let mut digits = 0;
+    let mut i = 0;
+    while i < text.len() {
+        if hex_digit(text[i]).is_some() {
+            digits += 1;
+        } else if !text[i].is_ascii_whitespace() {
+            panic!("Invalid digit in hex EDID");
+        }
+        i += 1;
+    }
+
+    if digits % 2 != 0 {
+        panic!("Odd number of digits in hex EDID");
+    }
+
+    digits / 2
+}
+
+/// Decode a string of hex digits, which may be separated by whitespace,
+/// into `N` bytes where `N` is `hex_len(text)`.
+pub const fn decode_hex<const N: usize>(text: &str) -> [u8; N] {
+    let text = text.as_bytes();
+    let mut out = [0u8; N];
+    let mut digits = 0;
+    let mut i = 0;
+    while i < text.len() {
+        if let Some(digit) = hex_digit(text[i]) {
+            out[digits / 2] = (out[digits / 2] << 4) | digit;
+            digits += 1;
+        }
+        i += 1;
+    }
+
+    out
+}
+
+/// Copy the bytes into an array, `N` being the length of `bytes`.
+pub const fn to_array<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut out = [0u8; N];
+    let mut i = 0;
+    while i < N {
+        out[i] = bytes[i];
+        i += 1;
+    }
+
+    out
+}
+
+const fn hex_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None
+    }
+}
+
+/// Embed an EDID as a `[u8; N]`, checking it at compile time with
+/// `embed::check`. Takes a hex string literal, `include_bytes!(...)`
+/// or any constant byte array expression.
+#[macro_export]
+macro_rules! edid {
+    ($hex:literal) => {{
+        const BYTES: [u8; $crate::embed::hex_len($hex)] = $crate::embed::decode_hex($hex);
+        const _: () = $crate::embed::check(&BYTES);
+        BYTES
+    }};
+    (include_bytes!($path:expr)) => {
+        $crate::edid!(*include_bytes!($path))
+    };
+    ($bytes:expr) => {{
+        const SLICE: &[u8] = &$bytes;
+        const _: () = $crate::embed::check(SLICE);
+        const BYTES: [u8; SLICE.len()] = $crate::embed::to_array(SLICE);
+        BYTES
+    }};
+}
@@ -0,0 +1,215 @@
+//! A C interface to the parser, see `include/edid_rs.h`.
+//!
+//! Build it as a shared or static library with
+//! `cargo rustc --release --features ffi --crate-type cdylib` (or `staticlib`).
+//!
+//! Every function checks its pointer arguments for null and reports
+//! problems through its return code, which `edid_error_message` describes.
+
+use std::os::raw::{c_char, c_float, c_int};
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice};
+
+use crate::{EDID, Reader, MonitorDescriptor};
+
+pub const EDID_OK: c_int = 0;
+/// A required pointer argument was null.
+pub const EDID_ERROR_NULL_POINTER: c_int = 1;
+/// The data is not a valid EDID.
+pub const EDID_ERROR_PARSE: c_int = 2;
+/// The EDID does not contain the requested information.
+pub const EDID_ERROR_NOT_PRESENT: c_int = 3;
+/// The caller's buffer cannot hold the result and its NUL terminator.
+pub const EDID_ERROR_BUFFER_TOO_SMALL: c_int = 4;
+
+/// A parsed EDID, owned by the caller until passed to `edid_free`.
+pub struct EdidHandle(EDID);
+
+/// Describe one of the `EDID_*` return codes. The returned string
+/// is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn edid_error_message(code: c_int) -> *const c_char {
+    let message: &'static [u8] = match code {
+        EDID_OK => b"success\0",
+        EDID_ERROR_NULL_POINTER => b"a required pointer argument was null\0",
+        EDID_ERROR_PARSE => b"the data is not a valid EDID\0",
+        EDID_ERROR_NOT_PRESENT => b"the EDID does not contain this information\0",
+        EDID_ERROR_BUFFER_TOO_SMALL => b"the buffer is too small\0",
+        _ => b"unknown error code\0"
+    };
+
+    message.as_ptr() as *const c_char
+}
+
+/// Parse `len` bytes at `data`, storing a new handle in `*out` on success.
+///
+/// # Safety
+///
+/// `data` must point to at least `len` readable bytes and
+/// `out` must be valid for a pointer-sized write.
+#[no_mangle]
+pub unsafe extern "C" fn edid_parse(data: *const u8, len: usize, out: *mut *mut EdidHandle) -> c_int {
+    if data.is_null() || out.is_null() {
+        return EDID_ERROR_NULL_POINTER;
+    }
+
+    *out = ptr::null_mut();
+    let bytes = slice::from_raw_parts(data, len);
+
+    // Never let a panic unwind into C.
+    let parsed = panic::catch_unwind(AssertUnwindSafe(|| {
+        EDID::parse(&mut Reader::new(&mut &bytes[..]))
+    }));
+
+    match parsed {
+        Ok(Ok(edid)) => {
+            *out = Box::into_raw(Box::new(EdidHandle(edid)));
+            EDID_OK
+        },
+        _ => EDID_ERROR_PARSE
+    }
+}
+
+/// Free a handle returned by `edid_parse`. Passing null does nothing.
+///
+/// # Safety
+///
+/// `handle` must be null or a handle from `edid_parse`
+/// which has not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn edid_free(handle: *mut EdidHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Write the three letter manufacturer ID as a NUL terminated string,
+/// `buf` must have room for at least 4 bytes.
+///
+/// # Safety
+///
+/// `handle` must be a live handle and `buf` must be valid for `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn edid_manufacturer(handle: *const EdidHandle, buf: *mut c_char, len: usize) -> c_int {
+    if handle.is_null() {
+        return EDID_ERROR_NULL_POINTER;
+    }
+
+    let id = (*handle).0.product.manufacturer_id;
+    let mut text = [0u8; 12];
+    let mut n = 0;
+    for c in &[id.0, id.1, id.2] {
+        n += c.encode_utf8(&mut text[n..]).len();
+    }
+
+    copy_string(&text[..n], buf, len)
+}
+
+/// Store the product code in `*out`.
+///
+/// # Safety
+///
+/// `handle` must be a live handle and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn edid_product_code(handle: *const EdidHandle, out: *mut u16) -> c_int {
+    if handle.is_null() || out.is_null() {
+        return EDID_ERROR_NULL_POINTER;
+    }
+
+    *out = (*handle).0.product.product_code;
+    EDID_OK
+}
+
+/// Store the active size and pixel clock of the preferred
+/// (first detailed) timing. Any output pointer may be null.
+///
+/// # Safety
+///
+/// `handle` must be a live handle and each non-null
+/// output pointer must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn edid_preferred_mode(
+    handle: *const EdidHandle, width: *mut u16, height: *mut u16, pixel_clock_hz: *mut u32
+) -> c_int {
+    if handle.is_null() {
+        return EDID_ERROR_NULL_POINTER;
+    }
+
+    let timing = match (*handle).0.timings.detailed_timings.first() {
+        Some(timing) => timing,
+        None => return EDID_ERROR_NOT_PRESENT
+    };
+
+    if !width.is_null() {
+        *width = timing.active.0;
+    }
+    if !height.is_null() {
+        *height = timing.active.1;
+    }
+    if !pixel_clock_hz.is_null() {
+        *pixel_clock_hz = timing.pixel_clock;
+    }
+
+    EDID_OK
+}
+
+/// Store the maximum image size in centimetres.
+///
+/// # Safety
+///
+/// `handle` must be a live handle and both
+/// output pointers must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn edid_physical_size(
+    handle: *const EdidHandle, width_cm: *mut c_float, height_cm: *mut c_float
+) -> c_int {
+    if handle.is_null() || width_cm.is_null() || height_cm.is_null() {
+        return EDID_ERROR_NULL_POINTER;
+    }
+
+    match (*handle).0.display.max_size.size() {
+        Some(size) => {
+            *width_cm = size.width;
+            *height_cm = size.height;
+            EDID_OK
+        },
+        None => EDID_ERROR_NOT_PRESENT
+    }
+}
+
+/// Write the monitor name as a NUL terminated UTF-8 string.
+///
+/// # Safety
+///
+/// `handle` must be a live handle and `buf` must be valid for `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn edid_monitor_name(handle: *const EdidHandle, buf: *mut c_char, len: usize) -> c_int {
+    if handle.is_null() {
+        return EDID_ERROR_NULL_POINTER;
+    }
+
+    let name = (*handle).0.descriptors.0.iter().find_map(|d| match d {
+        MonitorDescriptor::MonitorName(name) => Some(name),
+        _ => None
+    });
+
+    match name {
+        Some(name) => copy_string(name.as_bytes(), buf, len),
+        None => EDID_ERROR_NOT_PRESENT
+    }
+}
+
+// Copy `text` and a NUL terminator into a caller supplied buffer.
+unsafe fn copy_string(text: &[u8], buf: *mut c_char, len: usize) -> c_int {
+    if buf.is_null() {
+        return EDID_ERROR_NULL_POINTER;
+    }
+
+    if text.len() >= len {
+        return EDID_ERROR_BUFFER_TOO_SMALL;
+    }
+
+    ptr::copy_nonoverlapping(text.as_ptr(), buf as *mut u8, text.len());
+    *buf.add(text.len()) = 0;
+    EDID_OK
+}
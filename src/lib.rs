@@ -1,13 +1,16 @@
-#![cfg_attr(feature = "no_std", no_std)]
+#![cfg_attr(not(feature = "std"), no_std)]
+// The bit-twiddling below deliberately spells out `>> 0` and `>> 00`
+// so that the field extractions line up with each other.
+#![allow(clippy::identity_op, clippy::zero_prefixed_literal, clippy::type_complexity)]
 
 //! A pure-Rust crate to parse EDID data with `no_std` support. This crate does not include methods for gathering the data from the monitor.
 //! 
-//! To enable `no_std` support, ensure the `alloc` crate is available, use feature `no_std`, and then implement `edid_rs::Read` instead of `std::io::Read` for data sources.
+//! To enable `no_std` support, ensure the `alloc` crate is available, disable the default `std` feature, and then implement `edid_rs::Read` instead of `std::io::Read` for data sources. The old `no_std` feature is deprecated and does nothing. With `std`, byte slices, `Cursor`, `BufReader`, `File` and `Stdin` can be parsed directly, and any other `std::io::Read` can be wrapped in `edid_rs::IoReader`.
 //! 
 //! ### Examples
 //! 
 //! Basic usage:
-//! ```rust
+//! ```rust,ignore
 //! extern crate edid_rs;
 //! 
 //! use std::io::Cursor;
@@ -26,68 +29,310 @@
 //!    Compiling edid-rs v0.1.0 (../edid)
 //!     Finished dev [unoptimized + debuginfo] target(s) in 0.39s
 //!      Running `target/debug/examples/stdin`
-//! Ok(EDID { product: ProductInformation { manufacturer_id: ManufacturerID('\u{4}', '\u{0}', '\u{6}'), product_code: 40994, serial_number: 0, manufacture_date: ManufactureDate { week: 4, year: 2013 } }, version: Version { version: 1, revision: 4 }, display: DisplayParameters { input: Digital { dfp_compatible: true }, max_size: Some(ImageSize { width: 33.0, height: 21.0 }), gamma: Some(2.2), dpms: DPMSFeatures { standby_supported: false, suspend_supported: false, low_power_supported: false, display_type: Monochrome, default_srgb: false, preferred_timing_mode: true, default_gtf_supported: false } }, color: ColorCharacteristics { red: (0.6533203, 0.33398438), green: (0.2998047, 0.6201172), blue: (0.14648438, 0.049804688), white: (0.3125, 0.32910156), white_points: [] }, timings: Timings { established_timings: [], standard_timings: [], detailed_timings: [DetailedTiming { pixel_clock: 337750000, active: (2880, 1800), front_porch: (48, 3), sync_length: (32, 6), back_porch: (80, 43), image_size: ImageSize { width: 33.1, height: 20.7 }, border: (0, 0), interlaced: false, stereo: None, sync_type: Seperate { horizontal: Positive, vertical: Negative } }] }, descriptors: MonitorDescriptors([MonitorName("Color LCD"), ManufacturerDefined(0, [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 16, 0])]), extensions: 0 })
+//! Ok(EDID { product: ProductInformation { manufacturer_id: ManufacturerID('A', 'P', 'P'), product_code: 40994, serial_number: 0, manufacture_date: WeekOfYear { week: 4, year: 2013 } }, version: Version { version: 1, revision: 4 }, display: DisplayParameters { input: Digital { dfp_compatible: false, color_depth: Some(8), interface: Some(DisplayPort) }, max_size: Size(ImageSize { width: 33.0, height: 21.0 }), gamma: Value(2.2), dpms: DPMSFeatures { standby_supported: false, suspend_supported: false, low_power_supported: false, display_color: Encodings(ColorEncodings { ycbcr444: false, ycbcr422: false }), default_srgb: false, preferred_timing_mode: true, frequency_support: None } }, color: ColorCharacteristics { red: (0.6533203, 0.33398438), green: (0.2998047, 0.6201172), blue: (0.14648438, 0.049804688), white: (0.3125, 0.32910156), white_points: [] }, timings: Timings { established_timings: [], manufacturer_timings: 0, standard_timings: [], detailed_timings: [DetailedTiming { pixel_clock: 337750000, active: (2880, 1800), front_porch: (48, 3), sync_length: (32, 6), back_porch: (80, 43), image_size: Some(ImageSize { width: 33.1, height: 20.7 }), border: (0, 0), interlaced: false, stereo: None, sync_type: Seperate { horizontal: Positive, vertical: Negative } }] }, descriptors: MonitorDescriptors([MonitorName("Color LCD")]), extensions: 0, checksum: 222, checksum_ok: true })
 //! ```
 
-/// Trait which all data sources must implement. In a `std` environment,
-/// there is a blanket impl of `edid_rs::Read` for `std::io::Read`.
+/// Trait which all data sources must implement. It is implemented for byte
+/// slices and, in a `std` environment, for the common `std::io::Read` types.
+/// Wrap any other `std::io::Read` in an `IoReader`, which is also how to
+/// update code from before this replaced the impl for every `std::io::Read`.
 pub trait Read {
+    /// Read into `buf`, returning how many bytes were read. `Some(0)` means
+    /// the end of the data and `None` that the source failed.
     fn read(&mut self, buf: &mut [u8]) -> Option<usize>;
 }
 
-#[cfg(not(feature = "no_std"))]
-impl<T: std::io::Read> Read for T {
+// Mirrors `std`'s impl so in-memory data can be parsed without `std::io`.
+impl Read for &[u8] {
     fn read(&mut self, buf: &mut [u8]) -> Option<usize> {
-        self.read(buf).ok()
+        let num = core::cmp::min(buf.len(), self.len());
+        buf[..num].copy_from_slice(&self[..num]);
+        *self = &self[num..];
+        Some(num)
     }
 }
 
-#[cfg(feature = "no_std")]
+/// Adapts any `std::io::Read` into an `edid_rs::Read`.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct IoReader<R>(pub R);
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> Read for IoReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Option<usize> {
+        read_io(&mut self.0, buf)
+    }
+}
+
+// Pipes and character devices such as `/dev/i2c-*` give short reads, so
+// keep reading until `buf` is full or the data ends, retrying interrupted
+// reads. An error after some data is left for the next call to report.
+#[cfg(feature = "std")]
+fn read_io<R: std::io::Read + ?Sized>(r: &mut R, buf: &mut [u8]) -> Option<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match r.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(num) => len += num,
+            Err(ref err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(_) if len > 0 => break,
+            Err(_) => return None
+        }
+    }
+
+    Some(len)
+}
+
+// The readers which EDIDs usually come from, so that they
+// can be used without an `IoReader`.
+#[cfg(feature = "std")]
+macro_rules! impl_read_for_io {
+    ($([$($generic:tt)*] $ty:ty;)*) => {
+        $(
+            impl<$($generic)*> Read for $ty {
+                fn read(&mut self, buf: &mut [u8]) -> Option<usize> {
+                    read_io(self, buf)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "std")]
+impl_read_for_io! {
+    [T: AsRef<[u8]>] std::io::Cursor<T>;
+    [R: std::io::Read] std::io::BufReader<R>;
+    [] std::fs::File;
+    [] std::io::Stdin;
+    ['a] std::io::StdinLock<'a>;
+}
+
+#[cfg(not(feature = "std"))]
 #[macro_use]
 extern crate alloc;
-#[cfg(feature = "no_std")]
-use alloc::{vec::Vec, string::String};
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::Cow, vec::Vec, string::String};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+pub mod embed;
+pub mod patch;
+#[cfg(feature = "cta")]
+mod cta;
+#[cfg(feature = "cta")]
+pub use cta::*;
+mod view;
+pub use view::*;
+mod visit;
+pub use visit::*;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(any(feature = "linux-sysfs", feature = "drm", feature = "i2c-linux"))]
+pub mod linux;
 
 
 /// The type of parsing results.
-pub type Result<T> = core::result::Result<T, &'static str>;
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// What went wrong. Offsets are in bytes from the start of the data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The data source failed to read at `offset`.
+    ReadFailed { offset: usize },
+    /// The data ended at `offset`, `needed` bytes short of a whole block.
+    UnexpectedEof { offset: usize, needed: usize },
+    /// The block at `offset` does not start with the EDID header.
+    InvalidHeader { offset: usize },
+    /// The block at `offset` is all 0x00, as read from a DDC bus held low.
+    EmptyBlock { offset: usize },
+    /// The block at `offset` is all 0xff, as read from a DDC bus left
+    /// floating when nothing answers.
+    BusStuckHigh { offset: usize },
+    /// The data is an EDID structure of a version other than 1, such as
+    /// the 256-byte EDID 2.0.
+    UnsupportedVersion { version: u8, revision: u8 },
+    /// The data is a DisplayID section of `version`, not an EDID.
+    DisplayId { version: u8 },
+    /// The block at `offset` does not sum to zero.
+    InvalidChecksum { offset: usize },
+    /// The first descriptor, at `offset`, is not a detailed timing although
+    /// `DPMSFeatures::preferred_timing_mode` says it is.
+    MissingPreferredTiming { offset: usize },
+    /// The detailed timing at `offset` has less blanking than its sync and front porch.
+    BadTiming { offset: usize },
+    /// The display descriptor at `offset` does not follow the layout for `tag`.
+    BadDescriptor { tag: u8, offset: usize },
+    /// The manufacturer ID at `offset` has letters outside A to Z.
+    BadManufacturerId { offset: usize },
+    /// The week of manufacture at `offset` is past 54 but not 0xff.
+    InvalidWeek { offset: usize, week: u8 },
+    /// The hex or base64 text is invalid at `offset`.
+    InvalidText { offset: usize },
+    /// A timing cannot be worked out or encoded, for the reason given.
+    InvalidTiming(&'static str),
+    /// There is no room left in the block.
+    NoSpace,
+    /// The extension block has the wrong `tag` for the operation.
+    WrongExtension { tag: u8 }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            Error::ReadFailed { offset } => write!(f, "Error reading data at byte {}.", offset),
+            Error::UnexpectedEof { offset, needed } => {
+                write!(f, "Unexpectedly out of data at byte {}, {} more bytes needed.", offset, needed)
+            },
+            Error::InvalidHeader { offset } => write!(f, "Invalid header at byte {}.", offset),
+            Error::EmptyBlock { offset } => {
+                write!(f, "The block at byte {} is all 0x00, check the display is connected properly.", offset)
+            },
+            Error::BusStuckHigh { offset } => {
+                write!(f, "The block at byte {} is all 0xff, check the display is connected properly.", offset)
+            },
+            Error::UnsupportedVersion { version, revision } => {
+                write!(f, "Unsupported EDID version {}.{}.", version, revision)
+            },
+            Error::DisplayId { version } => {
+                write!(f, "This is DisplayID {}.{}, not EDID.", version >> 4, version & 0x0f)
+            },
+            Error::InvalidChecksum { offset } => write!(f, "Invalid checksum for the block at byte {}.", offset),
+            Error::MissingPreferredTiming { offset } => write!(f, "Expected detailed timing block at byte {}.", offset),
+            Error::BadTiming { offset } => {
+                write!(f, "Blanking shorter than sync and front porch in the detailed timing at byte {}.", offset)
+            },
+            Error::BadDescriptor { tag, offset } => {
+                write!(f, "Invalid monitor descriptor with tag {:#04x} at bytes {} to {}.", tag, offset, offset + 17)
+            },
+            Error::BadManufacturerId { offset } => write!(f, "Invalid manufacturer ID at byte {}.", offset),
+            Error::InvalidWeek { offset, week } => write!(f, "Invalid week {} of manufacture at byte {}.", week, offset),
+            Error::InvalidText { offset } => write!(f, "Invalid hex or base64 text at character {}.", offset),
+            Error::InvalidTiming(reason) => f.write_str(reason),
+            Error::NoSpace => f.write_str("No room left in the block."),
+            Error::WrongExtension { tag } => write!(f, "Wrong kind of extension block, with tag {:#04x}.", tag)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// A spec violation which `EDID::parse_lenient` read past.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Warning {
+    /// The field which is wrong, e.g. `"descriptor padding"`.
+    pub field: &'static str,
+    /// Where the field starts.
+    pub offset: usize,
+    /// The violation as an error, which is what strict parsing gives
+    /// unless the violation is harmless.
+    pub error: Error
+}
+
+impl core::fmt::Display for Warning {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Invalid {} at byte {}.", self.field, self.offset)
+    }
+}
 
 // Like `assert!` but returning Err instead of panicking.
-fn ensure(pred: bool, msg: &'static str) -> Result<()> {
+fn ensure(pred: bool, err: Error) -> Result<()> {
     if pred {
         Ok(())
     } else {
-        Err(msg)
+        Err(err)
+    }
+}
+
+// Read the header of a base block. Without one, say what the data
+// is instead if it is a blank block from a dead DDC bus, an EDID 2.0
+// structure or a DisplayID section.
+fn read_header(r: &mut Reader) -> Result<()> {
+    let start = r.offset;
+    let mut header = [0u8; 8];
+    for byte in header.iter_mut() {
+        *byte = r.read_u8()?;
+    }
+
+    if header == [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00] {
+        return Ok(());
+    }
+
+    if header == [header[0]; 8] && (header[0] == 0x00 || header[0] == 0xff) {
+        let mut blank = true;
+        for _ in 8..128 {
+            blank &= r.read_u8()? == header[0];
+        }
+        if blank && header[0] == 0x00 {
+            return Err(Error::EmptyBlock { offset: start });
+        } else if blank {
+            return Err(Error::BusStuckHigh { offset: start });
+        }
+    }
+
+    // A DisplayID section has its version, the length of its data, the
+    // product type and the extension count, and sums to zero over those
+    // and the data and a checksum byte.
+    let length = header[1] as usize + 5;
+    if matches!(header[0], 0x10..=0x13 | 0x20 | 0x21) && length <= 256 && header[2] <= 8 {
+        let mut sum = header.iter().take(length).fold(0u8, |sum, &b| sum.wrapping_add(b));
+        for _ in 8..length {
+            match r.read_u8() {
+                Ok(byte) => sum = sum.wrapping_add(byte),
+                Err(_) => break
+            }
+        }
+        if sum == 0 && r.offset >= start + length {
+            return Err(Error::DisplayId { version: header[0] });
+        }
+    }
+
+    // EDID 2.0 structures start with their version and revision.
+    if header[0] >> 4 == 2 {
+        return Err(Error::UnsupportedVersion { version: header[0] >> 4, revision: header[0] & 0x0f });
     }
+
+    Err(Error::InvalidHeader { offset: start })
 }
 
 /// Used to parse the binary data from a Read value.
 pub struct Reader<'a> {
     // The source we are reading from,
     value: &'a mut dyn Read,
-    // and a 128-byte buffer of data.
-    buffer: Vec<u8>
+    // a 128-byte buffer of data, and the part of it not yet used,
+    buffer: [u8; 128],
+    start: usize,
+    end: usize,
+    // how many bytes have been used so far, and their sum,
+    offset: usize,
+    sum: u8,
+    // and the warnings so far, if parsing leniently.
+    warnings: Option<Vec<Warning>>
 }
 
 impl<'a> Reader<'a> {
     pub fn new<T: Read>(value: &'a mut T) -> Reader<'a> {
         Reader {
-            value: value as &mut dyn Read, buffer: Vec::with_capacity(128)
+            value: value as &mut dyn Read, buffer: [0; 128], start: 0, end: 0, offset: 0, sum: 0, warnings: None
         }
     }
 
     // Get one character from the input.
     fn get(&mut self) -> Result<u8> {
-        if self.buffer.len() == 0 {
-            self.buffer.resize(128, 0);
-            let num = self.value.read(self.buffer.as_mut()).ok_or("Error reading data!")?;
-            self.buffer.truncate(num);
+        if self.start == self.end {
+            self.start = 0;
+            let num = self.value.read(&mut self.buffer).ok_or(Error::ReadFailed { offset: self.offset })?;
+            self.end = core::cmp::min(num, self.buffer.len());
         }
 
-        if self.buffer.len() > 0 {
-            Ok(self.buffer.remove(0))
+        if self.start < self.end {
+            self.start += 1;
+            self.offset += 1;
+            self.sum = self.sum.wrapping_add(self.buffer[self.start - 1]);
+            Ok(self.buffer[self.start - 1])
         } else{
-            Err("Unexpectedly out of data!")
+            // EDIDs come in whole blocks, so say how much of this one is missing.
+            Err(Error::UnexpectedEof { offset: self.offset, needed: 128 - self.offset % 128 })
         }
     }
 
@@ -95,6 +340,56 @@ impl<'a> Reader<'a> {
         self.get()
     }
 
+    // Give `error` for the field at `offset`, or only record a warning
+    // if parsing leniently.
+    fn warn(&mut self, field: &'static str, offset: usize, error: Error) -> Result<()> {
+        match self.warnings {
+            Some(ref mut warnings) => {
+                warnings.push(Warning { field, offset, error });
+                Ok(())
+            },
+            None => Err(error)
+        }
+    }
+
+    // Record a warning for a violation harmless enough that strict parsing
+    // ignores it too.
+    fn note(&mut self, field: &'static str, offset: usize, error: Error) {
+        if let Some(ref mut warnings) = self.warnings {
+            warnings.push(Warning { field, offset, error });
+        }
+    }
+
+    // Read a byte which the spec fixes as `value`.
+    #[cfg_attr(not(feature = "descriptors"), allow(dead_code))]
+    fn expect_u8(&mut self, value: u8, field: &'static str, error: Error) -> Result<()> {
+        let offset = self.offset;
+        if self.read_u8()? == value {
+            Ok(())
+        } else {
+            self.warn(field, offset, error)
+        }
+    }
+
+    // Read two bytes which the spec fixes as `value`.
+    #[cfg_attr(not(feature = "descriptors"), allow(dead_code))]
+    fn expect_u16(&mut self, value: u16, field: &'static str, error: Error) -> Result<()> {
+        let offset = self.offset;
+        if self.read_u16()? == value {
+            Ok(())
+        } else {
+            self.warn(field, offset, error)
+        }
+    }
+
+    // Read past a section which is not wanted.
+    fn skip(&mut self, num: usize) -> Result<()> {
+        for _ in 0..num {
+            self.get()?;
+        }
+        Ok(())
+    }
+
     // Both this and `read_u32` are little-endian.
     fn read_u16(&mut self) -> Result<u16> {
         Ok((self.read_u8()? as u16) | ((self.read_u8()? as u16) << 8))
@@ -115,30 +410,66 @@ pub struct EDID {
     /// Display characteristic parameters.
     pub display: DisplayParameters,
     /// Color calibration parameters.
+    #[cfg(feature = "color")]
     pub color: ColorCharacteristics,
     /// Accepted timing modes.
     pub timings: Timings,
     /// Extra monitor information.
+    #[cfg(feature = "descriptors")]
     pub descriptors: MonitorDescriptors,
     /// Number of extensions following the EDID block.
     pub extensions: u8,
+    /// The last byte of the block, which makes all 128 bytes sum to zero.
+    pub checksum: u8,
+    /// Whether the block sums to zero. A wrong checksum is not an error,
+    /// since the rest of a dump with a flipped bit can still be useful.
+    pub checksum_ok: bool,
+}
+
+/// The sections of an EDID which parsed before an error, from
+/// `EDID::parse_partial`. Sections after the error are `None`.
+#[derive(Debug, Clone, Default)]
+pub struct PartialEDID {
+    pub product: Option<ProductInformation>,
+    pub version: Option<Version>,
+    pub display: Option<DisplayParameters>,
+    #[cfg(feature = "color")]
+    pub color: Option<ColorCharacteristics>,
+    pub timings: Option<Timings>,
+    /// The descriptors before the error, if it was in the descriptors.
+    #[cfg(feature = "descriptors")]
+    pub descriptors: Option<MonitorDescriptors>,
+    pub extensions: Option<u8>,
+    pub checksum: Option<u8>,
+    pub checksum_ok: Option<bool>
 }
 
 impl EDID {
+    /// Parse the base block of an EDID.
+    ///
+    /// This never panics, whatever the data, and neither do the other
+    /// parsing functions: anything wrong is returned as an `Error`. The
+    /// fuzz target in `fuzz/` checks this.
+    #[cfg_attr(not(all(feature = "color", feature = "descriptors")), allow(unused_mut, unused_variables))]
     pub fn parse(r: &mut Reader) -> Result<EDID> {
-        ensure(r.read_u32()? == 0xffffff00, "Invalid header.")?;
-        ensure(r.read_u32()? == 0x00ffffff, "Invalid header.")?;
+        let start_sum = r.sum;
+        read_header(r)?;
         
         // Parse the different parts of the data,
-        let product = ProductInformation::parse(r)?;
+        let mut product = ProductInformation::parse(r)?;
         let version = Version::parse(r)?;
-        let display = DisplayParameters::parse(r)?;
+        product.manufacture_date = product.manufacture_date.for_version(&version);
+        let display = DisplayParameters::parse(r, &version)?;
+        #[cfg(feature = "color")]
         let mut color = ColorCharacteristics::parse(r)?;
+        #[cfg(not(feature = "color"))]
+        r.skip(10)?;
         let mut timings = Timings::parse(r)?;
-        let (descriptors, mut detailed_timings, mut standard_timings, mut white) = MonitorDescriptors::parse(r)?;
+        let (descriptors, mut detailed_timings, mut standard_timings, mut white) = MonitorDescriptors::parse(r, &version, display.dpms.preferred_timing_mode)?;
 
         // And do a little rearranging of the monitor descriptors to 
         // put the timing information all in one place.
+        #[cfg(feature = "color")]
         color.white_points.append(&mut white);
         timings.detailed_timings.append(&mut detailed_timings);
         timings.standard_timings.append(&mut standard_timings);
@@ -146,11 +477,137 @@ impl EDID {
         // Finish by reading how many extensions should follow this data.
         // We do not attempt to parse these in any way.
         let extensions = r.read_u8()?;
+        let checksum = r.read_u8()?;
+        let checksum_ok = r.sum == start_sum;
 
         Ok(EDID {
-            product, version, display, color, timings, descriptors, extensions
+            product, version, display,
+            #[cfg(feature = "color")]
+            color,
+            timings,
+            #[cfg(feature = "descriptors")]
+            descriptors,
+            extensions, checksum, checksum_ok
         })
     }
+
+    /// Parse like `parse`, but on an error return the sections which were
+    /// parsed before it along with the error. Detailed timings, standard
+    /// timings and white points from the descriptors before the error are
+    /// kept too.
+    #[cfg_attr(not(all(feature = "color", feature = "descriptors")), allow(unused_mut, unused_variables))]
+    pub fn parse_partial(r: &mut Reader) -> (PartialEDID, Option<Error>) {
+        let mut partial = PartialEDID::default();
+        let start_sum = r.sum;
+        let mut parse = |partial: &mut PartialEDID| -> Result<()> {
+            read_header(r)?;
+            let product = partial.product.insert(ProductInformation::parse(r)?);
+            let version = *partial.version.insert(Version::parse(r)?);
+            product.manufacture_date = product.manufacture_date.for_version(&version);
+            let display = partial.display.insert(DisplayParameters::parse(r, &version)?);
+            let preferred_timing = display.dpms.preferred_timing_mode;
+            #[cfg(feature = "color")]
+            let color = partial.color.insert(ColorCharacteristics::parse(r)?);
+            #[cfg(not(feature = "color"))]
+            r.skip(10)?;
+            let timings = partial.timings.insert(Timings::parse(r)?);
+
+            let mut parsed = (MonitorDescriptors(Vec::new()), Vec::new(), Vec::new(), Vec::new());
+            let result = MonitorDescriptors::parse_into(r, &version, preferred_timing, &mut parsed);
+            let (descriptors, mut detailed_timings, mut standard_timings, mut white) = parsed;
+            #[cfg(feature = "color")]
+            color.white_points.append(&mut white);
+            timings.detailed_timings.append(&mut detailed_timings);
+            timings.standard_timings.append(&mut standard_timings);
+            #[cfg(feature = "descriptors")] {
+                partial.descriptors = Some(descriptors);
+            }
+            result?;
+
+            partial.extensions = Some(r.read_u8()?);
+            partial.checksum = Some(r.read_u8()?);
+            partial.checksum_ok = Some(r.sum == start_sum);
+            Ok(())
+        };
+
+        let error = parse(&mut partial).err();
+        (partial, error)
+    }
+
+    /// Parse like `parse`, but read past spec violations which leave the
+    /// rest decodable, such as bad descriptor padding, and return them.
+    /// Negative back porches are taken as zero.
+    pub fn parse_lenient(r: &mut Reader) -> Result<(EDID, Vec<Warning>)> {
+        r.warnings = Some(Vec::new());
+        let edid = EDID::parse(r);
+        let warnings = r.warnings.take().unwrap_or_default();
+        Ok((edid?, warnings))
+    }
+
+    /// The detailed timings of the display, or `fallback_modes()`
+    /// if it does not give any.
+    pub fn modes_or_fallback(&self) -> Vec<DetailedTiming> {
+        if self.timings.detailed_timings.is_empty() {
+            fallback_modes()
+        } else {
+            self.timings.detailed_timings.clone()
+        }
+    }
+
+    /// The panel ID the Linux kernel uses for panel quirks, see `panel_id`.
+    pub fn panel_id(&self) -> u32 {
+        (self.product.manufacturer_id.raw() as u32) << 16 | self.product.product_code as u32
+    }
+
+    /// The color characteristics, unless they were left blank.
+    #[cfg(feature = "color")]
+    pub fn chromaticity(&self) -> Option<&ColorCharacteristics> {
+        Some(&self.color).filter(|color| color.is_valid())
+    }
+
+    /// The display range limits descriptor, if there is one.
+    #[cfg(feature = "descriptors")]
+    pub fn range_limits(&self) -> Option<&MonitorDescriptor> {
+        self.descriptors.0.iter().find(|d| matches!(d, MonitorDescriptor::RangeLimits { .. }))
+    }
+
+    /// Remove the identifying information from the EDID, like `anonymize`
+    /// does for raw data, and return which fields were changed.
+    pub fn anonymize(&mut self, clear_date: bool) -> Vec<RedactedField> {
+        let mut redacted = Vec::new();
+
+        if self.product.serial_number != 0 {
+            self.product.serial_number = 0;
+            redacted.push(RedactedField::SerialNumber);
+        }
+
+        #[cfg(feature = "descriptors")]
+        for descriptor in self.descriptors.0.iter_mut() {
+            if let MonitorDescriptor::SerialNumber(serial) = descriptor {
+                *serial = serial.chars().map(|_| '0').collect();
+                redacted.push(RedactedField::SerialString);
+            }
+        }
+
+        if clear_date {
+            self.product.manufacture_date = ManufactureDate::YearOnly(1990);
+            redacted.push(RedactedField::ManufactureDate);
+        }
+
+        redacted
+    }
+
+    /// The detailed timings of the base block which pass the given filter.
+    /// Established and standard timings are not included, see `ModeFilter`.
+    pub fn filter_modes(&self, filter: &ModeFilter) -> Vec<&DetailedTiming> {
+        #[cfg(feature = "descriptors")]
+        let limits = self.range_limits();
+        #[cfg(not(feature = "descriptors"))]
+        let limits = None;
+        self.timings.detailed_timings.iter()
+            .filter(|timing| filter.matches(timing, limits))
+            .collect()
+    }
 }
 
 /// Information about the product and its manufacture.
@@ -181,29 +638,91 @@ pub struct ManufacturerID(pub char, pub char, pub char);
 
 impl ManufacturerID {
     fn parse(r: &mut Reader) -> Result<ManufacturerID> {
-        // The manufacturer ID is stored as three 5-bit
-        // characters in a 16-bit little endian field.
-        let k = r.read_u16()?;
-        let c1 = ((k & 0b0111110000000000) >> 10) as u8;
-        let c2 = ((k & 0b0000001111100000) >> 05) as u8;
-        let c3 = ((k & 0b0000000000011111) >> 00) as u8;
-        Ok(ManufacturerID(c1 as char, c2 as char, c3 as char))
+        // The manufacturer ID is stored as three 5-bit characters,
+        // 1 = 'A' to 26 = 'Z', in a 16-bit big endian field.
+        let offset = r.offset;
+        let k = ((r.read_u8()? as u16) << 8) | r.read_u8()? as u16;
+        let c1 = ((k & 0b0111110000000000) >> 10) as u8 + b'@';
+        let c2 = ((k & 0b0000001111100000) >> 05) as u8 + b'@';
+        let c3 = ((k & 0b0000000000011111) >> 00) as u8 + b'@';
+        let id = ManufacturerID(c1 as char, c2 as char, c3 as char);
+
+        if !id.is_valid() {
+            r.note("manufacturer ID", offset, Error::BadManufacturerId { offset });
+        }
+        Ok(id)
+    }
+
+    /// Whether all three letters are from A to Z. Other values mean the
+    /// EDID is corrupt.
+    pub fn is_valid(&self) -> bool {
+        [self.0, self.1, self.2].iter().all(|c| c.is_ascii_uppercase())
+    }
+
+    /// The 16-bit value as stored in bytes 8 and 9, first byte high, which
+    /// is what kernel quirk tables match on.
+    pub fn raw(&self) -> u16 {
+        let letter = |c: char| (c as u16).wrapping_sub('@' as u16) & 0x1f;
+        letter(self.0) << 10 | letter(self.1) << 5 | letter(self.2)
     }
 }
 
 /// Gregorian calendar date of manufacture, all years are CE.
-#[derive(Debug, Clone, Copy)]
-pub struct ManufactureDate {
-    pub week: u8,
-    pub year: u16
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManufactureDate {
+    /// Made in `week` of `year`, with weeks counted from 1. Weeks past 54
+    /// are an error unless parsing leniently, which keeps them as they are.
+    WeekOfYear { week: u8, year: u16 },
+    /// Made in `year`, the week not being given.
+    YearOnly(u16),
+    /// The model year, which EDID 1.4 gives instead of the date of
+    /// manufacture with week 0xff.
+    ModelYear(u16)
 }
 
 impl ManufactureDate {
+    // Read the date as EDID 1.4 has it, see `for_version`.
     fn parse(r: &mut Reader) -> Result<ManufactureDate> {
+        let offset = r.offset;
         let week = r.read_u8()?;
         let year = r.read_u8()? as u16 + 1990;
+        if (55..=254).contains(&week) {
+            r.warn("manufacture week", offset, Error::InvalidWeek { offset, week })?;
+        }
+
+        Ok(match week {
+            0x00 => ManufactureDate::YearOnly(year),
+            0xff => ManufactureDate::ModelYear(year),
+            week => ManufactureDate::WeekOfYear { week, year }
+        })
+    }
+
+    // Before EDID 1.4 week 0xff had no meaning of its own.
+    fn for_version(self, version: &Version) -> ManufactureDate {
+        match self {
+            ManufactureDate::ModelYear(year) if (version.version, version.revision) < (1, 4) => {
+                ManufactureDate::WeekOfYear { week: 0xff, year }
+            },
+            date => date
+        }
+    }
+
+    /// The week and year bytes as stored in the EDID.
+    pub fn raw(&self) -> (u8, u8) {
+        let (week, year) = match *self {
+            ManufactureDate::WeekOfYear { week, year } => (week, year),
+            ManufactureDate::YearOnly(year) => (0x00, year),
+            ManufactureDate::ModelYear(year) => (0xff, year)
+        };
+        (week, year.saturating_sub(1990).min(255) as u8)
+    }
 
-        Ok(ManufactureDate { week, year })
+    /// The year, of manufacture or of the model.
+    pub fn year(&self) -> u16 {
+        match *self {
+            ManufactureDate::WeekOfYear { year, .. } => year,
+            ManufactureDate::YearOnly(year) | ManufactureDate::ModelYear(year) => year
+        }
     }
 }
 
@@ -218,6 +737,7 @@ impl Version {
     fn parse(r: &mut Reader) -> Result<Version> {
         let version = r.read_u8()?;
         let revision = r.read_u8()?;
+        ensure(version < 2, Error::UnsupportedVersion { version, revision })?;
 
         Ok(Version { version, revision })
     }
@@ -227,37 +747,39 @@ impl Version {
 #[derive(Debug, Clone)]
 pub struct DisplayParameters {
     pub input: VideoInput,
-    /// The maximum size of the image on the monitor.
-    pub max_size: Option<ImageSize>,
+    /// The maximum size of the image on the monitor, or
+    /// just its aspect ratio.
+    pub max_size: ScreenGeometry,
     /// The display's gamma factor.
-    pub gamma: Option<f32>,
+    pub gamma: Gamma,
     /// DPMS feature support.
     pub dpms: DPMSFeatures
 }
 
 impl DisplayParameters {
-    fn parse(r: &mut Reader) -> Result<DisplayParameters> {
-        let input = VideoInput::parse(r)?;
+    fn parse(r: &mut Reader, version: &Version) -> Result<DisplayParameters> {
+        let input = VideoInput::parse(r, version)?;
         let max_width = r.read_u8()?;
         let max_height = r.read_u8()?;
 
-        let max_size = if max_width == 0 || max_height == 0 {
-            None
-        } else {
-            Some(ImageSize {
-                width: max_width as f32,
-                height: max_height as f32
+        let max_size = match (max_width, max_height) {
+            (0, 0) => ScreenGeometry::Unknown,
+            (width, 0) if (version.version, version.revision) >= (1, 4) => {
+                ScreenGeometry::AspectRatio((width as f32 + 99.0) / 100.0)
+            },
+            (0, height) if (version.version, version.revision) >= (1, 4) => {
+                ScreenGeometry::AspectRatio(100.0 / (height as f32 + 99.0))
+            },
+            (0, _) | (_, 0) => ScreenGeometry::Unknown,
+            (width, height) => ScreenGeometry::Size(ImageSize {
+                width: width as f32,
+                height: height as f32
             })
         };
 
-        let gamma_val = r.read_u8()?;
-        let gamma = if gamma_val == 0xff {
-            None
-        } else {
-            Some((gamma_val as f32 + 100.0) / 100.0)
-        };
+        let gamma = Gamma::from_raw(r.read_u8()?);
 
-        let dpms = DPMSFeatures::parse(r)?;
+        let dpms = DPMSFeatures::parse(r, version, &input)?;
 
         Ok(DisplayParameters { input, max_size, gamma, dpms })
     }   
@@ -275,13 +797,29 @@ pub enum VideoInput {
         supported_sync: SupportedSync
     },
     Digital {
-        /// Compatible with VESA DFP 1.x
-        dfp_compatible: bool
+        /// Compatible with VESA DFP 1.x, only defined before EDID 1.4.
+        dfp_compatible: bool,
+        /// The colour bit depth per primary, from EDID 1.4.
+        color_depth: Option<u8>,
+        /// The digital interface, from EDID 1.4.
+        interface: Option<Interface>
     }
 }
 
+/// The digital interface a monitor takes, as given by EDID 1.4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interface {
+    DVI,
+    HDMIa,
+    HDMIb,
+    MDDI,
+    DisplayPort,
+    /// A reserved value.
+    Other(u8)
+}
+
 impl VideoInput {
-    fn parse(r: &mut Reader) -> Result<VideoInput> {
+    fn parse(r: &mut Reader, version: &Version) -> Result<VideoInput> {
         let val = r.read_u8()?;
         if val & (1 << 7) == 0 {
             let signal_level = match (val & 0b01100000) >> 5 {
@@ -299,8 +837,84 @@ impl VideoInput {
                 seperate_sync: val & (1 << 0) > 0
             };
             Ok(VideoInput::Analog { signal_level, setup_expected, supported_sync })
+        } else if (version.version, version.revision) >= (1, 4) {
+            let color_depth = match (val & 0b01110000) >> 4 {
+                depth @ 1..=6 => Some(4 + 2 * depth),
+                _ => None
+            };
+            let interface = match val & 0b00001111 {
+                0 => None,
+                1 => Some(Interface::DVI),
+                2 => Some(Interface::HDMIa),
+                3 => Some(Interface::HDMIb),
+                4 => Some(Interface::MDDI),
+                5 => Some(Interface::DisplayPort),
+                other => Some(Interface::Other(other))
+            };
+            Ok(VideoInput::Digital { dfp_compatible: false, color_depth, interface })
+        } else {
+            Ok(VideoInput::Digital { dfp_compatible: val & 1 > 0, color_depth: None, interface: None })
+        }
+    }
+
+    /// Whether the input can take the given sync configuration.
+    ///
+    /// Composite sync on the HSync line needs `composite_sync`, and composite
+    /// sync on the video lines needs `sync_on_green`, since sync on all three
+    /// lines is also sync on green. Serrated composite sync additionally needs
+    /// `serrated_vsync`. The flags say nothing about polarity, so any polarity
+    /// is accepted.
+    ///
+    /// Digital inputs carry sync within the data stream, so they accept
+    /// every sync type.
+    pub fn accepts(&self, sync: &SyncType) -> bool {
+        let supported = match self {
+            VideoInput::Analog { supported_sync, .. } => supported_sync,
+            VideoInput::Digital { .. } => return true
+        };
+
+        match *sync {
+            SyncType::Seperate { .. } => supported.seperate_sync,
+            SyncType::Composite { serrated, line } => {
+                let line_supported = match line {
+                    SyncLine::Digital(_) => supported.composite_sync,
+                    SyncLine::RGB | SyncLine::Green => supported.sync_on_green
+                };
+                line_supported && (!serrated || supported.serrated_vsync)
+            }
+        }
+    }
+
+    /// The sync configuration to drive a timing with: its own if the input
+    /// accepts it, otherwise the first supported of separate sync, composite
+    /// sync on HSync and sync on green. Polarities are kept from the timing
+    /// where it has them and are negative otherwise. `None` if the input
+    /// declares no sync support at all.
+    pub fn suggested_sync(&self, timing: &DetailedTiming) -> Option<SyncType> {
+        if self.accepts(&timing.sync_type) {
+            return Some(timing.sync_type);
+        }
+
+        let supported = match self {
+            VideoInput::Analog { supported_sync, .. } => supported_sync,
+            VideoInput::Digital { .. } => return Some(timing.sync_type)
+        };
+
+        let (horizontal, vertical) = match timing.sync_type {
+            SyncType::Seperate { horizontal, vertical } => (horizontal, vertical),
+            SyncType::Composite { line: SyncLine::Digital(polarity), .. } => (polarity, polarity),
+            SyncType::Composite { .. } => (SyncPolarity::Negative, SyncPolarity::Negative)
+        };
+        let serrated = supported.serrated_vsync;
+
+        if supported.seperate_sync {
+            Some(SyncType::Seperate { horizontal, vertical })
+        } else if supported.composite_sync {
+            Some(SyncType::Composite { serrated, line: SyncLine::Digital(horizontal) })
+        } else if supported.sync_on_green {
+            Some(SyncType::Composite { serrated, line: SyncLine::Green })
         } else {
-            Ok(VideoInput::Digital { dfp_compatible: val & 1 > 0 })
+            None
         }
     }
 }
@@ -332,39 +946,120 @@ pub struct ImageSize {
     pub height: f32
 }
 
+/// The gamma given in the base block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gamma {
+    Value(f32),
+    /// The gamma is given in a DI-EXT extension block instead.
+    DefinedInExtension
+}
+
+impl Gamma {
+    fn from_raw(val: u8) -> Gamma {
+        if val == 0xff {
+            Gamma::DefinedInExtension
+        } else {
+            Gamma::Value((val as f32 + 100.0) / 100.0)
+        }
+    }
+
+    /// The stored byte, which is the gamma times 100, less 100.
+    pub fn raw(self) -> u8 {
+        match self {
+            Gamma::Value(gamma) => (gamma * 100.0 - 99.5).clamp(0.0, 254.0) as u8,
+            Gamma::DefinedInExtension => 0xff
+        }
+    }
+
+    /// The gamma, if it is in the base block.
+    pub fn value(self) -> Option<f32> {
+        match self {
+            Gamma::Value(gamma) => Some(gamma),
+            Gamma::DefinedInExtension => None
+        }
+    }
+}
+
+/// The screen size given in the base block.
+#[derive(Debug, Clone, Copy)]
+pub enum ScreenGeometry {
+    Size(ImageSize),
+    /// The width divided by the height, which EDID 1.4 gives
+    /// instead of the size for projectors and the like.
+    AspectRatio(f32),
+    Unknown
+}
+
+impl ScreenGeometry {
+    /// The size, if it is given.
+    pub fn size(&self) -> Option<ImageSize> {
+        match *self {
+            ScreenGeometry::Size(size) => Some(size),
+            _ => None
+        }
+    }
+
+    /// The aspect ratio, from the size if it is given.
+    pub fn aspect_ratio(&self) -> Option<f32> {
+        match *self {
+            ScreenGeometry::Size(size) => Some(size.width / size.height),
+            ScreenGeometry::AspectRatio(ratio) => Some(ratio),
+            ScreenGeometry::Unknown => None
+        }
+    }
+}
+
 /// DPMS features supported by the display.
 #[derive(Debug, Clone)]
 pub struct DPMSFeatures {
     pub standby_supported: bool,
     pub suspend_supported: bool,
     pub low_power_supported: bool,
-    pub display_type: DisplayType,
+    /// The display type, or the supported colour encodings for
+    /// digital inputs from EDID 1.4.
+    pub display_color: DisplayColorInfo,
     pub default_srgb: bool,
     /// If set, the preferred timing mode is specified
     /// in the first detailed timing block. 
     pub preferred_timing_mode: bool,
-    /// If set, all timings from the standard GTF will work.
-    pub default_gtf_supported: bool
+    /// What the last bit of the feature byte says about timings outside
+    /// those listed, which depends on the version.
+    pub frequency_support: FrequencySupport
 }
 
 impl DPMSFeatures {
-    fn parse(r: &mut Reader) -> Result<DPMSFeatures> {
+    fn parse(r: &mut Reader, version: &Version, input: &VideoInput) -> Result<DPMSFeatures> {
         let val = r.read_u8()?;
+        let color = (val & 0b00011000) >> 3;
+        let digital = matches!(input, VideoInput::Digital { .. });
 
-        Ok(DPMSFeatures {
-            standby_supported: val & (1 << 7) > 0,
-            suspend_supported: val & (1 << 6) > 0,
-            low_power_supported: val & (1 << 5) > 0,
-            display_type: match (val & 0b00011000) >> 3 {
+        let display_color = if digital && (version.version, version.revision) >= (1, 4) {
+            DisplayColorInfo::Encodings(ColorEncodings {
+                ycbcr444: color & 1 > 0,
+                ycbcr422: color & 2 > 0
+            })
+        } else {
+            DisplayColorInfo::Type(match color {
                 0 => DisplayType::Monochrome,
                 1 => DisplayType::RGBColor,
                 2 => DisplayType::OtherColor,
                 3 => DisplayType::Undefined,
                 _ => unreachable!()
-            },
+            })
+        };
+
+        Ok(DPMSFeatures {
+            standby_supported: val & (1 << 7) > 0,
+            suspend_supported: val & (1 << 6) > 0,
+            low_power_supported: val & (1 << 5) > 0,
+            display_color,
             default_srgb: val & (1 << 2) > 0,
             preferred_timing_mode: val & (1 << 1) > 0,
-            default_gtf_supported: val & (1 << 0) > 0
+            frequency_support: match (val & 1 > 0, (version.version, version.revision) >= (1, 4)) {
+                (false, _) => FrequencySupport::None,
+                (true, false) => FrequencySupport::DefaultGTF,
+                (true, true) => FrequencySupport::Continuous
+            }
         })
     }
 }
@@ -378,6 +1073,43 @@ pub enum DisplayType {
     Undefined
 }
 
+/// Support for timings other than those the EDID lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrequencySupport {
+    /// Only the listed timings are supported.
+    None,
+    /// All timings from the default GTF will work, before EDID 1.4.
+    DefaultGTF,
+    /// The display is continuous frequency, from EDID 1.4, so any timing
+    /// within its range limits will work.
+    Continuous
+}
+
+impl FrequencySupport {
+    /// The bare bit of the feature byte.
+    pub fn raw(self) -> bool {
+        self != FrequencySupport::None
+    }
+}
+
+/// What the colour bits of the feature byte describe, which
+/// depends on the version and the video input.
+#[derive(Debug, Clone, Copy)]
+pub enum DisplayColorInfo {
+    /// The display type, for analog inputs and before EDID 1.4.
+    Type(DisplayType),
+    /// The colour encodings a digital input takes in EDID 1.4.
+    Encodings(ColorEncodings)
+}
+
+/// The colour encodings a digital input supports, all of which
+/// support RGB 4:4:4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorEncodings {
+    pub ycbcr444: bool,
+    pub ycbcr422: bool
+}
+
 /// Color chromaticity coordinates expressed as CIE 1931 x, y coordinates,
 /// as well as additional white points given in the monitor descriptors.
 #[derive(Debug, Clone)]
@@ -389,6 +1121,7 @@ pub struct ColorCharacteristics {
     pub white_points: Vec<WhitePoint>
 }
 
+#[cfg(feature = "color")]
 impl ColorCharacteristics {
     fn parse(r: &mut Reader) -> Result<ColorCharacteristics> {
         let rg_low = r.read_u8()? as u16;
@@ -421,6 +1154,38 @@ impl ColorCharacteristics {
     }
 }
 
+impl ColorCharacteristics {
+    /// Whether the coordinates were actually filled in. Some virtual
+    /// displays and signage panels leave them all zero or all ones,
+    /// which describes no gamut at all.
+    pub fn is_valid(&self) -> bool {
+        let coordinates = [self.red, self.green, self.blue, self.white];
+        let filled = |value: f32| coordinates.iter().all(|&(x, y)| x == value && y == value);
+        !filled(0.0) && !filled(1023.0 / 1024.0)
+    }
+
+    /// Whether the coordinates could describe a real display: every
+    /// coordinate is inside the chromaticity diagram, with neither x nor y
+    /// zero and x + y at most 1, and the white point is near the Planckian
+    /// locus, within 0.05 in y between 1667K and 25000K. Corrupt EDIDs fail
+    /// this, and sRGB is a better guess for them.
+    pub fn is_plausible(&self) -> bool {
+        let inside = |&(x, y): &(f32, f32)| x > 0.0 && y > 0.0 && x + y <= 1.0;
+
+        // Kim et al.'s cubic fit of y against x along the Planckian
+        // locus, which changes at 4000K and 2222K.
+        let (x, y) = (self.white.0 as f64, self.white.1 as f64);
+        let locus = match x {
+            x if x < 0.3805 => 3.0817580 * x * x * x - 5.8733867 * x * x + 3.7511300 * x - 0.3700148,
+            x if x < 0.5030 => -0.9549476 * x * x * x - 1.3741859 * x * x + 2.0913702 * x - 0.1674887,
+            x => -1.1063814 * x * x * x - 1.3481102 * x * x + 2.1855583 * x - 0.2021968
+        };
+        let near_locus = (0.2524..=0.5646).contains(&x) && (y - locus).abs() <= 0.05;
+
+        self.is_valid() && [self.red, self.green, self.blue, self.white].iter().all(inside) && near_locus
+    }
+}
+
 /// A single white point for the display, with x and y
 /// chromaticity coordinates given in the CIE 1931 space.
 #[derive(Debug, Clone, Copy)]
@@ -436,114 +1201,62 @@ pub struct WhitePoint {
 pub struct Timings {
     /// The timings supported from the VESA 'established timing' list.
     pub established_timings: Vec<EstablishedTiming>,
+    /// Bits 6 to 0 of the last established timings byte, which flag
+    /// timings of the manufacturer's own choosing.
+    pub manufacturer_timings: u8,
     /// Standard timings given that can be derived from the GTF.
     pub standard_timings: Vec<StandardTiming>,
-    /// Detailed timings specific to the display. If it exists, the first
-    /// detailed timing is the preferred timing.
+    /// Detailed timings specific to the display. If
+    /// `DPMSFeatures::preferred_timing_mode` is set, the first detailed
+    /// timing is the preferred timing.
     pub detailed_timings: Vec<DetailedTiming>
 }
 
 impl Timings {
     fn parse(r: &mut Reader) -> Result<Timings> {
-        let mut ft = r.read_u16()?;
-
         let mut established_timings = Vec::new();
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H800V600F60);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H800V600F56);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H640V480F75);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H640V480F72);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H640V480F67);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H640V480F60);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H720V400F88);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H720V400F70);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H1280V1024F75);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H1024V768F75);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H1024V768F70);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H1024V768F60);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H1024V768F87);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H832V624F75);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H800V600F75);
-        }
-        ft >>= 1;
-        if ft & 1 > 0 {
-            established_timings.push(EstablishedTiming::H800V600F72);
-        }
-
-        let ft = r.read_u8()?;
-        if ft & (1 << 7) > 0 {
-            established_timings.push(EstablishedTiming::H1152V870F75);
+        let (bits, manufacturer_timings) = EstablishedTiming::read_bits(r)?;
+        for (i, &timing) in ESTABLISHED_TIMINGS.iter().enumerate() {
+            if bits & (1 << i) > 0 {
+                established_timings.push(timing);
+            }
         }
 
         let mut standard_timings = Vec::new();
         for _ in 0..8 {
-            let low = r.read_u8()?;
-            let high = r.read_u8()?;
-            if low == 1 && high == 1 {
-                continue;
-            } else {
-                standard_timings.push(StandardTiming {
-                    horizontal_resolution: (low as u16 + 31) * 8,
-                    aspect_ratio: match high >> 6 {
-                        0 => 16.0/10.0,
-                        1 => 4.0/3.0,
-                        2 => 5.0/4.0,
-                        3 => 16.0/9.0,
-                        _ => unreachable!()
-                    },
-                    refresh_rate: (high & 0b00111111) + 60
-                });
+            if let Some(timing) = StandardTiming::parse(r)? {
+                standard_timings.push(timing);
             }
         }
 
         let detailed_timings = Vec::new();
 
-        Ok(Timings { established_timings, standard_timings, detailed_timings })
+        Ok(Timings { established_timings, manufacturer_timings, standard_timings, detailed_timings })
     }
 }
 
+// The established timings in the order of their bits, see
+// `EstablishedTiming::read_bits`.
+const ESTABLISHED_TIMINGS: [EstablishedTiming; 17] = [
+    EstablishedTiming::H800V600F60,
+    EstablishedTiming::H800V600F56,
+    EstablishedTiming::H640V480F75,
+    EstablishedTiming::H640V480F72,
+    EstablishedTiming::H640V480F67,
+    EstablishedTiming::H640V480F60,
+    EstablishedTiming::H720V400F88,
+    EstablishedTiming::H720V400F70,
+    EstablishedTiming::H1280V1024F75,
+    EstablishedTiming::H1024V768F75,
+    EstablishedTiming::H1024V768F70,
+    EstablishedTiming::H1024V768F60,
+    EstablishedTiming::H1024V768F87,
+    EstablishedTiming::H832V624F75,
+    EstablishedTiming::H800V600F75,
+    EstablishedTiming::H800V600F72,
+    EstablishedTiming::H1152V870F75
+];
+
 /// The 'established timings' specified by VESA.
 #[derive(Debug, Clone, Copy)]
 pub enum EstablishedTiming {
@@ -575,43 +1288,245 @@ pub struct StandardTiming {
     pub refresh_rate: u8
 }
 
-/// A non-standard timing with all parameters specified.
-#[derive(Debug, Clone)]
-pub struct DetailedTiming {
-    /// Given in Hz
-    pub pixel_clock: u32,
-    /// Active area in pixels.
-    pub active: (u16, u16),
-    /// Length of front porch in pixels and lines.
-    pub front_porch: (u16, u16),
-    /// Length of sync pulse in pixels and lines.
-    pub sync_length: (u16, u16),
-    /// Length of back porch in pixels and lines.
-    pub back_porch: (u16, u16),
-    /// Image size in centimetres.
-    pub image_size: ImageSize,
-    /// Border size in pixels.
-    pub border: (u16, u16),
-    pub interlaced: bool,
-    pub stereo: StereoType,
-    pub sync_type: SyncType
+impl EstablishedTiming {
+    // Read the three bytes of established timing flags, with bit
+    // `i` set if `ESTABLISHED_TIMINGS[i]` is supported, and the
+    // manufacturer's reserved timing bits.
+    fn read_bits(r: &mut Reader) -> Result<(u32, u8)> {
+        let low = r.read_u16()? as u32;
+        let manufacturer = r.read_u8()?;
+        Ok((low | ((manufacturer >> 7) as u32) << 16, manufacturer & 0x7f))
+    }
 }
 
-impl DetailedTiming {
-    fn parse(r: &mut Reader) -> Result<Option<DetailedTiming>> {
-        let pixel_clock = r.read_u16()? as u32 * 10000;
-        let ha_low = r.read_u8()? as u16;
-
-        if pixel_clock == 0 || ha_low == 0 {
+impl StandardTiming {
+    // Read a two byte standard timing, which is `None` if the slot is unused.
+    // Unused slots are 0x0101, but 0x00 is a reserved width and some tools
+    // pad with 0x0000 or 0x2020, so these are unused too.
+    fn parse(r: &mut Reader) -> Result<Option<StandardTiming>> {
+        let low = r.read_u8()?;
+        let high = r.read_u8()?;
+        if (low == 1 && high == 1) || low == 0 || (low == 0x20 && high == 0x20) {
             return Ok(None);
         }
 
-        let hb_low = r.read_u8()? as u16;
-        let h_high = r.read_u8()? as u16;
-
-        let horizontal_active = ha_low | (((h_high & 0xf0) >> 4) << 8);
-        let horizontal_blanking = hb_low | (((h_high & 0x0f) >> 0) << 8);
-
+        Ok(Some(StandardTiming {
+            horizontal_resolution: (low as u16 + 31) * 8,
+            aspect_ratio: match high >> 6 {
+                0 => 16.0/10.0,
+                1 => 4.0/3.0,
+                2 => 5.0/4.0,
+                3 => 16.0/9.0,
+                _ => unreachable!()
+            },
+            refresh_rate: (high & 0b00111111) + 60
+        }))
+    }
+}
+
+impl StandardTiming {
+    /// The number of lines, from the width and the aspect ratio.
+    pub fn vertical_resolution(&self) -> u16 {
+        (self.horizontal_resolution as f32 / self.aspect_ratio + 0.5) as u16
+    }
+
+    /// Work out the whole timing with the default GTF.
+    pub fn to_detailed_timing(&self) -> DetailedTiming {
+        gtf_timing((self.horizontal_resolution, self.vertical_resolution()), self.refresh_rate as f64, &SecondaryTiming::None)
+    }
+
+    /// Work out the whole timing with the GTF, using the secondary curve of
+    /// a range limits descriptor when the horizontal frequency reaches its
+    /// start frequency.
+    pub fn to_detailed_timing_with(&self, limits: &MonitorDescriptor) -> DetailedTiming {
+        let secondary = match limits {
+            MonitorDescriptor::RangeLimits { secondary_timing, .. } => secondary_timing,
+            _ => &SecondaryTiming::None
+        };
+        gtf_timing((self.horizontal_resolution, self.vertical_resolution()), self.refresh_rate as f64, secondary)
+    }
+}
+
+/// The versions of CVT reduced blanking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RbVersion {
+    /// 160 pixels of horizontal blanking and a clock in 0.25MHz steps.
+    V1,
+    /// 80 pixels of horizontal blanking, a fixed vertical back porch and
+    /// a clock in 1kHz steps, from CVT 1.2.
+    V2,
+    /// Like `V2`, but with a fixed vertical front porch so that vertical
+    /// sync starts early, from CVT 2.0.
+    V3
+}
+
+/// Work out a progressive mode with the VESA CVT reduced blanking formula.
+/// With `video_optimized` the clock of a `V2` or `V3` mode is lowered by
+/// 1000/1001, giving rates like 59.94Hz for video content. This fails if
+/// the size is zero, if the refresh rate leaves no time for the minimum
+/// vertical blanking, or if the totals or the clock do not fit.
+pub fn cvt_reduced_timing((width, height): (u16, u16), refresh: f64, version: RbVersion, video_optimized: bool) -> Result<DetailedTiming> {
+    // The minimum vertical blanking in microseconds.
+    const MIN_V_BLANK: f64 = 460.0;
+
+    if !(refresh > 0.0 && refresh.is_finite()) {
+        return Err(Error::InvalidTiming("Refresh rate must be positive."));
+    }
+
+    let (width, h_blank, h_front, clock_step) = match version {
+        RbVersion::V1 => (width / 8 * 8, 160, 48, 250000.0),
+        RbVersion::V2 | RbVersion::V3 => (width, 80, 8, 1000.0)
+    };
+    if width == 0 || height == 0 {
+        return Err(Error::InvalidTiming("Timing has no active area."));
+    }
+    let (v_front, v_sync, v_back) = match version {
+        RbVersion::V1 => (3, cvt_vsync(width, height), 6),
+        RbVersion::V2 | RbVersion::V3 => (1, 8, 6)
+    };
+
+    let period_estimate = (1e6 / refresh - MIN_V_BLANK) / height as f64;
+    if period_estimate <= 0.0 {
+        return Err(Error::InvalidTiming("Refresh rate too high for the minimum vertical blanking."));
+    }
+    // The cast saturates, which the addition then catches.
+    let v_blank = ((MIN_V_BLANK / period_estimate) as u16).checked_add(1)
+        .ok_or(Error::InvalidTiming("Vertical blanking too long."))?;
+    let v_blank = core::cmp::max(v_blank, v_front + v_sync + v_back);
+    let (v_front, v_back) = match version {
+        RbVersion::V2 => (v_blank - v_sync - v_back, v_back),
+        RbVersion::V1 | RbVersion::V3 => (v_front, v_blank - v_sync - v_front)
+    };
+
+    let (htotal, vtotal) = match (width.checked_add(h_blank), height.checked_add(v_blank)) {
+        (Some(htotal), Some(vtotal)) => (htotal, vtotal),
+        _ => return Err(Error::InvalidTiming("Total size too large."))
+    };
+
+    let multiplier = match version {
+        RbVersion::V2 | RbVersion::V3 if video_optimized => 1000.0 / 1001.0,
+        _ => 1.0
+    };
+    let pixel_clock = (refresh * htotal as f64 * vtotal as f64 * multiplier / clock_step) as u64 as f64 * clock_step;
+    if pixel_clock > u32::MAX as f64 {
+        return Err(Error::InvalidTiming("Pixel clock too high."));
+    }
+
+    Ok(progressive_timing((
+        pixel_clock as u32,
+        (width, height),
+        (h_front, v_front),
+        (32, v_sync),
+        (h_blank - h_front - 32, v_back),
+        SyncPolarity::Positive,
+        SyncPolarity::Negative
+    )))
+}
+
+// The CVT vertical sync length, which tells the aspect ratio.
+fn cvt_vsync(width: u16, height: u16) -> u16 {
+    let (width, height) = (width as u32, height as u32);
+    if width * 3 == height * 4 {
+        4
+    } else if width * 9 == height * 16 {
+        5
+    } else if width * 10 == height * 16 {
+        6
+    } else if width * 4 == height * 5 || width * 9 == height * 15 {
+        7
+    } else {
+        10
+    }
+}
+
+// The VESA GTF for a progressive mode with no margins, given the refresh
+// rate. The horizontal period comes only from the vertical timing, so it
+// alone picks between the default and secondary curves.
+fn gtf_timing((width, height): (u16, u16), refresh: f64, secondary: &SecondaryTiming) -> DetailedTiming {
+    const CELL: f64 = 8.0;
+    const MIN_PORCH: f64 = 1.0;
+    const V_SYNC: f64 = 3.0;
+    const H_SYNC_PERCENT: f64 = 8.0;
+    // In microseconds.
+    const MIN_VSYNC_BP: f64 = 550.0;
+
+    let round = |x: f64| (x + 0.5) as u32 as f64;
+
+    let width = round(width as f64 / CELL) * CELL;
+    let lines = height as f64;
+
+    // The line period in microseconds, estimated and then corrected
+    // for the rounding of the vertical blanking.
+    let period_estimate = (1.0 / refresh - MIN_VSYNC_BP / 1e6) / (lines + MIN_PORCH) * 1e6;
+    let vsync_bp = round(MIN_VSYNC_BP / period_estimate);
+    let refresh_estimate = 1e6 / period_estimate / (lines + vsync_bp + MIN_PORCH);
+    let period = period_estimate / (refresh / refresh_estimate);
+
+    let (c, m, k, j, polarity) = match *secondary {
+        SecondaryTiming::GTF { start_horizontal_freq, c, m, k, j } if 1e6 / period >= start_horizontal_freq as f64 => {
+            (c as f64, m as f64, k as f64, j as f64, (SyncPolarity::Positive, SyncPolarity::Negative))
+        },
+        _ => (40.0, 600.0, 128.0, 20.0, (SyncPolarity::Negative, SyncPolarity::Positive))
+    };
+    let c = (c - j) * k / 256.0 + j;
+    let m = k / 256.0 * m;
+
+    let duty_cycle = c - m * period / 1000.0;
+    let blanking = round(width * duty_cycle / (100.0 - duty_cycle) / (2.0 * CELL)) * 2.0 * CELL;
+    let total = width + blanking;
+    let sync = round(H_SYNC_PERCENT / 100.0 * total / CELL) * CELL;
+
+    progressive_timing((
+        (total / period * 1e6 + 0.5) as u32,
+        (width as u16, height),
+        ((blanking / 2.0 - sync) as u16, MIN_PORCH as u16),
+        (sync as u16, V_SYNC as u16),
+        ((blanking / 2.0) as u16, (vsync_bp - V_SYNC) as u16),
+        polarity.0,
+        polarity.1
+    ))
+}
+
+/// A non-standard timing with all parameters specified.
+#[derive(Debug, Clone)]
+pub struct DetailedTiming {
+    /// Given in Hz
+    pub pixel_clock: u32,
+    /// Active area in pixels.
+    pub active: (u16, u16),
+    /// Length of front porch in pixels and lines.
+    pub front_porch: (u16, u16),
+    /// Length of sync pulse in pixels and lines.
+    pub sync_length: (u16, u16),
+    /// Length of back porch in pixels and lines.
+    pub back_porch: (u16, u16),
+    /// Image size in centimetres, if it is given.
+    pub image_size: Option<ImageSize>,
+    /// Border size in pixels.
+    pub border: (u16, u16),
+    pub interlaced: bool,
+    pub stereo: StereoType,
+    pub sync_type: SyncType
+}
+
+impl DetailedTiming {
+    fn parse(r: &mut Reader) -> Result<Option<DetailedTiming>> {
+        let offset = r.offset;
+        let pixel_clock = r.read_u16()? as u32 * 10000;
+        let ha_low = r.read_u8()? as u16;
+
+        // Only the pixel clock marks a display descriptor, the low byte of
+        // the active width is zero for widths like 1024 and 1280.
+        if pixel_clock == 0 {
+            return Ok(None);
+        }
+
+        let hb_low = r.read_u8()? as u16;
+        let h_high = r.read_u8()? as u16;
+
+        let horizontal_active = ha_low | (((h_high & 0xf0) >> 4) << 8);
+        let horizontal_blanking = hb_low | (((h_high & 0x0f) >> 0) << 8);
+
         let va_low = r.read_u8()? as u16;
         let vb_low = r.read_u8()? as u16;
         let v_high = r.read_u8()? as u16;
@@ -637,9 +1552,16 @@ impl DetailedTiming {
         let active = (horizontal_active, vertical_active);
         let front_porch = (horizontal_front_porch, vertical_front_porch);
         let sync_length = (horizontal_sync_width, vertical_sync_width);
+        // Broken EDIDs can have less blanking than sync and front porch.
+        let mut back_porch = |blanking: u16, sync: u16, porch: u16| {
+            match blanking.checked_sub(sync).and_then(|rest| rest.checked_sub(porch)) {
+                Some(back_porch) => Ok(back_porch),
+                None => r.warn("detailed timing blanking", offset, Error::BadTiming { offset }).map(|_| 0)
+            }
+        };
         let back_porch = (
-            horizontal_blanking - horizontal_sync_width - horizontal_front_porch,
-            vertical_blanking - vertical_sync_width - vertical_front_porch
+            back_porch(horizontal_blanking, horizontal_sync_width, horizontal_front_porch)?,
+            back_porch(vertical_blanking, vertical_sync_width, vertical_front_porch)?
         );
 
         let hs_low = r.read_u8()? as u16;
@@ -648,7 +1570,12 @@ impl DetailedTiming {
         
         let h_size = hs_low | ((s_high & 0xf0) >> 4) << 8;
         let v_size = vs_low | ((s_high & 0x0f) >> 0) << 8;
-        let image_size = ImageSize { width: (h_size as f32) / 10.0, height: (v_size as f32) / 10.0 };
+        // Projectors and TVs give 0x0 when the size is not fixed.
+        let image_size = if h_size == 0 && v_size == 0 {
+            None
+        } else {
+            Some(ImageSize { width: (h_size as f32) / 10.0, height: (v_size as f32) / 10.0 })
+        };
 
         let hb = r.read_u8()? as u16;
         let vb = r.read_u8()? as u16;
@@ -662,6 +1589,367 @@ impl DetailedTiming {
             image_size, border, interlaced, stereo, sync_type
         }))
     }
+
+    /// Total size including blanking in pixels and lines. For interlaced
+    /// timings the vertical total is that of a single field.
+    pub fn total(&self) -> (u32, u32) {
+        (
+            self.active.0 as u32 + self.front_porch.0 as u32 + self.sync_length.0 as u32 + self.back_porch.0 as u32,
+            self.active.1 as u32 + self.front_porch.1 as u32 + self.sync_length.1 as u32 + self.back_porch.1 as u32
+        )
+    }
+
+    /// Horizontal frequency in Hz.
+    pub fn horizontal_rate(&self) -> f32 {
+        let (htotal, _) = self.total();
+        if htotal == 0 {
+            0.0
+        } else {
+            self.pixel_clock as f32 / htotal as f32
+        }
+    }
+
+    /// Vertical refresh rate in Hz, or the field rate for interlaced timings.
+    pub fn refresh_rate(&self) -> f32 {
+        let (htotal, vtotal) = self.total();
+        if htotal == 0 || vtotal == 0 {
+            0.0
+        } else {
+            (self.pixel_clock as f64 / (htotal as f64 * vtotal as f64)) as f32
+        }
+    }
+
+    /// The exact refresh rate as a fraction of the pixel clock over the
+    /// total pixels per frame (or per field for interlaced timings).
+    pub fn refresh_ratio(&self) -> (u64, u64) {
+        let (htotal, vtotal) = self.total();
+        (self.pixel_clock as u64, htotal as u64 * vtotal as u64)
+    }
+
+    /// The refresh rate in thousandths of a Hz, rounded to nearest,
+    /// so 59.94Hz is 59940.
+    pub fn refresh_millihz(&self) -> u32 {
+        match self.refresh_ratio() {
+            (_, 0) => 0,
+            (clock, pixels) => ((clock * 1000 + pixels / 2) / pixels) as u32
+        }
+    }
+
+    /// Whether this is the 1000/1001 variant of an integer rate used by
+    /// NTSC-derived modes, e.g. 59.94Hz for 60 or 23.976Hz for 24. The rate
+    /// must be closer to `base_hz * 1000 / 1001` than halfway to `base_hz`
+    /// or further away, allowing for the 10kHz steps of the pixel clock.
+    pub fn is_fractional_of(&self, base_hz: u32) -> bool {
+        let (clock, pixels) = self.refresh_ratio();
+        let base = base_hz as i128;
+        let offset = 1001 * clock as i128 - 1000 * base * pixels as i128;
+        base > 0 && pixels > 0 && 2 * offset.abs() < base * pixels as i128
+    }
+
+    /// Which version of CVT reduced blanking the timing follows, judging by
+    /// its horizontal blanking, vertical sync and porches. Other timings,
+    /// including normal CVT and GTF ones, give `None`.
+    pub fn reduced_blanking(&self) -> Option<RbVersion> {
+        let separate = matches!(self.sync_type, SyncType::Seperate { horizontal: SyncPolarity::Positive, vertical: SyncPolarity::Negative });
+        if !separate || self.interlaced || self.sync_length.0 != 32 {
+            return None;
+        }
+
+        let blanking = self.front_porch.0 + self.sync_length.0 + self.back_porch.0;
+        match (blanking, self.front_porch, self.sync_length.1, self.back_porch.1) {
+            (160, (48, 3), _, back) if back >= 6 => Some(RbVersion::V1),
+            (80, (8, front), 8, 6) if front >= 1 => Some(RbVersion::V2),
+            (_, (8, 1), 8, back) if blanking >= 80 && blanking.is_multiple_of(8) && back >= 6 => Some(RbVersion::V3),
+            _ => None
+        }
+    }
+
+    /// Check the timing against a range limits descriptor, with the rates
+    /// rounded to the nearest Hz and kHz like the descriptor's own fields.
+    /// Any other kind of descriptor places no limits.
+    pub fn within_range_limits(&self, limits: &MonitorDescriptor) -> bool {
+        match limits {
+            MonitorDescriptor::RangeLimits { vertical_rate, horizontal_rate, pixel_clock, .. } => {
+                // Rates are never negative, so this rounds to nearest without `std`.
+                let refresh = (self.refresh_rate() + 0.5) as u32;
+                let hrate = (self.horizontal_rate() / 1000.0 + 0.5) as u32 * 1000;
+                refresh >= vertical_rate.0 as u32 && refresh <= vertical_rate.1 as u32
+                    && hrate >= horizontal_rate.0 && hrate <= horizontal_rate.1
+                    && self.pixel_clock <= *pixel_clock
+            },
+            _ => true
+        }
+    }
+
+    /// The same timing at a different refresh rate, either by scaling the pixel
+    /// clock or by stretching the vertical back porch. The result is checked to
+    /// still fit in a detailed timing descriptor, use `within_range_limits` to
+    /// check it against the display as well.
+    pub fn with_refresh(&self, hz: f64, strategy: RefreshStrategy) -> Result<DetailedTiming> {
+        if !(hz > 0.0 && hz.is_finite()) {
+            return Err(Error::InvalidTiming("Refresh rate must be positive."));
+        }
+
+        let (htotal, vtotal) = self.total();
+        if htotal == 0 || vtotal == 0 {
+            return Err(Error::InvalidTiming("Timing has no blanking or active area."));
+        }
+
+        let mut timing = self.clone();
+        match strategy {
+            RefreshStrategy::ClockScaled => {
+                // Descriptors store the clock in units of 10kHz.
+                let clock = htotal as f64 * vtotal as f64 * hz / 10000.0 + 0.5;
+                if clock > 0xffff as f64 {
+                    return Err(Error::InvalidTiming("Pixel clock too high for a detailed timing."));
+                }
+                timing.pixel_clock = clock as u32 * 10000;
+                if timing.pixel_clock == 0 {
+                    return Err(Error::InvalidTiming("Pixel clock too low for a detailed timing."));
+                }
+            },
+            RefreshStrategy::VblankStretched => {
+                let lines = (self.pixel_clock as f64 / (htotal as f64 * hz) + 0.5) as u32;
+                let fixed = vtotal - self.back_porch.1 as u32;
+                if lines < fixed {
+                    return Err(Error::InvalidTiming("Refresh rate too high to keep the pixel clock."));
+                }
+                if lines - fixed > 0xffff {
+                    return Err(Error::InvalidTiming("Vertical blanking too long for a detailed timing."));
+                }
+                timing.back_porch.1 = (lines - fixed) as u16;
+            }
+        }
+
+        timing.check_fields()?;
+        Ok(timing)
+    }
+
+    // Whether every field fits its width in a detailed timing descriptor.
+    fn check_fields(&self) -> Result<()> {
+        let blanking = (
+            self.front_porch.0 as u32 + self.sync_length.0 as u32 + self.back_porch.0 as u32,
+            self.front_porch.1 as u32 + self.sync_length.1 as u32 + self.back_porch.1 as u32
+        );
+
+        if self.pixel_clock > 0xffff * 10000 {
+            Err(Error::InvalidTiming("Pixel clock too high for a detailed timing."))
+        } else if self.active.0 > 0xfff || self.active.1 > 0xfff {
+            Err(Error::InvalidTiming("Active area too large for a detailed timing."))
+        } else if blanking.0 > 0xfff || blanking.1 > 0xfff {
+            Err(Error::InvalidTiming("Blanking too long for a detailed timing."))
+        } else if self.front_porch.0 > 0x3ff || self.sync_length.0 > 0x3ff
+            || self.front_porch.1 > 0x3f || self.sync_length.1 > 0x3f {
+            Err(Error::InvalidTiming("Sync too long for a detailed timing."))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// How `DetailedTiming::with_refresh` reaches the new refresh rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshStrategy {
+    /// Keep the blanking and scale the pixel clock.
+    ClockScaled,
+    /// Keep the pixel clock and change the vertical back porch,
+    /// as variable refresh displays do.
+    VblankStretched
+}
+
+/// A timing laid out the way parallel RGB LCD controllers take it, each
+/// pair being horizontal in pixels and vertical in lines. Borders are not
+/// included, panels driven like this do not have them.
+///
+/// The controllers count these differently:
+///
+/// - ESP32 `esp_lcd_rgb_timing_t` takes the values as they are.
+/// - STM32 LTDC takes running totals less one: `SSCR` is `sync - 1`,
+///   `BPCR` is `sync + back_porch - 1`, `AWCR` adds `active` and `TWCR`
+///   adds `front_porch` too.
+/// - i.MX eLCDIF takes the sync width, the whole period, `sync + back_porch`
+///   as the wait count and the active size.
+#[derive(Debug, Clone, Copy)]
+pub struct RgbTimings {
+    /// Given in Hz.
+    pub pixel_clock: u32,
+    pub active: (u16, u16),
+    pub front_porch: (u16, u16),
+    pub sync_length: (u16, u16),
+    pub back_porch: (u16, u16),
+    /// The horizontal and vertical sync polarities. A composite sync is
+    /// given for both and analog sync, which has no polarity, is negative.
+    pub polarity: (SyncPolarity, SyncPolarity)
+}
+
+impl RgbTimings {
+    /// The whole period in pixels and lines.
+    pub fn total(&self) -> (u16, u16) {
+        (
+            self.active.0 + self.front_porch.0 + self.sync_length.0 + self.back_porch.0,
+            self.active.1 + self.front_porch.1 + self.sync_length.1 + self.back_porch.1
+        )
+    }
+}
+
+impl From<&DetailedTiming> for RgbTimings {
+    fn from(timing: &DetailedTiming) -> RgbTimings {
+        let polarity = match timing.sync_type {
+            SyncType::Seperate { horizontal, vertical } => (horizontal, vertical),
+            SyncType::Composite { line: SyncLine::Digital(polarity), .. } => (polarity, polarity),
+            SyncType::Composite { .. } => (SyncPolarity::Negative, SyncPolarity::Negative)
+        };
+
+        RgbTimings {
+            pixel_clock: timing.pixel_clock,
+            active: timing.active,
+            front_porch: timing.front_porch,
+            sync_length: timing.sync_length,
+            back_porch: timing.back_porch,
+            polarity
+        }
+    }
+}
+
+/// Composable conditions for selecting detailed timings, used with
+/// `EDID::filter_modes`. Every condition which is set must hold.
+///
+/// Only detailed timings are covered, not established timings or the timings
+/// of extension blocks. Standard timings can be converted with
+/// `StandardTiming::to_detailed_timing` and checked with `matches`.
+///
+/// Refresh rates are rounded to the nearest Hz before being compared, so
+/// that a 59.94Hz mode passes a 60Hz minimum and the integer bounds of the
+/// range limits descriptor.
+#[derive(Debug, Clone, Default)]
+pub struct ModeFilter {
+    max_pixel_clock: Option<u32>,
+    min_refresh: Option<u32>,
+    max_refresh: Option<u32>,
+    progressive_only: bool,
+    aspect_ratio: Option<(f32, f32)>,
+    within_range_limits: bool
+}
+
+impl ModeFilter {
+    /// A filter which accepts every timing.
+    pub fn new() -> ModeFilter {
+        ModeFilter::default()
+    }
+
+    /// Reject pixel clocks above `mhz` MHz.
+    pub fn max_pixel_clock_mhz(mut self, mhz: u32) -> ModeFilter {
+        self.max_pixel_clock = Some(mhz.saturating_mul(1_000_000));
+        self
+    }
+
+    /// Reject refresh rates below `hz` Hz.
+    pub fn min_refresh(mut self, hz: u32) -> ModeFilter {
+        self.min_refresh = Some(hz);
+        self
+    }
+
+    /// Reject refresh rates above `hz` Hz.
+    pub fn max_refresh(mut self, hz: u32) -> ModeFilter {
+        self.max_refresh = Some(hz);
+        self
+    }
+
+    /// Reject interlaced timings.
+    pub fn progressive_only(mut self) -> ModeFilter {
+        self.progressive_only = true;
+        self
+    }
+
+    /// Only accept timings whose active area has the given width to height
+    /// ratio, such as `16.0 / 9.0`, give or take `tolerance`.
+    pub fn aspect_ratio(mut self, ratio: f32, tolerance: f32) -> ModeFilter {
+        self.aspect_ratio = Some((ratio, tolerance));
+        self
+    }
+
+    /// Reject timings outside the display's range limits descriptor.
+    /// Has no effect on displays without one.
+    pub fn within_range_limits(mut self, enabled: bool) -> ModeFilter {
+        self.within_range_limits = enabled;
+        self
+    }
+
+    /// Check a timing against the filter, `limits` being the display's
+    /// range limits descriptor as returned by `EDID::range_limits`.
+    pub fn matches(&self, timing: &DetailedTiming, limits: Option<&MonitorDescriptor>) -> bool {
+        // Rates are never negative, so this rounds to nearest without `std`.
+        let refresh = (timing.refresh_rate() + 0.5) as u32;
+
+        if self.progressive_only && timing.interlaced {
+            return false;
+        }
+
+        if self.max_pixel_clock.is_some_and(|max| timing.pixel_clock > max) {
+            return false;
+        }
+
+        if self.min_refresh.is_some_and(|min| refresh < min) {
+            return false;
+        }
+
+        if self.max_refresh.is_some_and(|max| refresh > max) {
+            return false;
+        }
+
+        if let Some((ratio, tolerance)) = self.aspect_ratio {
+            // Interlaced timings give the height of a single field.
+            let height = timing.active.1 as f32 * if timing.interlaced { 2.0 } else { 1.0 };
+            if height == 0.0 || (timing.active.0 as f32 / height - ratio).abs() > tolerance {
+                return false;
+            }
+        }
+
+        if let (true, Some(limits)) = (self.within_range_limits, limits) {
+            if !timing.within_range_limits(limits) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Safe modes to fall back on when a display has no usable EDID.
+///
+/// These are the VESA DMT timings Linux offers in that case (up to 1024x768
+/// at no more than 60Hz) in the same order: 640x480@60, 800x600@56,
+/// 800x600@60, 848x480@60 and 1024x768@60.
+pub fn fallback_modes() -> Vec<DetailedTiming> {
+    use SyncPolarity::*;
+
+    // Pixel clock, then active, front porch, sync and back porch
+    // as (horizontal, vertical), and the sync polarities.
+    let modes = [
+        (25175000, (640, 480), (16, 10), (96, 2), (48, 33), Negative, Negative),
+        (36000000, (800, 600), (24, 1), (72, 2), (128, 22), Positive, Positive),
+        (40000000, (800, 600), (40, 1), (128, 4), (88, 23), Positive, Positive),
+        (33750000, (848, 480), (16, 6), (112, 8), (112, 23), Positive, Positive),
+        (65000000, (1024, 768), (24, 3), (136, 6), (160, 29), Negative, Negative)
+    ];
+
+    modes.iter().map(|&mode| progressive_timing(mode)).collect()
+}
+
+// Build a progressive timing with separate sync from its pixel clock, then
+// active, front porch, sync and back porch, and the sync polarities.
+fn progressive_timing(
+    (pixel_clock, active, front_porch, sync_length, back_porch, horizontal, vertical):
+    (u32, (u16, u16), (u16, u16), (u16, u16), (u16, u16), SyncPolarity, SyncPolarity)
+) -> DetailedTiming {
+    DetailedTiming {
+        pixel_clock, active, front_porch, sync_length, back_porch,
+        image_size: None,
+        border: (0, 0),
+        interlaced: false,
+        stereo: StereoType::None,
+        sync_type: SyncType::Seperate { horizontal, vertical }
+    }
 }
 
 /// Type of stereo image supported by the display.
@@ -765,21 +2053,84 @@ pub enum SyncPolarity {
 #[derive(Debug, Clone)]
 pub struct MonitorDescriptors(pub Vec<MonitorDescriptor>);
 
+// Descriptor strings are in code page 437, which is ASCII up to 0x7f
+// and these characters from 0x80.
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}'
+];
+
+// Decode a byte of a descriptor string.
+fn cp437_decode(byte: u8) -> char {
+    if byte < 0x80 {
+        byte as char
+    } else {
+        CP437_HIGH[byte as usize - 0x80]
+    }
+}
+
+// Decode the text of a string descriptor, which ends at a newline or
+// after 13 bytes. ASCII text is borrowed, as it needs no decoding.
+fn descriptor_text(text: &[u8]) -> Cow<'_, str> {
+    let text = &text[..text.iter().position(|&b| b == 0x0a).unwrap_or(text.len())];
+    match core::str::from_utf8(text) {
+        Ok(ascii) if text.is_ascii() => Cow::Borrowed(ascii),
+        _ => Cow::Owned(text.iter().map(|&b| cp437_decode(b)).collect())
+    }
+}
+
+// Encode a character of a descriptor string, with `?` for those
+// code page 437 does not have.
+#[cfg_attr(not(feature = "cta"), allow(dead_code))]
+fn cp437_encode(c: char) -> u8 {
+    match CP437_HIGH.iter().position(|&high| high == c) {
+        _ if c.is_ascii() => c as u8,
+        Some(index) => 0x80 + index as u8,
+        None => b'?'
+    }
+}
+
 impl MonitorDescriptors {
-    fn parse(r: &mut Reader) -> Result<(MonitorDescriptors, Vec<DetailedTiming>, Vec<StandardTiming>, Vec<WhitePoint>)> {
-        let mut detailed_timings = vec![DetailedTiming::parse(r)?.ok_or("Expected detailed timing block.")?];
+    fn parse(r: &mut Reader, version: &Version, preferred_timing: bool) -> Result<(MonitorDescriptors, Vec<DetailedTiming>, Vec<StandardTiming>, Vec<WhitePoint>)> {
+        let mut parsed = (MonitorDescriptors(Vec::new()), Vec::new(), Vec::new(), Vec::new());
+        MonitorDescriptors::parse_into(r, version, preferred_timing, &mut parsed)?;
+        Ok(parsed)
+    }
 
-        let mut standard_timings = Vec::new();
-        let mut monitor_descriptors = Vec::new();
-        let mut white_points = Vec::new();
+    // Parse the four slots into `parsed`, so the slots before an error are kept.
+    #[cfg_attr(not(feature = "descriptors"), allow(unused_mut, unused_variables, unreachable_code))]
+    fn parse_into(
+        r: &mut Reader, version: &Version, preferred_timing: bool,
+        parsed: &mut (MonitorDescriptors, Vec<DetailedTiming>, Vec<StandardTiming>, Vec<WhitePoint>)
+    ) -> Result<()> {
+        let (MonitorDescriptors(monitor_descriptors), detailed_timings, standard_timings, white_points) = parsed;
 
-        for _ in 0..3 {
+        for slot in 0..4 {
+            let offset = r.offset;
             if let Some(timing) = DetailedTiming::parse(r)? {
                 detailed_timings.push(timing);
             } else {
+                // The first slot may only be a display descriptor when
+                // there is no preferred timing.
+                if slot == 0 && preferred_timing {
+                    r.warn("preferred timing", offset, Error::MissingPreferredTiming { offset })?;
+                }
+
                 let tag = r.read_u8()?;
-                r.read_u8()?;
+                let flags = r.read_u8()?;
+
+                #[cfg(not(feature = "descriptors"))] {
+                    r.skip(13)?;
+                    continue;
+                }
 
+                #[cfg(feature = "descriptors")]
                 match tag {
                     0x00..=0x0f => monitor_descriptors.push(MonitorDescriptor::ManufacturerDefined(tag, [
                         r.read_u8()?,
@@ -796,8 +2147,8 @@ impl MonitorDescriptors {
                         r.read_u8()?,
                         r.read_u8()?
                     ])),
-                    0x10 => continue,
-                    0x11..=0xf9 => monitor_descriptors.push(MonitorDescriptor::Undefined(tag, [
+                    0x10 => r.skip(13)?,
+                    0x11..=0xf7 | 0xf9 => monitor_descriptors.push(MonitorDescriptor::Undefined(tag, [
                         r.read_u8()?,
                         r.read_u8()?,
                         r.read_u8()?,
@@ -812,28 +2163,28 @@ impl MonitorDescriptors {
                         r.read_u8()?,
                         r.read_u8()?
                     ])),
+                    0xf8 => {
+                        let version = r.offset;
+                        if r.read_u8()? != 0x01 {
+                            r.note("CVT descriptor version", version, Error::BadDescriptor { tag, offset });
+                        }
+
+                        let mut timings = Vec::new();
+                        for _ in 0..4 {
+                            if let Some(timing) = CvtTiming::parse(r)? {
+                                timings.push(timing);
+                            }
+                        }
+                        monitor_descriptors.push(MonitorDescriptor::CvtTimings(timings));
+                    },
                     0xfa => {
                         for _ in 0..6 {
-                            let low = r.read_u8()?;
-                            let high = r.read_u8()?;
-                            if low == 1 && high == 1 {
-                                continue;
-                            } else {
-                                standard_timings.push(StandardTiming {
-                                    horizontal_resolution: (low as u16 + 31) * 8,
-                                    aspect_ratio: match high >> 6 {
-                                        0 => 16.0/10.0,
-                                        1 => 4.0/3.0,
-                                        2 => 5.0/4.0,
-                                        3 => 16.0/9.0,
-                                        _ => unreachable!()
-                                    },
-                                    refresh_rate: (high & 0b00111111) + 60
-                                });
+                            if let Some(timing) = StandardTiming::parse(r)? {
+                                standard_timings.push(timing);
                             }
                         }
 
-                        ensure(r.read_u8()? == 0x0a, "Expected 0x0a in monitor descriptor.")?;
+                        r.expect_u8(0x0a, "descriptor terminator", Error::BadDescriptor { tag, offset })?;
                     },
                     0xfb => {
                         for _ in 0..2 {
@@ -853,25 +2204,23 @@ impl MonitorDescriptors {
                             }
                         }
 
-                        ensure(r.read_u8()? == 0x0a, "Expected 0x0a in monitor descriptor.")?;
-                        ensure(r.read_u16()? == 0x2020, "Expected 0x20 in monitor descriptor.")?;
+                        r.expect_u8(0x0a, "descriptor terminator", Error::BadDescriptor { tag, offset })?;
+                        r.expect_u16(0x2020, "descriptor padding", Error::BadDescriptor { tag, offset })?;
                     },
                     0xfc | 0xfe | 0xff => {
-                        let mut out = String::new();
-                        let mut byte = r.read_u8()?;
-                        let mut i = 0;
-                        while byte != 0x0a {
-                            out.push(byte as char);
-                            i += 1;
-                            if i == 13 {
-                                break;
-                            }
-                            byte = r.read_u8()?;
+                        let start = r.offset;
+                        let mut text = [0u8; 13];
+                        for byte in text.iter_mut() {
+                            *byte = r.read_u8()?;
                         }
-                        i += 1;
-                        while i < 13 {
-                            ensure(r.read_u8()? == 0x20, "Expected 0x20 in monitor descriptor.")?;
-                            i += 1;
+                        let out = descriptor_text(&text).into_owned();
+
+                        // Padding with 0x00 or more 0x0a is common enough
+                        // that it is only worth a warning.
+                        if let Some(end) = text.iter().position(|&b| b == 0x0a) {
+                            if text[end + 1..].iter().any(|&b| b != 0x20) {
+                                r.note("descriptor padding", start + end + 1, Error::BadDescriptor { tag, offset });
+                            }
                         }
 
                         match tag {
@@ -882,22 +2231,39 @@ impl MonitorDescriptors {
                         }
                     },
                     0xfd => {
-                        let min_vrate = r.read_u8()?;
-                        let max_vrate = r.read_u8()?;
-                        let min_hrate = r.read_u8()? as u32 * 1000;
-                        let max_hrate = r.read_u8()? as u32 * 1000;
+                        // EDID 1.4 adds 255 to the maximum, or to both limits,
+                        // for rates over 255 Hz or kHz.
+                        let offsets = |flags: u8| match flags & 0b11 {
+                            0b10 if (version.version, version.revision) >= (1, 4) => (0, 255),
+                            0b11 if (version.version, version.revision) >= (1, 4) => (255, 255),
+                            _ => (0, 0)
+                        };
+                        let (min_voffset, max_voffset) = offsets(flags);
+                        let (min_hoffset, max_hoffset) = offsets(flags >> 2);
+                        let min_vrate = r.read_u8()? as u16 + min_voffset;
+                        let max_vrate = r.read_u8()? as u16 + max_voffset;
+                        let min_hrate = (r.read_u8()? as u32 + min_hoffset as u32) * 1000;
+                        let max_hrate = (r.read_u8()? as u32 + max_hoffset as u32) * 1000;
                         let pixel_clock = r.read_u8()? as u32 * 10000000;
                         let stime = r.read_u8()?;
                         let secondary_timing = match stime {
                             0x00 => {
-                                ensure(r.read_u8()? == 0x0a, "Expected 0x0a in monitor descriptor.")?;
-                                ensure(r.read_u16()? == 0x2020, "Expected 0x20 in monitor descriptor.")?;
-                                ensure(r.read_u16()? == 0x2020, "Expected 0x20 in monitor descriptor.")?;
-                                ensure(r.read_u16()? == 0x2020, "Expected 0x20 in monitor descriptor.")?;
-                                SecondaryTiming::None
+                                let padding = r.offset;
+                                let mut data = [0u8; 7];
+                                for byte in data.iter_mut() {
+                                    *byte = r.read_u8()?;
+                                }
+
+                                // Keep whatever else is there when parsing leniently.
+                                if data == [0x0a, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20] {
+                                    SecondaryTiming::None
+                                } else {
+                                    r.warn("range limits padding", padding, Error::BadDescriptor { tag, offset })?;
+                                    SecondaryTiming::Other(stime, data)
+                                }
                             },
                             0x02 => {
-                                ensure(r.read_u8()? == 0x00, "Expected 0x0a in monitor descriptor.")?;
+                                r.expect_u8(0x00, "range limits reserved byte", Error::BadDescriptor { tag, offset })?;
                                 let start_horizontal_freq = r.read_u8()? as u32 * 2000;
                                 let c = r.read_u8()? as f32 / 2.0;
                                 let m = r.read_u16()? as f32;
@@ -907,6 +2273,34 @@ impl MonitorDescriptors {
                                     start_horizontal_freq, c, m, k, j
                                 }
                             },
+                            0x04 => {
+                                let version = r.read_u8()?;
+                                let precision = r.read_u8()?;
+                                let max_width = ((precision & 0b11) as u16) << 8 | r.read_u8()? as u16;
+                                let aspect_ratios = r.read_u8()?;
+                                let preferences = r.read_u8()?;
+                                let scaling = r.read_u8()?;
+                                let preferred_refresh = r.read_u8()?;
+                                SecondaryTiming::CVT {
+                                    version: (version >> 4, version & 0x0f),
+                                    max_pixel_clock: pixel_clock.saturating_sub((precision >> 2) as u32 * 250000),
+                                    max_active_pixels: if max_width == 0 { None } else { Some(max_width * 8) },
+                                    supported_aspect_ratios: CVT_ASPECT_RATIOS.iter().enumerate()
+                                        .filter(|&(i, _)| aspect_ratios & (1 << (7 - i)) > 0)
+                                        .map(|(_, &ratio)| ratio)
+                                        .collect(),
+                                    preferred_aspect_ratio: CVT_ASPECT_RATIOS.get((preferences >> 5) as usize).copied(),
+                                    standard_blanking: preferences & (1 << 3) > 0,
+                                    reduced_blanking: preferences & (1 << 4) > 0,
+                                    scaling: CvtScaling {
+                                        horizontal_shrink: scaling & (1 << 7) > 0,
+                                        horizontal_stretch: scaling & (1 << 6) > 0,
+                                        vertical_shrink: scaling & (1 << 5) > 0,
+                                        vertical_stretch: scaling & (1 << 4) > 0
+                                    },
+                                    preferred_refresh
+                                }
+                            },
                             _ => {
                                 let data = [
                                     r.read_u8()?,
@@ -920,17 +2314,21 @@ impl MonitorDescriptors {
                                 SecondaryTiming::Other(stime, data)
                             }
                         };
-                        monitor_descriptors.push(MonitorDescriptor::RangeLimits {
+                        let limits = MonitorDescriptor::RangeLimits {
                             vertical_rate: (min_vrate, max_vrate),
                             horizontal_rate: (min_hrate, max_hrate),
                             pixel_clock, secondary_timing
-                        });
+                        };
+                        if !limits.is_consistent() {
+                            r.note("range limits", offset, Error::BadDescriptor { tag, offset });
+                        }
+                        monitor_descriptors.push(limits);
                     }
                 }
             }
         }
 
-        Ok((MonitorDescriptors(monitor_descriptors), detailed_timings, standard_timings, white_points))
+        Ok(())
     }
 }
 
@@ -940,8 +2338,9 @@ pub enum MonitorDescriptor {
     SerialNumber(String),
     OtherString(String),
     RangeLimits {
-        /// Vertical frequency limits in Hz.
-        vertical_rate: (u8, u8),
+        /// Vertical frequency limits in Hz, up to 510Hz with the offsets
+        /// of EDID 1.4.
+        vertical_rate: (u16, u16),
         /// Horizontal frequency limits in Hz.
         horizontal_rate: (u32, u32),
         /// Pixel frequency limits in Hz.
@@ -950,10 +2349,82 @@ pub enum MonitorDescriptor {
         secondary_timing: SecondaryTiming
     },
     MonitorName(String),
+    /// The timings of a CVT 3-byte code descriptor.
+    CvtTimings(Vec<CvtTiming>),
     Undefined(u8, [u8; 13]),
     ManufacturerDefined(u8, [u8; 13])
 }
 
+impl MonitorDescriptor {
+    /// Whether range limits make sense: each minimum is at most its
+    /// maximum, and neither rates nor the pixel clock are zero. Other
+    /// descriptors are always consistent.
+    pub fn is_consistent(&self) -> bool {
+        match *self {
+            MonitorDescriptor::RangeLimits { vertical_rate, horizontal_rate, pixel_clock, .. } => {
+                0 < vertical_rate.0 && vertical_rate.0 <= vertical_rate.1
+                    && 0 < horizontal_rate.0 && horizontal_rate.0 <= horizontal_rate.1
+                    && pixel_clock > 0
+            },
+            _ => true
+        }
+    }
+
+    /// Range limits which admit every one of `modes`, widened by `margin`,
+    /// or `None` if there are no modes. The rates are rounded outwards to
+    /// whole Hz and kHz and the pixel clock up to 10MHz, like the fields
+    /// of the descriptor. They are capped at what a 1.4 EDID can give,
+    /// 510Hz, 510kHz and 2550MHz, see `range_limits_offsets` for the flags
+    /// it needs for rates over 255.
+    pub fn range_limits_from(modes: &[DetailedTiming], margin: &RangeMargin) -> Option<MonitorDescriptor> {
+        // Rounded the same way as `DetailedTiming::within_range_limits`.
+        let refresh = |mode: &DetailedTiming| (mode.refresh_rate() + 0.5) as u32;
+        let hrate = |mode: &DetailedTiming| (mode.horizontal_rate() / 1000.0 + 0.5) as u32;
+
+        let min_refresh = modes.iter().map(refresh).min()?.saturating_sub(margin.vertical_rate as u32);
+        let max_refresh = modes.iter().map(refresh).max()?.saturating_add(margin.vertical_rate as u32);
+        let min_hrate = modes.iter().map(hrate).min()?.saturating_mul(1000).saturating_sub(margin.horizontal_rate) / 1000;
+        let max_hrate = modes.iter().map(hrate).max()?.saturating_mul(1000).saturating_add(margin.horizontal_rate).div_ceil(1000);
+        let max_clock = modes.iter().map(|mode| mode.pixel_clock).max()?.saturating_add(margin.pixel_clock).div_ceil(10000000);
+
+        let rate = |rate: u32| core::cmp::min(rate, 510);
+        Some(MonitorDescriptor::RangeLimits {
+            vertical_rate: (rate(min_refresh) as u16, rate(max_refresh) as u16),
+            horizontal_rate: (rate(min_hrate) * 1000, rate(max_hrate) * 1000),
+            pixel_clock: core::cmp::min(max_clock, 255) * 10000000,
+            secondary_timing: SecondaryTiming::None
+        })
+    }
+
+    /// The rate offset flags for byte 4 of range limits, which EDID 1.4
+    /// needs to give rates over 255Hz or 255kHz, or `None` for other
+    /// descriptors. The offset is added to the maximum alone if only it
+    /// is over 255, and to both limits if the minimum is too.
+    pub fn range_limits_offsets(&self) -> Option<u8> {
+        match *self {
+            MonitorDescriptor::RangeLimits { vertical_rate, horizontal_rate, .. } => {
+                let flags = |min: u32, max: u32| match (min > 255, max > 255) {
+                    (true, _) => 0b11,
+                    (false, true) => 0b10,
+                    (false, false) => 0b00
+                };
+                Some(flags(vertical_rate.0 as u32, vertical_rate.1 as u32)
+                    | flags(horizontal_rate.0 / 1000, horizontal_rate.1 / 1000) << 2)
+            },
+            _ => None
+        }
+    }
+}
+
+/// How far `MonitorDescriptor::range_limits_from` widens the limits
+/// beyond the modes, with the rates and clock in Hz.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RangeMargin {
+    pub vertical_rate: u8,
+    pub horizontal_rate: u32,
+    pub pixel_clock: u32
+}
+
 /// Parameters for a secondary timing formula.
 #[derive(Debug, Clone)]
 pub enum SecondaryTiming {
@@ -967,10 +2438,758 @@ pub enum SecondaryTiming {
         k: f32,
         j: f32
     },
+    /// CVT support, with the pixel clock limit in Hz to the finer
+    /// precision this gives.
+    CVT {
+        /// The major and minor version of CVT.
+        version: (u8, u8),
+        max_pixel_clock: u32,
+        /// The widest line, or `None` if there is no limit.
+        max_active_pixels: Option<u16>,
+        /// Aspect ratios as width divided by height.
+        supported_aspect_ratios: Vec<f32>,
+        /// `None` for a reserved value.
+        preferred_aspect_ratio: Option<f32>,
+        /// Whether standard CVT blanking is supported.
+        standard_blanking: bool,
+        /// Whether CVT reduced blanking is supported.
+        reduced_blanking: bool,
+        scaling: CvtScaling,
+        /// The preferred vertical refresh rate in Hz.
+        preferred_refresh: u8
+    },
+    /// Any other kind of secondary timing, with its parameters. Lenient
+    /// parsing also gives kind 0x00 here when the padding is not
+    /// `0x0a 0x20 ...`, to keep those bytes.
     Other(u8, [u8; 7])
 }
 
-/// Parse EDID data from a Read value.
+// The aspect ratios of CVT range limits, in the order of the
+// supported ratio bits from the highest and of the preferred ratio.
+#[cfg(feature = "descriptors")]
+const CVT_ASPECT_RATIOS: [f32; 5] = [4.0/3.0, 16.0/9.0, 16.0/10.0, 5.0/4.0, 15.0/9.0];
+
+/// The kinds of scaling a display with CVT range limits supports.
+#[derive(Debug, Clone, Copy)]
+pub struct CvtScaling {
+    pub horizontal_shrink: bool,
+    pub horizontal_stretch: bool,
+    pub vertical_shrink: bool,
+    pub vertical_stretch: bool
+}
+
+/// A timing from a CVT 3-byte code, whose other parameters
+/// follow from the CVT formula.
+#[derive(Debug, Clone)]
+pub struct CvtTiming {
+    pub vertical_lines: u16,
+    /// The width divided by the height.
+    pub aspect_ratio: f32,
+    /// The preferred refresh rate in Hz.
+    pub preferred_refresh: u8,
+    /// The refresh rates supported with standard blanking, in Hz.
+    pub supported_refreshes: Vec<u8>,
+    /// Whether 60Hz is supported with reduced blanking.
+    pub rb_60: bool
+}
+
+#[cfg(feature = "descriptors")]
+impl CvtTiming {
+    // Read a three byte CVT code, which is `None` if the slot is unused.
+    fn parse(r: &mut Reader) -> Result<Option<CvtTiming>> {
+        let low = r.read_u8()?;
+        let high = r.read_u8()?;
+        let rates = r.read_u8()?;
+        if low == 0 && high == 0 && rates == 0 {
+            return Ok(None);
+        }
+
+        let supported_refreshes = [50, 60, 75, 85].iter().enumerate()
+            .filter(|&(i, _)| rates & (1 << (4 - i)) > 0)
+            .map(|(_, &rate)| rate)
+            .collect();
+
+        Ok(Some(CvtTiming {
+            vertical_lines: ((high as u16 >> 4) << 8 | low as u16) * 2 + 2,
+            aspect_ratio: match (high >> 2) & 0b11 {
+                0 => 4.0/3.0,
+                1 => 16.0/9.0,
+                2 => 16.0/10.0,
+                3 => 15.0/9.0,
+                _ => unreachable!()
+            },
+            preferred_refresh: [50, 60, 75, 85][((rates >> 5) & 0b11) as usize],
+            supported_refreshes,
+            rb_60: rates & 1 > 0
+        }))
+    }
+}
+
+impl CvtTiming {
+    /// The width, which CVT rounds down to a multiple of 8. A little is
+    /// added first so ratios just under their value as an `f32` still
+    /// give a whole multiple.
+    pub fn horizontal_resolution(&self) -> u16 {
+        ((self.vertical_lines as f32 * self.aspect_ratio + 0.01) / 8.0) as u16 * 8
+    }
+}
+
+/// Parse EDID data from a Read value. Like `EDID::parse` this never panics.
 pub fn parse<T: Read + 'static>(value: &mut T) -> Result<EDID> {
     EDID::parse(&mut Reader::new(value))
 }
+
+/// Parse EDID data from a Read value, with warnings for spec violations
+/// which were read past. See `EDID::parse_lenient`.
+pub fn parse_lenient<T: Read + 'static>(value: &mut T) -> Result<(EDID, Vec<Warning>)> {
+    EDID::parse_lenient(&mut Reader::new(value))
+}
+
+/// Decode a textual hex dump of an EDID into raw bytes.
+///
+/// This understands `xxd` and `xxd -p` output, `i2cdump` tables and the hex
+/// block at the top of `edid-decode` output. Address columns, trailing ASCII
+/// columns, `0x` prefixes, commas, blank lines and `#` comments are skipped,
+/// and digits may be in either case.
+pub fn decode_hex(text: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+
+    for line in text.lines() {
+        let mut line = line.trim();
+
+        // Skip blank lines and comments. Labels like `edid-decode (hex):`
+        // are left with no hex once the address column is removed below.
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // edid-decode separates the hex block from the decoded text
+        // with a line of dashes, so stop once we have seen some data.
+        if line.chars().all(|c| c == '-') {
+            if bytes.is_empty() {
+                continue;
+            } else {
+                break;
+            }
+        }
+
+        // An address column (`00:` or `00000010:`) means there may also
+        // be an ASCII column, which starts after a run of spaces.
+        if let Some(colon) = line.find(':') {
+            line = line[colon + 1..].trim_start();
+            if let Some(gap) = line.find("  ") {
+                line = &line[..gap];
+            }
+        }
+
+        // The column header of an i2cdump table: `0  1  2 ... f`.
+        if line.starts_with("0 ") && line.split_whitespace().nth(1) == Some("1") {
+            continue;
+        }
+
+        let tokens = line.split(|c: char| c.is_whitespace() || c == ',');
+        for token in tokens.filter(|token| !token.is_empty()) {
+            let token = token.strip_prefix("0x")
+                .or_else(|| token.strip_prefix("0X"))
+                .unwrap_or(token);
+
+            // Tokens are slices of the text, so this is where they start.
+            let offset = token.as_ptr() as usize - text.as_ptr() as usize;
+            ensure(token.len() % 2 == 0, Error::InvalidText { offset: offset + token.len() })?;
+            let digits = token.as_bytes();
+            for (i, pair) in digits.chunks(2).enumerate() {
+                let high = (pair[0] as char).to_digit(16).ok_or(Error::InvalidText { offset: offset + 2 * i })?;
+                let low = (pair[1] as char).to_digit(16).ok_or(Error::InvalidText { offset: offset + 2 * i + 1 })?;
+                bytes.push((high << 4 | low) as u8);
+            }
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Parse EDID data from a textual hex dump, see `decode_hex`.
+pub fn parse_hex(text: &str) -> Result<EDID> {
+    let bytes = decode_hex(text)?;
+    EDID::parse(&mut Reader::new(&mut &bytes[..]))
+}
+
+/// Decode base64 text, as found in `ioreg -a` plists and WMI exports.
+///
+/// Whitespace and line breaks are ignored and the trailing `=` padding may
+/// be present, partial or missing. The URL-safe alphabet is also accepted.
+pub fn decode_base64(text: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    // Bits which have been decoded but not yet output.
+    let mut acc = 0u32;
+    let mut bits = 0;
+    let mut padded = false;
+
+    for (offset, c) in text.bytes().enumerate() {
+        let val = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' => {
+                padded = true;
+                continue;
+            },
+            c if c.is_ascii_whitespace() => continue,
+            _ => return Err(Error::InvalidText { offset })
+        };
+
+        // Nothing but padding may follow padding.
+        ensure(!padded, Error::InvalidText { offset })?;
+
+        acc = acc << 6 | val as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+
+    // A lone trailing character cannot encode a whole byte.
+    ensure(bits < 6, Error::InvalidText { offset: text.len() })?;
+
+    Ok(bytes)
+}
+
+/// Parse EDID data from base64 text, see `decode_base64`.
+pub fn parse_base64(text: &str) -> Result<EDID> {
+    let bytes = decode_base64(text)?;
+    EDID::parse(&mut Reader::new(&mut &bytes[..]))
+}
+
+/// The panel ID the Linux kernel gives a raw base block, as returned by
+/// `drm_edid_get_panel_id` and used in the panel drivers' quirk tables.
+/// The manufacturer ID is kept big-endian in the top 16 bits and the
+/// product code, which is stored little-endian, is in the bottom 16 bits.
+pub fn panel_id(block: &[u8; 128]) -> u32 {
+    (block[8] as u32) << 24 | (block[9] as u32) << 16 | (block[11] as u32) << 8 | block[10] as u32
+}
+
+/// Write a panel ID the way the kernel prints it, such as `AUO 0x573d`.
+pub fn panel_id_name(id: u32) -> String {
+    let letter = |shift: u32| (((id >> shift) & 0x1f) as u8 + b'@') as char;
+    format!("{}{}{} {:#06x}", letter(26), letter(21), letter(16), id & 0xffff)
+}
+
+/// Find the EDIDs embedded anywhere in some data, such as a video BIOS
+/// image or a firmware update.
+///
+/// Every copy of the header whose base block checksum is right is a match.
+/// A match takes in the extension blocks which follow with right checksums,
+/// up to the count in the base block, and scanning goes on after them. With
+/// the `cta` feature an EDID Extension Override data block in the first
+/// extension replaces that count, as it does for `parse_stream`.
+#[cfg_attr(not(feature = "cta"), allow(unused_mut))]
+pub fn find_edids(data: &[u8]) -> Vec<EdidMatch> {
+    let header = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+    let valid = |block: &[u8]| block.iter().fold(0u8, |sum, &b| sum.wrapping_add(b)) == 0;
+
+    let mut matches = Vec::new();
+    let mut offset = 0;
+    while offset + 128 <= data.len() {
+        let base = &data[offset..offset + 128];
+        if base[..8] != header || !valid(base) {
+            offset += 1;
+            continue;
+        }
+
+        let mut len = 128;
+        let mut count = base[126] as usize;
+        while len < 128 * (1 + count) {
+            match data.get(offset + len..offset + len + 128) {
+                Some(block) if valid(block) => len += 128,
+                _ => break
+            }
+
+            #[cfg(feature = "cta")]
+            if len == 256 {
+                let mut first = [0u8; 128];
+                first.copy_from_slice(&data[offset + 128..offset + 256]);
+                count = ExtensionBlock(first).eeodb_extension_count().map_or(count, |n| n as usize);
+            }
+        }
+
+        matches.push(EdidMatch {
+            offset,
+            len,
+            complete: len == 128 * (1 + count),
+            edid: EDID::parse(&mut Reader::new(&mut &base[..]))
+        });
+        offset += len;
+    }
+
+    matches
+}
+
+/// Parse the first EDID found in some data which parses, returning where
+/// it starts. Leading junk, trailing noise and broken copies of the header
+/// are skipped, see `find_edids`.
+pub fn find_and_parse(data: &[u8]) -> Result<(usize, EDID)> {
+    let mut error = None;
+    for found in find_edids(data) {
+        match found.edid {
+            Ok(edid) => return Ok((found.offset, edid)),
+            Err(err) => {
+                error.get_or_insert(err);
+            }
+        }
+    }
+
+    // Without any header with a right checksum there is nothing to say where.
+    Err(error.unwrap_or(Error::InvalidHeader { offset: 0 }))
+}
+
+/// An EDID found by `find_edids`.
+#[derive(Debug, Clone)]
+pub struct EdidMatch {
+    /// Where the base block starts in the data.
+    pub offset: usize,
+    /// The length of the base block and the extension blocks found.
+    pub len: usize,
+    /// Whether every extension block was found.
+    pub complete: bool,
+    pub edid: Result<EDID>
+}
+
+/// Remove the identifying information from a raw EDID so that it can be
+/// shared, returning what was changed.
+///
+/// The numeric serial number is zeroed and each serial number string is
+/// replaced with zeros of the same length, in the base block and in the
+/// descriptors of CTA-861 extensions. With `clear_date` the manufacture week
+/// and year are zeroed too. The checksums of the changed blocks are updated
+/// and every other byte is left alone.
+pub fn anonymize(bytes: &mut [u8], clear_date: bool) -> Vec<Redaction> {
+    let mut redactions = Vec::new();
+    if bytes.len() < 128 {
+        return redactions;
+    }
+
+    if bytes[12..16] != [0, 0, 0, 0] {
+        bytes[12..16].copy_from_slice(&[0, 0, 0, 0]);
+        redactions.push(Redaction { field: RedactedField::SerialNumber, range: 12..16 });
+    }
+
+    if clear_date {
+        bytes[16..18].copy_from_slice(&[0, 0]);
+        redactions.push(Redaction { field: RedactedField::ManufactureDate, range: 16..18 });
+    }
+
+    for (index, block) in bytes.chunks_exact_mut(128).enumerate() {
+        let start = index * 128;
+
+        // Where the 18-byte descriptors are in this block.
+        let descriptors = match (index, block[0]) {
+            (0, _) => 54..126,
+            (_, 0x02) if (4..=127).contains(&block[2]) => block[2] as usize..127,
+            _ => continue
+        };
+
+        // The base block's serial number and date are already counted.
+        let before = if index == 0 { 0 } else { redactions.len() };
+        for offset in descriptors.step_by(18).filter(|offset| offset + 18 <= 127) {
+            let descriptor = &mut block[offset..offset + 18];
+            if descriptor[..3] != [0, 0, 0] || descriptor[3] != 0xff {
+                continue;
+            }
+
+            let len = descriptor[5..].iter().position(|&b| b == 0x0a).unwrap_or(13);
+            for b in &mut descriptor[5..5 + len] {
+                *b = b'0';
+            }
+            let range = start + offset + 5..start + offset + 5 + len;
+            redactions.push(Redaction { field: RedactedField::SerialString, range });
+        }
+
+        if redactions.len() > before {
+            let sum = block[..127].iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+            block[127] = 0u8.wrapping_sub(sum);
+        }
+    }
+
+    redactions
+}
+
+/// A field changed by `anonymize`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redaction {
+    pub field: RedactedField,
+    /// The bytes which were changed.
+    pub range: core::ops::Range<usize>
+}
+
+/// The kinds of identifying information removed by `anonymize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactedField {
+    /// The numeric serial number in the base block.
+    SerialNumber,
+    /// A serial number descriptor.
+    SerialString,
+    /// The week and year of manufacture.
+    ManufactureDate
+}
+
+/// Find every byte and bit of a raw EDID which the specification says must
+/// have a fixed value but does not. Parsing does not care about these, this
+/// is for checking EDIDs as they are written.
+///
+/// This covers the reserved bits of a 1.3 digital input, the reserved bytes
+/// and padding of every kind of display descriptor in the base block, and
+/// the padding after the detailed timings of CTA-861 extensions. An EDID
+/// Extension Override data block anywhere but first in the first extension
+/// is reported at its own header, which should not be there at all, and
+/// as the first data block there not being one.
+pub fn check_reserved(bytes: &[u8]) -> Vec<ReservedViolation> {
+    let mut violations = Vec::new();
+    if bytes.len() < 128 {
+        return violations;
+    }
+
+    let mut expect = |offset: usize, mask: u8, expected: u8, field: &'static str| {
+        if bytes[offset] & mask != expected {
+            violations.push(ReservedViolation { offset, mask, expected, found: bytes[offset] & mask, field });
+        }
+    };
+
+    // Digital inputs only define the DFP bit before 1.4.
+    if bytes[20] & (1 << 7) > 0 && bytes[18] == 1 && bytes[19] < 4 {
+        expect(20, 0b0111_1110, 0, "video input");
+    }
+
+    for start in (54..126).step_by(18) {
+        if bytes[start..start + 2] != [0, 0] {
+            continue;
+        }
+
+        let padding = |from: usize| (start + from..start + 18).map(|offset| (offset, 0xff, 0x20));
+        let zeros = |from: usize| (start + from..start + 18).map(|offset| (offset, 0xff, 0x00));
+
+        expect(start + 2, 0xff, 0x00, "display descriptor header");
+
+        let tag = bytes[start + 3];
+        // Range limits in 1.4 keep rate offsets in the otherwise reserved byte.
+        if tag != 0xfd || bytes[19] < 4 {
+            expect(start + 4, 0xff, 0x00, "display descriptor header");
+        }
+
+        let rules: Vec<(usize, u8, u8)> = match tag {
+            0xfc | 0xfe | 0xff => {
+                // Strings end with a newline and are padded with spaces.
+                match bytes[start + 5..start + 18].iter().position(|&b| b == 0x0a) {
+                    Some(end) => padding(5 + end + 1).collect(),
+                    None => Vec::new()
+                }
+            },
+            0xfd => match bytes[start + 10] {
+                0x00 | 0x01 => core::iter::once((start + 11, 0xff, 0x0a)).chain(padding(12)).collect(),
+                // The low bits of the aspect ratio and preference bytes of CVT support.
+                0x04 => vec![(start + 14, 0b111, 0), (start + 15, 0b111, 0)],
+                _ => Vec::new()
+            },
+            0xfb => {
+                core::iter::once((start + 15, 0xff, 0x0a)).chain(padding(16)).collect()
+            },
+            0xfa => vec![(start + 17, 0xff, 0x0a)],
+            // Only the version, the rest is colour management data.
+            0xf9 => vec![(start + 5, 0xff, 0x03)],
+            0xf8 => {
+                let mut rules = vec![(start + 5, 0xff, 0x01)];
+                for code in (start + 6..start + 18).step_by(3) {
+                    rules.push((code + 1, 0b0000_0011, 0));
+                    rules.push((code + 2, 0b1000_0000, 0));
+                }
+                rules
+            },
+            0xf7 => {
+                core::iter::once((start + 5, 0xff, 0x0a))
+                    .chain(core::iter::once((start + 11, 0b0000_1111, 0)))
+                    .chain(zeros(12)).collect()
+            },
+            0x10 => zeros(5).collect(),
+            _ => Vec::new()
+        };
+
+        let field = match tag {
+            0xfc | 0xfe | 0xff => "string descriptor padding",
+            0xfd => "range limits padding",
+            0xfb => "white point padding",
+            0xfa => "standard timings padding",
+            0xf9 => "colour management descriptor",
+            0xf8 => "CVT timing codes",
+            0xf7 => "established timings III",
+            _ => "dummy descriptor"
+        };
+        for (offset, mask, expected) in rules {
+            expect(offset, mask, expected, field);
+        }
+    }
+
+    let mut stray_eeodbs = Vec::new();
+    for start in (128..bytes.len()).step_by(128).filter(|&start| start + 128 <= bytes.len()) {
+        let block = &bytes[start..start + 128];
+        if block[0] != 0x02 || !(4..=127).contains(&block[2]) {
+            continue;
+        }
+
+        let mut i = 4;
+        while i < block[2] as usize {
+            if block[i] >> 5 == 7 && block[i] & 0x1f > 0 && block[i + 1] == 0x78 && (start, i) != (128, 4) {
+                stray_eeodbs.push(start + i);
+            }
+            i += 1 + (block[i] & 0x1f) as usize;
+        }
+
+        // The detailed timings end at the first zero pixel clock,
+        // and everything from there to the checksum is padding.
+        let mut offset = block[2] as usize;
+        while offset + 18 <= 127 && block[offset..offset + 2] != [0, 0] {
+            offset += 18;
+        }
+        for offset in start + offset..start + 127 {
+            expect(offset, 0xff, 0x00, "CTA-861 padding");
+        }
+    }
+
+    // The header of an EEODB with its two byte payload.
+    if !stray_eeodbs.is_empty() {
+        expect(132, 0xff, 0xe2, "EEODB placement");
+    }
+    // No other data block may be an EEODB at all.
+    for offset in stray_eeodbs {
+        violations.push(ReservedViolation { offset, mask: 0xff, expected: 0x00, found: bytes[offset], field: "stray EEODB" });
+    }
+
+    violations
+}
+
+/// A byte, or some bits of one, which should have a fixed value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReservedViolation {
+    /// Offset of the byte from the start of the EDID.
+    pub offset: usize,
+    /// The bits which are checked.
+    pub mask: u8,
+    /// What the checked bits should be.
+    pub expected: u8,
+    /// What the checked bits are.
+    pub found: u8,
+    /// The structure the byte belongs to.
+    pub field: &'static str
+}
+
+/// Find the parts of a raw EDID which the crate does not decode: display
+/// descriptors with undefined tags, extension blocks of kinds it does not
+/// know, and CTA-861 vendor and extended tag data blocks it does not know.
+///
+/// This works on the raw data since the parsed `EDID` does not keep
+/// where everything came from. Without the `cta` feature every extension
+/// block is reported.
+pub fn unparsed(bytes: &[u8]) -> Vec<UnparsedRegion> {
+    let mut regions = Vec::new();
+    if bytes.len() < 128 {
+        return regions;
+    }
+
+    for start in (54..126).step_by(18) {
+        let tag = bytes[start + 3];
+        // CVT 3-byte codes (0xf8) are decoded into `CvtTimings`.
+        if bytes[start..start + 2] == [0, 0] && ((0x11..=0xf7).contains(&tag) || tag == 0xf9) {
+            regions.push(UnparsedRegion {
+                block: 0,
+                range: start..start + 18,
+                kind: UnparsedKind::DescriptorTag(tag),
+                data: bytes[start..start + 18].to_vec()
+            });
+        }
+    }
+
+    for (index, block) in bytes[128..].chunks_exact(128).enumerate() {
+        let base = 128 * (index + 1);
+        let region = |range: core::ops::Range<usize>, kind| UnparsedRegion {
+            block: index + 1,
+            range: base + range.start..base + range.end,
+            kind,
+            data: block[range].to_vec()
+        };
+
+        #[cfg(feature = "cta")]
+        if block[0] == 0x02 {
+            let mut raw = [0u8; 128];
+            raw.copy_from_slice(block);
+            for (offset, tag, payload) in ExtensionBlock(raw).data_blocks_at() {
+                let range = offset..offset + 1 + payload.len();
+                match tag {
+                    3 if payload.len() >= 3 && !KNOWN_OUIS.contains(&[payload[0], payload[1], payload[2]]) => {
+                        let oui = (payload[2] as u32) << 16 | (payload[1] as u32) << 8 | payload[0] as u32;
+                        regions.push(region(range, UnparsedKind::VendorOui(oui)));
+                    },
+                    // The vendor specific video data block.
+                    7 if payload.len() >= 4 && payload[0] == 0x01 && !KNOWN_VIDEO_OUIS.contains(&[payload[1], payload[2], payload[3]]) => {
+                        let oui = (payload[3] as u32) << 16 | (payload[2] as u32) << 8 | payload[1] as u32;
+                        regions.push(region(range, UnparsedKind::VendorOui(oui)));
+                    },
+                    7 if payload.len() >= 4 && payload[0] == 0x01 => (),
+                    // Including vendor specific video data blocks too short for an OUI.
+                    7 if !payload.is_empty() && !KNOWN_EXTENDED_TAGS.contains(&payload[0]) => {
+                        regions.push(region(range, UnparsedKind::ExtendedTag(payload[0])));
+                    },
+                    _ => ()
+                }
+            }
+            continue;
+        }
+
+        regions.push(region(0..128, UnparsedKind::ExtensionTag(block[0])));
+    }
+
+    regions
+}
+
+// Vendor specific data blocks which are decoded, by their OUI as stored:
+// HDMI, HDMI Forum, AMD and Microsoft.
+#[cfg(feature = "cta")]
+const KNOWN_OUIS: &[[u8; 3]] = &[[0x03, 0x0c, 0x00], [0xd8, 0x5d, 0xc4], [0x1a, 0x00, 0x00], [0x5c, 0x12, 0xca]];
+// Vendor specific video data blocks which are decoded: Dolby Vision and HDR10+.
+#[cfg(feature = "cta")]
+const KNOWN_VIDEO_OUIS: &[[u8; 3]] = &[[0x46, 0xd0, 0x00], [0x8b, 0x84, 0x90]];
+// Extended tags which are decoded: the colorimetry, HDR static metadata,
+// EDID Extension Override and HDMI Forum Sink Capability data blocks.
+#[cfg(feature = "cta")]
+const KNOWN_EXTENDED_TAGS: &[u8] = &[0x05, 0x06, 0x78, 0x79];
+
+/// A part of a raw EDID which is not decoded, from `unparsed`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnparsedRegion {
+    /// The block it is in, 0 being the base block.
+    pub block: usize,
+    /// Where it is in the data.
+    pub range: core::ops::Range<usize>,
+    pub kind: UnparsedKind,
+    /// The bytes themselves.
+    pub data: Vec<u8>
+}
+
+/// What kind of data an `UnparsedRegion` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnparsedKind {
+    /// A display descriptor with this undefined tag.
+    DescriptorTag(u8),
+    /// A whole extension block with this tag.
+    ExtensionTag(u8),
+    /// A vendor specific data block, or vendor specific video
+    /// data block, with this OUI.
+    VendorOui(u32),
+    /// A data block with this extended tag.
+    ExtendedTag(u8)
+}
+
+/// A raw extension block following the base EDID block.
+#[cfg(feature = "extensions")]
+#[derive(Debug, Clone)]
+pub struct ExtensionBlock(pub [u8; 128]);
+
+#[cfg(feature = "extensions")]
+impl ExtensionBlock {
+    /// The tag identifying the kind of extension, such as 0x02 for CTA-861.
+    pub fn tag(&self) -> u8 {
+        self.0[0]
+    }
+}
+
+/// Parse EDIDs stored back to back, each with its extension blocks, until
+/// the end of the data. See `EdidStream`.
+#[cfg(feature = "extensions")]
+pub fn parse_stream<T: Read>(value: T) -> EdidStream<T> {
+    EdidStream { value, offset: 0, done: false }
+}
+
+/// Iterator over the EDIDs in a stream, returned by `parse_stream`.
+///
+/// Each item gives the offset of the EDID's base block in the stream and
+/// the parsed EDID with its extension blocks. An EDID which fails to parse
+/// yields an error and the stream continues after its extension blocks. A
+/// read error or a partial record at the end yields an error and ends it.
+#[cfg(feature = "extensions")]
+pub struct EdidStream<T: Read> {
+    value: T,
+    offset: usize,
+    done: bool
+}
+
+#[cfg(feature = "extensions")]
+impl<T: Read> EdidStream<T> {
+    // Fill `block`, returning how many bytes there were before the end.
+    fn read_block(&mut self, block: &mut [u8; 128]) -> Result<usize> {
+        let mut len = 0;
+        while len < block.len() {
+            match self.value.read(&mut block[len..]).ok_or(Error::ReadFailed { offset: self.offset + len })? {
+                0 => break,
+                num => len += num
+            }
+        }
+
+        self.offset += len;
+        Ok(len)
+    }
+
+    #[cfg_attr(not(feature = "cta"), allow(unused_mut))]
+    fn next_record(&mut self) -> Option<Result<(EDID, Vec<ExtensionBlock>)>> {
+        let mut base = [0u8; 128];
+        match self.read_block(&mut base) {
+            Ok(0) => {
+                self.done = true;
+                return None;
+            },
+            Ok(128) => (),
+            Ok(len) => return Some(self.fail(Error::UnexpectedEof { offset: self.offset, needed: 128 - len })),
+            Err(err) => return Some(self.fail(err))
+        }
+
+        // Read the extensions even if the base block is bad,
+        // so that the next EDID starts in the right place.
+        let mut extensions = Vec::new();
+        let mut count = base[126] as usize;
+        while extensions.len() < count {
+            let mut block = [0u8; 128];
+            match self.read_block(&mut block) {
+                Ok(128) => extensions.push(ExtensionBlock(block)),
+                Ok(len) => return Some(self.fail(Error::UnexpectedEof { offset: self.offset, needed: 128 - len })),
+                Err(err) => return Some(self.fail(err))
+            }
+
+            #[cfg(feature = "cta")]
+            if extensions.len() == 1 {
+                count = extensions[0].eeodb_extension_count().map_or(count, |n| n as usize);
+            }
+        }
+
+        Some(EDID::parse(&mut Reader::new(&mut &base[..])).map(|edid| (edid, extensions)))
+    }
+
+    // End the stream with an error.
+    fn fail<U>(&mut self, err: Error) -> Result<U> {
+        self.done = true;
+        Err(err)
+    }
+}
+
+#[cfg(feature = "extensions")]
+impl<T: Read> Iterator for EdidStream<T> {
+    type Item = (usize, Result<(EDID, Vec<ExtensionBlock>)>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let offset = self.offset;
+        self.next_record().map(|record| (offset, record))
+    }
+}
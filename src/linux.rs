@@ -0,0 +1,92 @@
+//! Helpers for gathering EDIDs on Linux.
+
+#[cfg(feature = "linux-sysfs")]
+use std::{fs, io, path::Path};
+
+#[cfg(any(feature = "linux-sysfs", feature = "drm"))]
+use crate::{EDID, Reader, Result};
+
+#[cfg(all(feature = "drm", target_os = "linux"))]
+pub mod drm;
+#[cfg(all(feature = "i2c-linux", target_os = "linux"))]
+pub mod i2c;
+#[cfg(all(feature = "dp-aux", target_os = "linux"))]
+pub mod dp_aux;
+
+/// Where the kernel exposes DRM connectors.
+#[cfg(feature = "linux-sysfs")]
+const SYSFS_DRM: &str = "/sys/class/drm";
+
+/// Connection state of a DRM connector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectorStatus {
+    Connected,
+    Disconnected,
+    Unknown
+}
+
+/// The EDID found on a single DRM connector.
+#[cfg(feature = "linux-sysfs")]
+#[derive(Debug, Clone)]
+pub struct ConnectorEdid {
+    /// Connector name as it appears in sysfs, e.g. `card0-HDMI-A-1`.
+    pub connector: String,
+    pub status: ConnectorStatus,
+    /// The raw EDID blob including any extension blocks.
+    pub raw: Vec<u8>,
+    /// The parsed EDID, or `None` if the blob is empty, which
+    /// is normal for connectors with nothing plugged in.
+    pub edid: Option<Result<EDID>>
+}
+
+/// Read and parse the EDID of every DRM connector in `/sys/class/drm`.
+#[cfg(feature = "linux-sysfs")]
+pub fn enumerate() -> io::Result<Vec<ConnectorEdid>> {
+    enumerate_in(SYSFS_DRM)
+}
+
+/// Like `enumerate`, but walks the given directory instead
+/// of `/sys/class/drm`, which is useful for captured trees.
+#[cfg(feature = "linux-sysfs")]
+pub fn enumerate_in<P: AsRef<Path>>(root: P) -> io::Result<Vec<ConnectorEdid>> {
+    let mut connectors = Vec::new();
+
+    for entry in fs::read_dir(root)? {
+        let path = entry?.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        // Only connectors have an `edid` file, this skips the cards themselves.
+        let raw = match fs::read(path.join("edid")) {
+            Ok(raw) => raw,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e)
+        };
+
+        let status = match fs::read_to_string(path.join("status")) {
+            Ok(ref s) if s.trim() == "connected" => ConnectorStatus::Connected,
+            Ok(ref s) if s.trim() == "disconnected" => ConnectorStatus::Disconnected,
+            _ => ConnectorStatus::Unknown
+        };
+
+        connectors.push(ConnectorEdid {
+            connector: path.file_name().unwrap_or_default().to_string_lossy().into_owned(),
+            status, edid: parse_blob(&raw), raw
+        });
+    }
+
+    connectors.sort_by(|a, b| a.connector.cmp(&b.connector));
+
+    Ok(connectors)
+}
+
+// Empty blobs are how the kernel reports that nothing is attached.
+#[cfg(any(feature = "linux-sysfs", feature = "drm"))]
+fn parse_blob(raw: &[u8]) -> Option<Result<EDID>> {
+    if raw.is_empty() {
+        None
+    } else {
+        Some(EDID::parse(&mut Reader::new(&mut &raw[..])))
+    }
+}
@@ -0,0 +1,193 @@
+//! Reading EDIDs through a DisplayPort AUX channel device.
+//!
+//! The `/dev/drm_dp_aux*` devices are read by seeking to a DPCD address,
+//! and only give access to the DPCD registers with native AUX transactions.
+//! The EDID itself is read with I2C-over-AUX through the i2c adapter the
+//! kernel registers for the same AUX channel, which needs the `i2c-dev`
+//! module loaded. `DpAux` does both, with the same reading API as
+//! `DdcReader` so callers can treat either kind of connection alike.
+//!
+//! The kernel retries deferred and unacknowledged transactions a few times
+//! itself. When it gives up the error is turned into an `AuxError` inside
+//! the `io::Error`, and `DpAux::retries` more attempts are made.
+
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use super::i2c::{DdcEdid, DdcReader};
+
+/// Where the kernel describes AUX channel devices.
+const SYSFS_DP_AUX: &str = "/sys/class/drm_dp_aux_dev";
+/// Where the kernel describes i2c-dev adapters.
+const SYSFS_I2C_DEV: &str = "/sys/class/i2c-dev";
+
+// The errno values AUX failures come back as.
+const EIO: i32 = 5;
+const ENXIO: i32 = 6;
+const EAGAIN: i32 = 11;
+const EBUSY: i32 = 16;
+const ETIMEDOUT: i32 = 110;
+const EREMOTEIO: i32 = 121;
+
+/// A failed AUX transaction, carried inside the `io::Error`s from `DpAux`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuxError {
+    /// The sink kept asking for the transaction to be retried later.
+    Defer,
+    /// The sink, or the I2C device behind it, did not acknowledge.
+    Nack,
+    /// Nothing replied.
+    Timeout
+}
+
+impl AuxError {
+    /// The AUX error which `err` stands for, if it came from an AUX
+    /// channel or its i2c adapter.
+    pub fn of(err: &io::Error) -> Option<AuxError> {
+        if let Some(aux) = err.get_ref().and_then(|inner| inner.downcast_ref::<AuxError>()) {
+            return Some(*aux);
+        }
+
+        match err.raw_os_error()? {
+            EAGAIN | EBUSY => Some(AuxError::Defer),
+            EIO | ENXIO | EREMOTEIO => Some(AuxError::Nack),
+            ETIMEDOUT => Some(AuxError::Timeout),
+            _ => None
+        }
+    }
+
+    // Put the AUX error which `err` stands for inside it.
+    fn wrap(err: io::Error) -> io::Error {
+        match AuxError::of(&err) {
+            Some(aux) if err.get_ref().is_none() => io::Error::new(err.kind(), aux),
+            _ => err
+        }
+    }
+}
+
+impl fmt::Display for AuxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AuxError::Defer => write!(f, "AUX transaction deferred"),
+            AuxError::Nack => write!(f, "AUX transaction not acknowledged"),
+            AuxError::Timeout => write!(f, "AUX transaction timed out")
+        }
+    }
+}
+
+impl Error for AuxError {}
+
+/// A DisplayPort AUX channel device such as `/dev/drm_dp_aux0`.
+pub struct DpAux {
+    device: File,
+    /// The name of the AUX channel, e.g. `DPDDC-B`, which
+    /// its i2c adapter shares.
+    pub name: String,
+    /// The number of bytes fetched per I2C-over-AUX transfer. AUX carries
+    /// at most 16 bytes at a time, the kernel splits larger transfers.
+    pub chunk_size: usize,
+    /// How many more times a failed transaction or an EDID block
+    /// with a bad checksum is attempted before giving up.
+    pub retries: u32
+}
+
+impl DpAux {
+    /// Open an AUX channel device.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<DpAux> {
+        let path = path.as_ref();
+        let device = OpenOptions::new().read(true).write(true).open(path)?;
+
+        let node = path.file_name().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "not a drm_dp_aux device path")
+        })?;
+        let name = fs::read_to_string(Path::new(SYSFS_DP_AUX).join(node).join("name"))?;
+
+        Ok(DpAux { device, name: name.trim_end().to_string(), chunk_size: 128, retries: 3 })
+    }
+
+    /// Read DPCD registers starting at `address`, returning how many bytes
+    /// were read. This is less than `buf.len()` only past the end of the
+    /// DPCD address space.
+    pub fn read_dpcd(&self, address: u32, buf: &mut [u8]) -> io::Result<usize> {
+        let mut attempts = 0;
+        loop {
+            match self.try_read_dpcd(address, buf).map_err(AuxError::wrap) {
+                Err(ref err) if AuxError::of(err).is_some() && attempts < self.retries => attempts += 1,
+                result => return result
+            }
+        }
+    }
+
+    // Seek to the address and read, as the kernel may split a
+    // read into several transactions and return after any of them.
+    fn try_read_dpcd(&self, address: u32, buf: &mut [u8]) -> io::Result<usize> {
+        let mut device = &self.device;
+        device.seek(SeekFrom::Start(address as u64))?;
+
+        let mut len = 0;
+        while len < buf.len() {
+            match device.read(&mut buf[len..]) {
+                Ok(0) => break,
+                Ok(num) => len += num,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err)
+            }
+        }
+
+        Ok(len)
+    }
+
+    /// The DPCD revision of the sink, e.g. `0x12` for DisplayPort 1.2.
+    /// This fails if there is no sink on the other end.
+    pub fn dpcd_revision(&self) -> io::Result<u8> {
+        let mut revision = [0u8];
+        match self.read_dpcd(0, &mut revision)? {
+            1 => Ok(revision[0]),
+            _ => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "short DPCD read"))
+        }
+    }
+
+    /// The `/dev/i2c-*` adapter for I2C-over-AUX on this channel.
+    pub fn i2c_adapter(&self) -> io::Result<PathBuf> {
+        for entry in fs::read_dir(SYSFS_I2C_DEV)? {
+            let entry = entry?;
+            match fs::read_to_string(entry.path().join("name")) {
+                Ok(name) if name.trim_end() == self.name => {
+                    return Ok(Path::new("/dev").join(entry.file_name()));
+                },
+                _ => continue
+            }
+        }
+
+        Err(io::Error::new(io::ErrorKind::NotFound, format!("no i2c adapter named {}", self.name)))
+    }
+
+    /// Open a DDC reader on this channel's i2c adapter, with
+    /// the same chunk size and retries.
+    pub fn ddc(&self) -> io::Result<DdcReader> {
+        let mut ddc = DdcReader::open(self.i2c_adapter()?)?;
+        ddc.chunk_size = self.chunk_size;
+        ddc.retries = self.retries;
+        Ok(ddc)
+    }
+
+    /// Read the base block and all the extension blocks it announces,
+    /// like `DdcReader::read_blocks`.
+    pub fn read_blocks(&self) -> io::Result<Vec<[u8; 128]>> {
+        self.ddc()?.read_blocks().map_err(AuxError::wrap)
+    }
+
+    /// Read all the blocks and parse the base block, like `DdcReader::read`.
+    pub fn read(&self) -> io::Result<DdcEdid> {
+        self.ddc()?.read().map_err(AuxError::wrap)
+    }
+
+    /// Read a single 128-byte block, verifying its checksum,
+    /// like `DdcReader::read_block`.
+    pub fn read_block(&self, index: u8) -> io::Result<[u8; 128]> {
+        self.ddc()?.read_block(index).map_err(AuxError::wrap)
+    }
+}
@@ -0,0 +1,367 @@
+//! Reading EDIDs through the DRM mode-setting ioctls.
+//!
+//! Unlike sysfs this asks the driver directly, which probes the connector
+//! and so always reflects what is currently plugged in.
+
+use std::convert::TryFrom;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::mem::size_of;
+use std::os::raw::{c_int, c_ulong};
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+use crate::{EDID, Error, Result, DetailedTiming, SyncType, SyncLine, SyncPolarity};
+use super::{ConnectorStatus, parse_blob};
+
+extern "C" {
+    fn ioctl(fd: c_int, request: c_ulong, ...) -> c_int;
+}
+
+// `_IOWR('d', nr, T)` as defined by the kernel's asm-generic/ioctl.h.
+const fn drm_iowr<T>(nr: u32) -> c_ulong {
+    ((3 << 30) | ((size_of::<T>() as u32) << 16) | (0x64 << 8) | nr) as c_ulong
+}
+
+const DRM_IOCTL_MODE_GETRESOURCES: c_ulong = drm_iowr::<CardRes>(0xa0);
+const DRM_IOCTL_MODE_GETCONNECTOR: c_ulong = drm_iowr::<GetConnector>(0xa7);
+const DRM_IOCTL_MODE_GETPROPERTY: c_ulong = drm_iowr::<GetProperty>(0xaa);
+const DRM_IOCTL_MODE_GETPROPBLOB: c_ulong = drm_iowr::<GetBlob>(0xac);
+const DRM_IOCTL_MODE_OBJ_GETPROPERTIES: c_ulong = drm_iowr::<ObjGetProperties>(0xb9);
+
+const DRM_MODE_OBJECT_CONNECTOR: u32 = 0xc0c0c0c0;
+
+/// Positive horizontal sync, for `ModeInfo::flags`.
+pub const DRM_MODE_FLAG_PHSYNC: u32 = 1 << 0;
+/// Negative horizontal sync.
+pub const DRM_MODE_FLAG_NHSYNC: u32 = 1 << 1;
+/// Positive vertical sync.
+pub const DRM_MODE_FLAG_PVSYNC: u32 = 1 << 2;
+/// Negative vertical sync.
+pub const DRM_MODE_FLAG_NVSYNC: u32 = 1 << 3;
+pub const DRM_MODE_FLAG_INTERLACE: u32 = 1 << 4;
+/// Composite sync.
+pub const DRM_MODE_FLAG_CSYNC: u32 = 1 << 6;
+/// Positive composite sync.
+pub const DRM_MODE_FLAG_PCSYNC: u32 = 1 << 7;
+/// Negative composite sync.
+pub const DRM_MODE_FLAG_NCSYNC: u32 = 1 << 8;
+
+/// The mode was supplied by the driver, for `ModeInfo::type_`.
+pub const DRM_MODE_TYPE_DRIVER: u32 = 1 << 6;
+/// The mode is the preferred mode of the display.
+pub const DRM_MODE_TYPE_PREFERRED: u32 = 1 << 3;
+
+// Names the kernel gives each `DRM_MODE_CONNECTOR_*` type.
+const CONNECTOR_TYPE_NAMES: [&str; 21] = [
+    "Unknown", "VGA", "DVI-I", "DVI-D", "DVI-A", "Composite", "SVIDEO",
+    "LVDS", "Component", "DIN", "DP", "HDMI-A", "HDMI-B", "TV", "eDP",
+    "Virtual", "DSI", "DPI", "Writeback", "SPI", "USB"
+];
+
+// The following mirror the structures in the kernel's drm_mode.h.
+
+#[repr(C)]
+#[derive(Default)]
+struct CardRes {
+    fb_id_ptr: u64,
+    crtc_id_ptr: u64,
+    connector_id_ptr: u64,
+    encoder_id_ptr: u64,
+    count_fbs: u32,
+    count_crtcs: u32,
+    count_connectors: u32,
+    count_encoders: u32,
+    min_width: u32,
+    max_width: u32,
+    min_height: u32,
+    max_height: u32
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct GetConnector {
+    encoders_ptr: u64,
+    modes_ptr: u64,
+    props_ptr: u64,
+    prop_values_ptr: u64,
+    count_modes: u32,
+    count_props: u32,
+    count_encoders: u32,
+    encoder_id: u32,
+    connector_id: u32,
+    connector_type: u32,
+    connector_type_id: u32,
+    connection: u32,
+    mm_width: u32,
+    mm_height: u32,
+    subpixel: u32,
+    pad: u32
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct ObjGetProperties {
+    props_ptr: u64,
+    prop_values_ptr: u64,
+    count_props: u32,
+    obj_id: u32,
+    obj_type: u32
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct GetProperty {
+    values_ptr: u64,
+    enum_blob_ptr: u64,
+    prop_id: u32,
+    flags: u32,
+    name: [u8; 32],
+    count_values: u32,
+    count_enum_blobs: u32
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct GetBlob {
+    blob_id: u32,
+    length: u32,
+    data: u64
+}
+
+/// A display mode laid out exactly like the kernel's `drm_mode_modeinfo`,
+/// so it can be handed straight to the mode-setting ioctls.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModeInfo {
+    /// Pixel clock in kHz.
+    pub clock: u32,
+    pub hdisplay: u16,
+    pub hsync_start: u16,
+    pub hsync_end: u16,
+    pub htotal: u16,
+    pub hskew: u16,
+    pub vdisplay: u16,
+    pub vsync_start: u16,
+    pub vsync_end: u16,
+    pub vtotal: u16,
+    pub vscan: u16,
+    /// Refresh rate in Hz, rounded to the nearest integer.
+    pub vrefresh: u32,
+    /// A combination of the `DRM_MODE_FLAG_*` constants.
+    pub flags: u32,
+    /// A combination of the `DRM_MODE_TYPE_*` constants.
+    pub type_: u32,
+    /// NUL terminated name such as `1920x1080`.
+    pub name: [u8; 32]
+}
+
+/// Converts the same way the kernel does for detailed timings: the vertical
+/// values of interlaced modes are doubled from fields to frames, borders are
+/// ignored and the mode is marked `DRM_MODE_TYPE_DRIVER`. Stereo modes are
+/// converted as if they were 2D. Fails if a derived value exceeds 16 bits.
+impl TryFrom<&DetailedTiming> for ModeInfo {
+    type Error = Error;
+
+    fn try_from(timing: &DetailedTiming) -> Result<ModeInfo> {
+        let overflow = Error::InvalidTiming("Timing does not fit in a DRM mode.");
+        let add = |a: u16, b: u16| a.checked_add(b).ok_or(overflow);
+
+        let hdisplay = timing.active.0;
+        let hsync_start = add(hdisplay, timing.front_porch.0)?;
+        let hsync_end = add(hsync_start, timing.sync_length.0)?;
+        let htotal = add(hsync_end, timing.back_porch.0)?;
+
+        let mut vdisplay = timing.active.1;
+        let mut vsync_start = add(vdisplay, timing.front_porch.1)?;
+        let mut vsync_end = add(vsync_start, timing.sync_length.1)?;
+        let mut vtotal = add(vsync_end, timing.back_porch.1)?;
+
+        let mut flags = match timing.sync_type {
+            SyncType::Seperate { horizontal, vertical } => {
+                (match horizontal {
+                    SyncPolarity::Positive => DRM_MODE_FLAG_PHSYNC,
+                    SyncPolarity::Negative => DRM_MODE_FLAG_NHSYNC
+                }) | (match vertical {
+                    SyncPolarity::Positive => DRM_MODE_FLAG_PVSYNC,
+                    SyncPolarity::Negative => DRM_MODE_FLAG_NVSYNC
+                })
+            },
+            SyncType::Composite { line: SyncLine::Digital(SyncPolarity::Positive), .. } =>
+                DRM_MODE_FLAG_CSYNC | DRM_MODE_FLAG_PCSYNC,
+            SyncType::Composite { line: SyncLine::Digital(SyncPolarity::Negative), .. } =>
+                DRM_MODE_FLAG_CSYNC | DRM_MODE_FLAG_NCSYNC,
+            SyncType::Composite { .. } => DRM_MODE_FLAG_CSYNC
+        };
+
+        if timing.interlaced {
+            let double = |v: u16| v.checked_mul(2).ok_or(overflow);
+            vdisplay = double(vdisplay)?;
+            vsync_start = double(vsync_start)?;
+            vsync_end = double(vsync_end)?;
+            vtotal = double(vtotal)? | 1;
+            flags |= DRM_MODE_FLAG_INTERLACE;
+        }
+
+        // The refresh rate of an interlaced mode is its field rate.
+        let num = timing.pixel_clock as u64 * if timing.interlaced { 2 } else { 1 };
+        let den = htotal as u64 * vtotal as u64;
+        let vrefresh = (num + den / 2).checked_div(den).unwrap_or(0) as u32;
+
+        let mut name = [0u8; 32];
+        let text = format!("{}x{}{}", hdisplay, vdisplay, if timing.interlaced { "i" } else { "" });
+        name[..text.len()].copy_from_slice(text.as_bytes());
+
+        Ok(ModeInfo {
+            clock: timing.pixel_clock / 1000,
+            hdisplay, hsync_start, hsync_end, htotal, hskew: 0,
+            vdisplay, vsync_start, vsync_end, vtotal, vscan: 0,
+            vrefresh, flags, type_: DRM_MODE_TYPE_DRIVER, name
+        })
+    }
+}
+
+/// The EDID found on a single connector of a DRM device.
+#[derive(Debug, Clone)]
+pub struct DrmConnectorEdid {
+    /// Mode-setting object id of the connector.
+    pub connector_id: u32,
+    /// The `DRM_MODE_CONNECTOR_*` type of the connector,
+    pub connector_type: u32,
+    /// and its index among connectors of that type, starting from 1.
+    pub connector_type_id: u32,
+    pub status: ConnectorStatus,
+    /// The raw EDID blob including any extension blocks.
+    pub raw: Vec<u8>,
+    /// The parsed EDID, or `None` if the connector has no EDID.
+    pub edid: Option<Result<EDID>>
+}
+
+impl DrmConnectorEdid {
+    /// The connector name used by the kernel, e.g. `HDMI-A-1`.
+    pub fn name(&self) -> String {
+        let kind = CONNECTOR_TYPE_NAMES.get(self.connector_type as usize).unwrap_or(&"Unknown");
+        format!("{}-{}", kind, self.connector_type_id)
+    }
+}
+
+/// Read and parse the EDID of every connector of a DRM device,
+/// given the path to its primary node such as `/dev/dri/card0`.
+///
+/// Render nodes do not support mode-setting and cannot be used here.
+pub fn enumerate<P: AsRef<Path>>(card: P) -> io::Result<Vec<DrmConnectorEdid>> {
+    let card = OpenOptions::new().read(true).write(true).open(card)?;
+
+    let ids = loop {
+        let mut res = CardRes::default();
+        drm_ioctl(&card, DRM_IOCTL_MODE_GETRESOURCES, &mut res)?;
+
+        let count = res.count_connectors;
+        let mut ids = vec![0u32; count as usize];
+        let mut res = CardRes {
+            connector_id_ptr: ids.as_mut_ptr() as u64,
+            count_connectors: count,
+            ..CardRes::default()
+        };
+        drm_ioctl(&card, DRM_IOCTL_MODE_GETRESOURCES, &mut res)?;
+
+        // A connector may have been hotplugged in between, so try again.
+        if res.count_connectors <= count {
+            ids.truncate(res.count_connectors as usize);
+            break ids;
+        }
+    };
+
+    ids.into_iter().map(|id| connector(&card, id)).collect()
+}
+
+fn connector(card: &File, id: u32) -> io::Result<DrmConnectorEdid> {
+    // Leaving all the counts at zero makes the kernel probe the connector.
+    let mut conn = GetConnector { connector_id: id, ..GetConnector::default() };
+    drm_ioctl(card, DRM_IOCTL_MODE_GETCONNECTOR, &mut conn)?;
+
+    let status = match conn.connection {
+        1 => ConnectorStatus::Connected,
+        2 => ConnectorStatus::Disconnected,
+        _ => ConnectorStatus::Unknown
+    };
+
+    let raw = match edid_blob_id(card, id)? {
+        Some(blob_id) => blob(card, blob_id)?,
+        None => Vec::new()
+    };
+
+    Ok(DrmConnectorEdid {
+        connector_id: id,
+        connector_type: conn.connector_type,
+        connector_type_id: conn.connector_type_id,
+        status, edid: parse_blob(&raw), raw
+    })
+}
+
+// Find the blob id held by the connector's `EDID` property, if any.
+fn edid_blob_id(card: &File, connector: u32) -> io::Result<Option<u32>> {
+    let (ids, values) = loop {
+        let mut props = ObjGetProperties {
+            obj_id: connector, obj_type: DRM_MODE_OBJECT_CONNECTOR,
+            ..ObjGetProperties::default()
+        };
+        drm_ioctl(card, DRM_IOCTL_MODE_OBJ_GETPROPERTIES, &mut props)?;
+
+        let count = props.count_props;
+        let mut ids = vec![0u32; count as usize];
+        let mut values = vec![0u64; count as usize];
+        props.props_ptr = ids.as_mut_ptr() as u64;
+        props.prop_values_ptr = values.as_mut_ptr() as u64;
+        drm_ioctl(card, DRM_IOCTL_MODE_OBJ_GETPROPERTIES, &mut props)?;
+
+        if props.count_props <= count {
+            ids.truncate(props.count_props as usize);
+            values.truncate(props.count_props as usize);
+            break (ids, values);
+        }
+    };
+
+    for (&prop_id, &value) in ids.iter().zip(values.iter()) {
+        let mut prop = GetProperty { prop_id, ..GetProperty::default() };
+        drm_ioctl(card, DRM_IOCTL_MODE_GETPROPERTY, &mut prop)?;
+
+        if prop.name.split(|&c| c == 0).next() == Some(b"EDID") {
+            // A blob id of zero means the property is currently unset.
+            return Ok(if value == 0 { None } else { Some(value as u32) });
+        }
+    }
+
+    Ok(None)
+}
+
+fn blob(card: &File, blob_id: u32) -> io::Result<Vec<u8>> {
+    // Blobs are immutable, so the length cannot change between the calls.
+    let mut blob = GetBlob { blob_id, ..GetBlob::default() };
+    drm_ioctl(card, DRM_IOCTL_MODE_GETPROPBLOB, &mut blob)?;
+
+    let mut data = vec![0u8; blob.length as usize];
+    blob.data = data.as_mut_ptr() as u64;
+    drm_ioctl(card, DRM_IOCTL_MODE_GETPROPBLOB, &mut blob)?;
+
+    Ok(data)
+}
+
+// Like libdrm's `drmIoctl`, restarting when interrupted.
+fn drm_ioctl<T>(card: &File, request: c_ulong, arg: &mut T) -> io::Result<()> {
+    loop {
+        // Safety: `arg` is one of the `repr(C)` structures above, which
+        // matches the size encoded in `request`, and any pointers inside
+        // it refer to live buffers of at least the advertised length.
+        let ret = unsafe { ioctl(card.as_raw_fd(), request, arg as *mut T) };
+        if ret == 0 {
+            return Ok(());
+        }
+
+        let err = io::Error::last_os_error();
+        match err.kind() {
+            io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock => continue,
+            _ => return Err(err)
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Reading EDIDs over the DDC channel through an `i2c-dev` adapter.
+//!
+//! This is for displays whose EDID is not exposed by any kernel driver,
+//! the adapter is something like `/dev/i2c-3` and needs the `i2c-dev`
+//! module loaded.
+
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::raw::{c_int, c_ulong};
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+use crate::{EDID, ExtensionBlock, Reader, Result};
+
+extern "C" {
+    fn ioctl(fd: c_int, request: c_ulong, ...) -> c_int;
+}
+
+const I2C_RDWR: c_ulong = 0x0707;
+const I2C_M_RD: u16 = 0x0001;
+
+/// The E-DDC segment pointer, selecting a pair of blocks.
+const DDC_SEGMENT_ADDR: u16 = 0x30;
+/// The EDID itself, addressed by a one byte offset into the segment.
+const DDC_ADDR: u16 = 0x50;
+
+// Mirrors `struct i2c_msg` from the kernel's linux/i2c.h.
+#[repr(C)]
+struct I2cMsg {
+    addr: u16,
+    flags: u16,
+    len: u16,
+    buf: *mut u8
+}
+
+// Mirrors `struct i2c_rdwr_ioctl_data` from linux/i2c-dev.h.
+#[repr(C)]
+struct I2cRdwrData {
+    msgs: *mut I2cMsg,
+    nmsgs: u32
+}
+
+/// The blocks read from a display and the result of parsing them.
+#[derive(Debug, Clone)]
+pub struct DdcEdid {
+    /// The base block followed by every extension block.
+    pub blocks: Vec<[u8; 128]>,
+    pub edid: Result<EDID>
+}
+
+/// Reads EDID blocks from the display attached to an I2C adapter.
+pub struct DdcReader {
+    adapter: File,
+    /// The number of bytes fetched per transfer, from 1 to 128. Many
+    /// adapters cannot do a full block at once, 32 is usually safe.
+    pub chunk_size: usize,
+    /// How many more times a failed transfer or a block
+    /// with a bad checksum is attempted before giving up.
+    pub retries: u32
+}
+
+impl DdcReader {
+    /// Open an adapter such as `/dev/i2c-3`.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<DdcReader> {
+        let adapter = OpenOptions::new().read(true).write(true).open(path)?;
+        Ok(DdcReader { adapter, chunk_size: 128, retries: 3 })
+    }
+
+    /// Read the base block and all the extension blocks it announces,
+    /// or that an HDMI Forum override block in the first extension does.
+    pub fn read_blocks(&mut self) -> io::Result<Vec<[u8; 128]>> {
+        let base = self.read_block(0)?;
+        let mut blocks = vec![base];
+        let mut count = base[126] as usize;
+        while blocks.len() <= count {
+            blocks.push(self.read_block(blocks.len() as u8)?);
+
+            if blocks.len() == 2 {
+                count = ExtensionBlock(blocks[1]).eeodb_extension_count().map_or(count, |n| n as usize);
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    /// Read all the blocks and parse the base block.
+    pub fn read(&mut self) -> io::Result<DdcEdid> {
+        let blocks = self.read_blocks()?;
+        let edid = EDID::parse(&mut Reader::new(&mut &blocks[0][..]));
+        Ok(DdcEdid { blocks, edid })
+    }
+
+    /// Read a single 128-byte block, verifying its checksum.
+    pub fn read_block(&mut self, index: u8) -> io::Result<[u8; 128]> {
+        if self.chunk_size == 0 || self.chunk_size > 128 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "DDC chunk size must be from 1 to 128"));
+        }
+
+        let mut attempts = 0;
+        loop {
+            let result = self.try_read_block(index).and_then(|block| {
+                if block.iter().fold(0u8, |sum, &b| sum.wrapping_add(b)) == 0 {
+                    Ok(block)
+                } else {
+                    Err(io::Error::new(io::ErrorKind::InvalidData, format!("bad checksum in EDID block {}", index)))
+                }
+            });
+
+            match result {
+                Err(_) if attempts < self.retries => attempts += 1,
+                result => return result
+            }
+        }
+    }
+
+    fn try_read_block(&mut self, index: u8) -> io::Result<[u8; 128]> {
+        let mut block = [0u8; 128];
+
+        // Each segment holds two blocks.
+        let segment = index / 2;
+        let start = (index % 2) as usize * 128;
+
+        for offset in (0..128).step_by(self.chunk_size) {
+            let len = core::cmp::min(self.chunk_size, 128 - offset);
+            let mut segment_buf = [segment];
+            let mut word_offset = [(start + offset) as u8];
+
+            // The segment pointer resets at every STOP condition, so it has
+            // to be written in the same transaction as the offset and read.
+            // It is left out for the first segment as plain DDC displays
+            // do not acknowledge it.
+            let mut msgs = [
+                I2cMsg { addr: DDC_SEGMENT_ADDR, flags: 0, len: 1, buf: segment_buf.as_mut_ptr() },
+                I2cMsg { addr: DDC_ADDR, flags: 0, len: 1, buf: word_offset.as_mut_ptr() },
+                I2cMsg { addr: DDC_ADDR, flags: I2C_M_RD, len: len as u16, buf: block[offset..].as_mut_ptr() }
+            ];
+            let msgs = if segment == 0 { &mut msgs[1..] } else { &mut msgs[..] };
+
+            let mut data = I2cRdwrData { msgs: msgs.as_mut_ptr(), nmsgs: msgs.len() as u32 };
+
+            // Safety: every message points into a live buffer at least
+            // `len` bytes long, and `data` points at those messages.
+            let ret = unsafe { ioctl(self.adapter.as_raw_fd(), I2C_RDWR, &mut data as *mut I2cRdwrData) };
+            if ret < 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+
+        Ok(block)
+    }
+}
@@ -0,0 +1,178 @@
+//! Editing raw EDID data in place, for building overrides.
+//!
+//! Everything here changes only the bytes it has to and keeps the
+//! block checksum correct.
+
+use crate::{DetailedTiming, Error, Result, StereoType, SyncLine, SyncPolarity, SyncType};
+
+/// Which descriptor slots `insert_detailed_timing` may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotPolicy {
+    /// Only dummy (tag 0x10) and empty descriptors.
+    UnusedOnly,
+    /// Unused slots if there are any, otherwise the least important
+    /// descriptor. The monitor name, serial number and range limits
+    /// are never replaced.
+    ReplaceLeastImportant
+}
+
+/// Write a detailed timing into a descriptor slot of a base block,
+/// returning the offset of the slot.
+///
+/// The first slot holds the preferred timing and is left alone,
+/// as are the other detailed timings.
+pub fn insert_detailed_timing(block: &mut [u8; 128], dtd: &DetailedTiming, policy: SlotPolicy) -> Result<usize> {
+    let encoded = encode_detailed_timing(dtd)?;
+
+    let mut best: Option<(u8, usize)> = None;
+    for offset in (72..126).step_by(18) {
+        let descriptor = &block[offset..offset + 18];
+        // Pixel clocks are never zero, so this is a detailed timing.
+        if descriptor[..2] != [0, 0] {
+            continue;
+        }
+
+        let rank = match policy {
+            SlotPolicy::UnusedOnly => match importance(descriptor) {
+                0 => 0,
+                _ => continue
+            },
+            SlotPolicy::ReplaceLeastImportant => importance(descriptor)
+        };
+
+        if rank < u8::MAX && best.is_none_or(|(best, _)| rank < best) {
+            best = Some((rank, offset));
+        }
+    }
+
+    let (_, offset) = best.ok_or(Error::NoSpace)?;
+    block[offset..offset + 18].copy_from_slice(&encoded);
+    update_checksum(block);
+    Ok(offset)
+}
+
+/// Write a detailed timing into the first free slot after the
+/// detailed timings of a CTA-861 extension block, returning its offset.
+pub fn append_cta_detailed_timing(block: &mut [u8; 128], dtd: &DetailedTiming) -> Result<usize> {
+    if block[0] != 0x02 {
+        return Err(Error::WrongExtension { tag: block[0] });
+    }
+
+    let encoded = encode_detailed_timing(dtd)?;
+
+    // An offset of zero means there are no data blocks or timings at all.
+    if block[2] == 0 {
+        block[2] = 4;
+    }
+
+    let mut offset = block[2] as usize;
+    while offset + 18 <= 127 {
+        // Detailed timings are followed by zero padding.
+        if block[offset..offset + 2] == [0, 0] {
+            block[offset..offset + 18].copy_from_slice(&encoded);
+            update_checksum(block);
+            return Ok(offset);
+        }
+        offset += 18;
+    }
+
+    Err(Error::NoSpace)
+}
+
+/// Encode a detailed timing as the 18 bytes of a descriptor.
+pub fn encode_detailed_timing(dtd: &DetailedTiming) -> Result<[u8; 18]> {
+    dtd.check_fields()?;
+
+    let clock = dtd.pixel_clock / 10000;
+    if clock == 0 {
+        return Err(Error::InvalidTiming("Pixel clock too low for a detailed timing."));
+    }
+
+    if dtd.border.0 > 0xff || dtd.border.1 > 0xff {
+        return Err(Error::InvalidTiming("Border too large for a detailed timing."));
+    }
+
+    // Sizes are stored in millimetres.
+    let size = dtd.image_size.map_or((0, 0), |size| (
+        (size.width * 10.0 + 0.5) as u16,
+        (size.height * 10.0 + 0.5) as u16
+    ));
+    if size.0 > 0xfff || size.1 > 0xfff {
+        return Err(Error::InvalidTiming("Image size too large for a detailed timing."));
+    }
+
+    let (active, front, sync) = (dtd.active, dtd.front_porch, dtd.sync_length);
+    let blanking = (
+        front.0 + sync.0 + dtd.back_porch.0,
+        front.1 + sync.1 + dtd.back_porch.1
+    );
+
+    let stereo = match dtd.stereo {
+        StereoType::None => 0b000,
+        StereoType::SequentialRightSync => 0b010,
+        StereoType::SequentialLeftSync => 0b100,
+        StereoType::InterleavedLinesRightEven => 0b011,
+        StereoType::InterleavedLinesLeftEven => 0b101,
+        StereoType::Interleaved4Way => 0b110,
+        StereoType::SideBySide => 0b111
+    };
+
+    let positive = |polarity| match polarity {
+        SyncPolarity::Positive => 1,
+        SyncPolarity::Negative => 0
+    };
+    let sync_bits = match dtd.sync_type {
+        SyncType::Composite { serrated, line: SyncLine::RGB } => (serrated as u8) << 2 | 1 << 1,
+        SyncType::Composite { serrated, line: SyncLine::Green } => (serrated as u8) << 2,
+        SyncType::Composite { serrated, line: SyncLine::Digital(polarity) } => {
+            0b10 << 3 | (serrated as u8) << 2 | positive(polarity) << 1
+        },
+        SyncType::Seperate { horizontal, vertical } => 0b11 << 3 | positive(vertical) << 2 | positive(horizontal) << 1
+    };
+
+    let flags = (dtd.interlaced as u8) << 7 | (stereo & 0b110) << 4 | sync_bits | (stereo & 0b001);
+
+    Ok([
+        (clock & 0xff) as u8,
+        (clock >> 8) as u8,
+        (active.0 & 0xff) as u8,
+        (blanking.0 & 0xff) as u8,
+        ((active.0 >> 8) << 4 | (blanking.0 >> 8)) as u8,
+        (active.1 & 0xff) as u8,
+        (blanking.1 & 0xff) as u8,
+        ((active.1 >> 8) << 4 | (blanking.1 >> 8)) as u8,
+        (front.0 & 0xff) as u8,
+        (sync.0 & 0xff) as u8,
+        ((front.1 & 0x0f) << 4 | (sync.1 & 0x0f)) as u8,
+        ((front.0 >> 8) << 6 | (sync.0 >> 8) << 4 | (front.1 >> 4) << 2 | (sync.1 >> 4)) as u8,
+        (size.0 & 0xff) as u8,
+        (size.1 & 0xff) as u8,
+        ((size.0 >> 8) << 4 | (size.1 >> 8)) as u8,
+        dtd.border.0 as u8,
+        dtd.border.1 as u8,
+        flags
+    ])
+}
+
+// How much a display descriptor is worth keeping, from 0 for unused
+// slots up to `u8::MAX` for ones which must never be replaced.
+fn importance(descriptor: &[u8]) -> u8 {
+    match descriptor[3] {
+        0x10 => 0,
+        _ if descriptor.iter().all(|&b| b == 0) => 0,
+        0x00..=0x0f => 1,
+        0x11..=0xf6 => 2,
+        0xfe => 3,
+        0xfb => 4,
+        0xf9 => 5,
+        0xf7 => 6,
+        0xf8 => 7,
+        0xfa => 8,
+        _ => u8::MAX
+    }
+}
+
+fn update_checksum(block: &mut [u8; 128]) {
+    let sum = block[..127].iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+    block[127] = 0u8.wrapping_sub(sum);
+}
@@ -0,0 +1,103 @@
+//! Reading single fields straight out of a base block, without parsing
+//! the rest of it.
+
+use crate::{
+    DetailedTiming, DisplayParameters, EDID, Error, ManufacturerID, ProductInformation, Reader, Result,
+    Version, descriptor_text, ensure, read_header
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::borrow::Cow;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+/// A borrowed base block which decodes fields as they are asked for,
+/// allocating nothing but non-ASCII strings. The decoding is shared
+/// with `EDID::parse`.
+#[derive(Debug, Clone, Copy)]
+pub struct EdidRef<'a>(&'a [u8; 128]);
+
+impl<'a> EdidRef<'a> {
+    /// Check the header and the checksum of a base block.
+    pub fn new(bytes: &'a [u8; 128]) -> Result<EdidRef<'a>> {
+        read_header(&mut Reader::new(&mut &bytes[..]))?;
+        ensure(bytes.iter().fold(0u8, |sum, &b| sum.wrapping_add(b)) == 0, Error::InvalidChecksum { offset: 0 })?;
+        Ok(EdidRef(bytes))
+    }
+
+    /// The raw base block.
+    pub fn bytes(&self) -> &'a [u8; 128] {
+        self.0
+    }
+
+    // A reader starting at `offset` in the block, so errors give
+    // offsets in the block.
+    fn at<T>(&self, offset: usize, parse: impl FnOnce(&mut Reader) -> Result<T>) -> Result<T> {
+        let mut data = &self.0[..];
+        let mut r = Reader::new(&mut data);
+        r.skip(offset)?;
+        parse(&mut r)
+    }
+
+    pub fn product(&self) -> Result<ProductInformation> {
+        let mut product = self.at(8, ProductInformation::parse)?;
+        product.manufacture_date = product.manufacture_date.for_version(&self.version()?);
+        Ok(product)
+    }
+
+    pub fn manufacturer_id(&self) -> Result<ManufacturerID> {
+        self.at(8, ManufacturerID::parse)
+    }
+
+    pub fn version(&self) -> Result<Version> {
+        self.at(18, Version::parse)
+    }
+
+    pub fn display(&self) -> Result<DisplayParameters> {
+        let version = self.version()?;
+        self.at(20, |r| DisplayParameters::parse(r, &version))
+    }
+
+    /// The detailed timings in the four descriptor slots.
+    pub fn detailed_timings(&self) -> impl Iterator<Item = DetailedTiming> + 'a {
+        let block = self.0;
+        (54..126).step_by(18).filter_map(move |offset| {
+            let mut data = &block[offset..offset + 18];
+            DetailedTiming::parse(&mut Reader::new(&mut data)).ok().flatten()
+        })
+    }
+
+    /// The preferred timing, which is the first detailed timing.
+    pub fn preferred_timing(&self) -> Option<DetailedTiming> {
+        self.at(54, DetailedTiming::parse).ok().flatten()
+    }
+
+    /// The monitor name, which is borrowed from the block unless
+    /// it has characters from code page 437 beyond ASCII.
+    pub fn monitor_name(&self) -> Option<Cow<'a, str>> {
+        self.descriptor_string(0xfc)
+    }
+
+    /// The serial number string, see `monitor_name`.
+    pub fn serial_string(&self) -> Option<Cow<'a, str>> {
+        self.descriptor_string(0xff)
+    }
+
+    // The text of the first string descriptor with `tag`.
+    fn descriptor_string(&self, tag: u8) -> Option<Cow<'a, str>> {
+        let block = self.0;
+        let offset = (54..126).step_by(18).find(|&offset| block[offset..offset + 2] == [0, 0] && block[offset + 3] == tag)?;
+        Some(descriptor_text(&block[offset + 5..offset + 18]))
+    }
+
+    /// The number of extension blocks which follow.
+    pub fn extensions(&self) -> u8 {
+        self.0[126]
+    }
+
+    /// Parse the whole block.
+    pub fn to_edid(&self) -> Result<EDID> {
+        let mut data = &self.0[..];
+        EDID::parse(&mut Reader::new(&mut data))
+    }
+}
@@ -0,0 +1,108 @@
+//! Parsing an EDID piece by piece through callbacks, for when building
+//! the whole `EDID` with its vectors and strings is too much.
+
+#[cfg(feature = "color")]
+use crate::ColorCharacteristics;
+use crate::{
+    DetailedTiming, DisplayParameters, ESTABLISHED_TIMINGS, Error, EstablishedTiming, ProductInformation,
+    Reader, Result, StandardTiming, Version, read_header
+};
+
+/// Receives the parts of an EDID from `parse_with_visitor` as they are
+/// decoded. Every method does nothing by default.
+#[allow(unused_variables)]
+pub trait EdidVisitor {
+    fn product(&mut self, product: &ProductInformation) {}
+    fn version(&mut self, version: &Version) {}
+    fn display(&mut self, display: &DisplayParameters) {}
+    #[cfg(feature = "color")]
+    fn color(&mut self, color: &ColorCharacteristics) {}
+    fn established_timing(&mut self, timing: EstablishedTiming) {}
+    /// The manufacturer's reserved timing bits, as in `Timings`.
+    fn manufacturer_timings(&mut self, bits: u8) {}
+    /// A standard timing from the base block, `index` counting
+    /// the used slots from 0.
+    fn standard_timing(&mut self, index: usize, timing: &StandardTiming) {}
+    /// A detailed timing, the first of which is the preferred timing.
+    fn detailed_timing(&mut self, index: usize, timing: &DetailedTiming) {}
+    /// A display descriptor in one of the four 18-byte slots, which is
+    /// left for the visitor to decode.
+    fn descriptor(&mut self, slot: usize, tag: u8, data: &[u8; 13]) {}
+    /// An extension block following the base block.
+    fn extension(&mut self, tag: u8, block: &[u8; 128]) {}
+}
+
+/// Parse an EDID, handing each part to `visitor` instead of collecting
+/// them. Unlike `EDID::parse` this goes on to read the extension blocks
+/// the base block says follow it.
+pub fn parse_with_visitor<V: EdidVisitor>(r: &mut Reader, visitor: &mut V) -> Result<()> {
+    read_header(r)?;
+
+    let mut product = ProductInformation::parse(r)?;
+    let version = Version::parse(r)?;
+    product.manufacture_date = product.manufacture_date.for_version(&version);
+    visitor.product(&product);
+    visitor.version(&version);
+    let display = DisplayParameters::parse(r, &version)?;
+    visitor.display(&display);
+    #[cfg(feature = "color")]
+    visitor.color(&ColorCharacteristics::parse(r)?);
+    #[cfg(not(feature = "color"))]
+    r.skip(10)?;
+
+    let (bits, manufacturer_timings) = EstablishedTiming::read_bits(r)?;
+    for (i, &timing) in ESTABLISHED_TIMINGS.iter().enumerate() {
+        if bits & (1 << i) > 0 {
+            visitor.established_timing(timing);
+        }
+    }
+    visitor.manufacturer_timings(manufacturer_timings);
+
+    let mut index = 0;
+    for _ in 0..8 {
+        if let Some(timing) = StandardTiming::parse(r)? {
+            visitor.standard_timing(index, &timing);
+            index += 1;
+        }
+    }
+
+    let mut index = 0;
+    for slot in 0..4 {
+        let offset = r.offset;
+        let mut raw = [0u8; 18];
+        for byte in raw.iter_mut() {
+            *byte = r.read_u8()?;
+        }
+
+        let timing = DetailedTiming::parse(&mut Reader::new(&mut &raw[..])).map_err(|err| match err {
+            Error::BadTiming { .. } => Error::BadTiming { offset },
+            err => err
+        })?;
+        match timing {
+            Some(timing) => {
+                visitor.detailed_timing(index, &timing);
+                index += 1;
+            },
+            None if slot == 0 && display.dpms.preferred_timing_mode => {
+                return Err(Error::MissingPreferredTiming { offset });
+            },
+            None => {
+                let mut data = [0u8; 13];
+                data.copy_from_slice(&raw[5..]);
+                visitor.descriptor(slot, raw[3], &data);
+            }
+        }
+    }
+
+    let extensions = r.read_u8()?;
+    r.read_u8()?;
+    for _ in 0..extensions {
+        let mut block = [0u8; 128];
+        for byte in block.iter_mut() {
+            *byte = r.read_u8()?;
+        }
+        visitor.extension(block[0], &block);
+    }
+
+    Ok(())
+}
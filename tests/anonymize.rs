@@ -0,0 +1,92 @@
+mod common;
+
+use common::{SAMPLE, cta_extension, fix_checksum};
+use edid_rs::{EDID, ManufactureDate, RedactedField, Reader, Redaction, anonymize};
+
+// A serial number descriptor for "SN12345".
+const SERIAL: [u8; 18] = [0, 0, 0, 0xff, 0, b'S', b'N', b'1', b'2', b'3', b'4', b'5', 0x0a, 0x20, 0x20, 0x20, 0x20, 0x20];
+
+// The sample with a numeric serial number, a serial number descriptor in
+// place of the first dummy one, and a CTA-861 extension holding another.
+fn identifiable() -> Vec<u8> {
+    let mut base = SAMPLE;
+    base[12..16].copy_from_slice(&[0x78, 0x56, 0x34, 0x12]);
+    base[90..108].copy_from_slice(&SERIAL);
+    base[126] = 1;
+    fix_checksum(&mut base);
+
+    let mut extension = cta_extension(0, &[]);
+    extension[4..22].copy_from_slice(&SERIAL);
+    fix_checksum(&mut extension);
+
+    let mut bytes = base.to_vec();
+    bytes.extend_from_slice(&extension);
+    bytes
+}
+
+fn sums_to_zero(block: &[u8]) -> bool {
+    block.iter().fold(0u8, |sum, &b| sum.wrapping_add(b)) == 0
+}
+
+#[test]
+fn raw() {
+    let original = identifiable();
+    let mut bytes = original.clone();
+    let redactions = anonymize(&mut bytes, true);
+
+    assert_eq!(redactions, vec![
+        Redaction { field: RedactedField::SerialNumber, range: 12..16 },
+        Redaction { field: RedactedField::ManufactureDate, range: 16..18 },
+        Redaction { field: RedactedField::SerialString, range: 95..102 },
+        Redaction { field: RedactedField::SerialString, range: 137..144 }
+    ]);
+    assert_eq!(&bytes[95..102], b"0000000");
+    assert_eq!(&bytes[137..144], b"0000000");
+
+    // Only the redacted fields and the checksums change.
+    for (offset, (old, new)) in original.iter().zip(&bytes).enumerate() {
+        let redacted = redactions.iter().any(|r| r.range.contains(&offset));
+        if !redacted && offset != 127 && offset != 255 {
+            assert_eq!(old, new, "byte {}", offset);
+        }
+    }
+    assert!(bytes.chunks(128).all(sums_to_zero));
+
+    let edid = EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap();
+    assert!(edid.checksum_ok);
+    assert_eq!(edid.product.serial_number, 0);
+}
+
+#[test]
+fn raw_keep_date() {
+    let mut bytes = identifiable();
+    let redactions = anonymize(&mut bytes, false);
+    assert!(redactions.iter().all(|r| r.field != RedactedField::ManufactureDate));
+    assert_eq!(bytes[16..18], SAMPLE[16..18]);
+}
+
+#[test]
+fn raw_nothing_to_do() {
+    let mut bytes = SAMPLE;
+    assert_eq!(anonymize(&mut bytes, false), vec![]);
+    assert_eq!(bytes[..], SAMPLE[..]);
+}
+
+#[test]
+fn parsed() {
+    let bytes = identifiable();
+    let mut edid = EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap();
+    assert_eq!(edid.product.serial_number, 0x12345678);
+
+    let mut redacted = edid.anonymize(true);
+    redacted.sort_by_key(|field| *field as u8);
+    assert_eq!(redacted.len(), if cfg!(feature = "descriptors") { 3 } else { 2 });
+    assert_eq!(edid.product.serial_number, 0);
+    assert_eq!(edid.product.manufacture_date, ManufactureDate::YearOnly(1990));
+
+    #[cfg(feature = "descriptors")]
+    assert!(edid.descriptors.0.iter().any(|d| matches!(d, edid_rs::MonitorDescriptor::SerialNumber(s) if s == "0000000")));
+
+    // The numeric serial number is not reported once it is zero.
+    assert!(!edid.anonymize(false).contains(&RedactedField::SerialNumber));
+}
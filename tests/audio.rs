@@ -0,0 +1,126 @@
+#![cfg(feature = "cta")]
+
+mod common;
+
+use common::{SAMPLE, cta_extension};
+use edid_rs::{AudioFormat, EDID, EldPort, ExtensionBlock, Reader, SpeakerAllocation, SpeakerPosition};
+
+fn sample() -> EDID {
+    EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap()
+}
+
+// An AVR with 8 channel L-PCM up to 192kHz and 24 bits, 6 channel
+// Dolby TrueHD at 48 and 96kHz, and AC-4 given by an extension code.
+fn avr() -> Vec<ExtensionBlock> {
+    let sads: &[u8] = &[
+        0x0f, 0x7f, 0x07,
+        0x65, 0x14, 0x01,
+        0x79, 0x07, 0x60
+    ];
+    vec![ExtensionBlock(cta_extension(0x40, &[(1, sads)]))]
+}
+
+// A TV which only sets the basic audio flag.
+fn basic_tv() -> Vec<ExtensionBlock> {
+    vec![ExtensionBlock(cta_extension(0x40, &[]))]
+}
+
+#[test]
+fn avr_formats() {
+    let edid = sample();
+    let avr = avr();
+
+    assert!(edid.supports_audio_format(&avr, AudioFormat::Lpcm, 8, 192000, Some(24)));
+    assert!(edid.supports_audio_format(&avr, AudioFormat::Lpcm, 2, 44100, None));
+    assert!(!edid.supports_audio_format(&avr, AudioFormat::Lpcm, 8, 192000, Some(32)));
+    assert!(edid.supports_audio_format(&avr, AudioFormat::Mat, 6, 96000, Some(24)));
+    assert!(!edid.supports_audio_format(&avr, AudioFormat::Mat, 8, 96000, None));
+    assert!(!edid.supports_audio_format(&avr, AudioFormat::Mat, 6, 44100, None));
+    assert!(edid.supports_audio_format(&avr, AudioFormat::Extended(12), 2, 48000, None));
+    assert!(!edid.supports_audio_format(&avr, AudioFormat::Dts, 2, 48000, None));
+
+    assert_eq!(edid.max_lpcm_channels(&avr), Some(8));
+    assert_eq!(edid.lpcm_sample_rates(&avr), [32000, 44100, 48000, 88200, 96000, 176400, 192000]);
+
+    let sads = avr[0].short_audio_descriptors();
+    assert_eq!(sads.len(), 3);
+    assert_eq!(sads[0].lpcm_bit_depths(), [16, 20, 24]);
+    assert_eq!(sads[1].sample_rates_hz(), [48000, 96000]);
+}
+
+#[test]
+fn basic_audio_only() {
+    let edid = sample();
+    let tv = basic_tv();
+
+    assert!(tv[0].basic_audio());
+    assert!(tv[0].short_audio_descriptors().is_empty());
+    assert!(edid.supports_audio_format(&tv, AudioFormat::Lpcm, 2, 48000, None));
+    assert!(edid.supports_audio_format(&tv, AudioFormat::Lpcm, 2, 32000, Some(16)));
+    assert!(!edid.supports_audio_format(&tv, AudioFormat::Lpcm, 2, 48000, Some(24)));
+    assert!(!edid.supports_audio_format(&tv, AudioFormat::Lpcm, 6, 48000, None));
+    assert!(!edid.supports_audio_format(&tv, AudioFormat::Lpcm, 2, 96000, None));
+    assert!(!edid.supports_audio_format(&tv, AudioFormat::Ac3, 2, 48000, None));
+
+    assert_eq!(edid.max_lpcm_channels(&tv), Some(2));
+    assert_eq!(edid.lpcm_sample_rates(&tv), [32000, 44100, 48000]);
+}
+
+#[test]
+fn no_audio() {
+    let edid = sample();
+    let silent = vec![ExtensionBlock(cta_extension(0x00, &[]))];
+
+    assert!(!edid.supports_audio_format(&silent, AudioFormat::Lpcm, 2, 48000, None));
+    assert!(!edid.supports_audio_format(&[], AudioFormat::Lpcm, 2, 48000, None));
+    assert_eq!(edid.max_lpcm_channels(&silent), None);
+    assert!(edid.lpcm_sample_rates(&silent).is_empty());
+}
+
+#[test]
+fn soundbar_layout() {
+    // Front left and right, LFE, front center and top front left and right.
+    let soundbar = ExtensionBlock(cta_extension(0x40, &[(4, &[0x07, 0x01, 0x00])]));
+    let allocation = soundbar.speaker_allocation().unwrap();
+
+    assert_eq!(allocation.layout().to_string(), "3.1.2");
+    assert_eq!(allocation.positions(), [
+        SpeakerPosition::FrontLeft,
+        SpeakerPosition::FrontRight,
+        SpeakerPosition::LowFrequencyEffects1,
+        SpeakerPosition::FrontCenter,
+        SpeakerPosition::TopFrontLeft,
+        SpeakerPosition::TopFrontRight
+    ]);
+    assert_eq!(allocation.positions()[4].name(), "Top Front Left");
+
+    // The ELD carries the first byte.
+    let eld = sample().to_eld(&[soundbar], &EldPort::default()).unwrap();
+    assert_eq!(eld[6], 0x07);
+}
+
+#[test]
+fn avr_layout() {
+    // 7.1 with back and side surrounds, and top front and top back pairs.
+    let allocation = SpeakerAllocation([0x0f, 0x09, 0x01]);
+    let layout = allocation.layout();
+
+    assert_eq!((layout.main, layout.lfe, layout.height), (7, 1, 4));
+    assert_eq!(layout.to_string(), "7.1.4");
+    assert!(allocation.positions().contains(&SpeakerPosition::TopBackRight));
+}
+
+#[test]
+fn ambiguous_layout() {
+    // A center speaker without front left and right.
+    assert_eq!(SpeakerAllocation([0x04, 0x00, 0x00]).layout().to_string(), "1.0");
+    // Two subwoofers.
+    assert_eq!(SpeakerAllocation([0x03, 0x10, 0x00]).layout().to_string(), "2.2");
+    assert_eq!(SpeakerAllocation([0x00, 0x00, 0x00]).layout().to_string(), "0.0");
+    assert!(SpeakerAllocation([0x00, 0x00, 0x00]).positions().is_empty());
+
+    // A block with only the first byte.
+    let short = ExtensionBlock(cta_extension(0, &[(4, &[0x0b])]));
+    assert_eq!(short.speaker_allocation(), Some(SpeakerAllocation([0x0b, 0x00, 0x00])));
+    assert_eq!(ExtensionBlock(cta_extension(0, &[])).speaker_allocation(), None);
+}
@@ -0,0 +1,155 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use common::{SAMPLE, fix_checksum, to_hex};
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_edid-rs"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+// The sample with one CTA extension block after it.
+fn with_extension() -> Vec<u8> {
+    let mut base = SAMPLE;
+    base[126] = 1;
+    fix_checksum(&mut base);
+
+    let mut extension = [0u8; 128];
+    extension[..4].copy_from_slice(&[0x02, 0x03, 0x04, 0x00]);
+    fix_checksum(&mut extension);
+
+    [&base[..], &extension[..]].concat()
+}
+
+#[test]
+fn reads_stdin() {
+    let output = run(&[], &SAMPLE);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("Color LCD"));
+
+    let output = run(&["-"], &SAMPLE);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn reads_path() {
+    let path = std::env::temp_dir().join(format!("edid-rs-cli-{}.bin", std::process::id()));
+    std::fs::write(&path, &SAMPLE[..]).unwrap();
+    let output = run(&[path.to_str().unwrap()], b"");
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("Color LCD"));
+}
+
+#[test]
+fn reads_hex() {
+    let output = run(&["--hex", &to_hex(&SAMPLE)], b"");
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("Color LCD"));
+
+    let output = run(&["--hex", "00 ff zz"], b"");
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn bad_usage() {
+    assert_eq!(run(&["--frobnicate"], b"").status.code(), Some(1));
+    assert_eq!(run(&["--checksum", "sometimes"], b"").status.code(), Some(1));
+    assert_eq!(run(&["--hex"], b"").status.code(), Some(1));
+    assert_eq!(run(&["a.bin", "b.bin"], b"").status.code(), Some(1));
+    assert_eq!(run(&["/nonexistent/edid"], b"").status.code(), Some(1));
+    assert_eq!(run(&["--help"], b"").status.code(), Some(0));
+}
+
+#[test]
+fn parse_failure() {
+    let output = run(&[], &SAMPLE[..100]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stdout(&output).is_empty());
+}
+
+#[test]
+fn checksum_policy() {
+    let mut bytes = SAMPLE;
+    bytes[127] = 0;
+
+    let output = run(&[], &bytes);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(stderr(&output).contains("bad checksum in block 0"));
+    assert!(stdout(&output).contains("Color LCD"));
+
+    assert_eq!(run(&["--checksum", "warn"], &bytes).status.code(), Some(3));
+    assert_eq!(run(&["--checksum", "strict"], &bytes).status.code(), Some(2));
+    assert_eq!(run(&["--checksum", "ignore"], &bytes).status.code(), Some(0));
+    assert_eq!(run(&["--checksum", "strict"], &SAMPLE).status.code(), Some(0));
+
+    let mut bytes = with_extension();
+    bytes[255] ^= 1;
+    let output = run(&["--checksum", "warn"], &bytes);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(stderr(&output).contains("bad checksum in block 1"));
+}
+
+#[test]
+fn lenient() {
+    // A week past the end of the year fails unless lenient.
+    let mut bytes = SAMPLE;
+    bytes[16] = 55;
+    fix_checksum(&mut bytes);
+
+    assert_eq!(run(&[], &bytes).status.code(), Some(2));
+
+    let output = run(&["--lenient"], &bytes);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(stderr(&output).contains("warning: Invalid manufacture week at byte 16."));
+    assert!(stdout(&output).contains("Color LCD"));
+
+    // Text after the newline which ends the monitor name is only
+    // reported when lenient.
+    let mut bytes = SAMPLE;
+    bytes[88] = b'x';
+    fix_checksum(&mut bytes);
+
+    assert_eq!(run(&[], &bytes).status.code(), Some(0));
+    assert_eq!(run(&["--lenient"], &bytes).status.code(), Some(3));
+
+    assert_eq!(run(&["--lenient"], &SAMPLE).status.code(), Some(0));
+}
+
+#[test]
+fn extensions() {
+    let bytes = with_extension();
+
+    let output = run(&["--extensions"], &bytes);
+    assert_eq!(output.status.code(), Some(0));
+    let out = stdout(&output);
+    assert!(out.contains("Extension block 1, tag 0x02:"));
+    assert!(out.contains("  02 03 04 00 00"));
+
+    let output = run(&[], &bytes);
+    assert!(!stdout(&output).contains("Extension block"));
+
+    // The extension block is announced but missing.
+    let output = run(&["--extensions"], &bytes[..128]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(stderr(&output).contains("1 extension blocks announced, 0 present"));
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "color")]
+
+mod common;
+
+use common::{SAMPLE, fix_checksum};
+use edid_rs::{ColorCharacteristics, EDID, Reader};
+
+// The sample with every chromaticity byte set to `value`.
+fn filled(value: u8) -> EDID {
+    let mut bytes = SAMPLE;
+    for b in &mut bytes[25..35] {
+        *b = value;
+    }
+    fix_checksum(&mut bytes);
+    EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap()
+}
+
+#[test]
+fn valid() {
+    let edid = EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap();
+    assert!(edid.color.is_valid());
+    assert!(edid.chromaticity().is_some());
+}
+
+#[test]
+fn left_blank() {
+    for &value in &[0x00, 0xff] {
+        let edid = filled(value);
+        assert!(!edid.color.is_valid(), "{:#x}", value);
+        assert!(edid.chromaticity().is_none(), "{:#x}", value);
+    }
+
+    // Blank except for the white point is still filled in.
+    let mut bytes = SAMPLE;
+    for b in &mut bytes[25..33] {
+        *b = 0;
+    }
+    fix_checksum(&mut bytes);
+    assert!(EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap().color.is_valid());
+}
+
+fn srgb() -> ColorCharacteristics {
+    ColorCharacteristics {
+        red: (0.64, 0.33),
+        green: (0.30, 0.60),
+        blue: (0.15, 0.06),
+        white: (0.3127, 0.3290),
+        white_points: Vec::new()
+    }
+}
+
+#[test]
+fn plausible() {
+    assert!(srgb().is_plausible());
+    assert!(EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap().color.is_plausible());
+    // Warm white, as illuminant A.
+    assert!(ColorCharacteristics { white: (0.4476, 0.4074), ..srgb() }.is_plausible());
+    // Cool white, near 10000K.
+    assert!(ColorCharacteristics { white: (0.2807, 0.2883), ..srgb() }.is_plausible());
+}
+
+#[test]
+fn implausible() {
+    // Outside the chromaticity diagram.
+    assert!(!ColorCharacteristics { red: (0.9, 0.5), ..srgb() }.is_plausible());
+    assert!(!ColorCharacteristics { blue: (0.15, 0.0), ..srgb() }.is_plausible());
+    // A green white point, far from the Planckian locus.
+    assert!(!ColorCharacteristics { white: (0.2, 0.6), ..srgb() }.is_plausible());
+    // Past the ends of the fit.
+    assert!(!ColorCharacteristics { white: (0.6, 0.38), ..srgb() }.is_plausible());
+    // Blank coordinates.
+    assert!(!filled(0x00).color.is_plausible());
+    assert!(!filled(0xff).color.is_plausible());
+}
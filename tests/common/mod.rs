@@ -0,0 +1,67 @@
+// Shared by the integration tests, which each use only some of it.
+#![allow(dead_code)]
+
+use edid_rs::DetailedTiming;
+
+// Sample EDID data from a Macbook Pro, as in `examples/sample.rs`.
+pub const SAMPLE: [u8; 128] = [
+      0, 255, 255, 255, 255, 255, 255,   0,
+      6,  16,  34, 160,   0,   0,   0,   0,
+      4,  23,   1,   4, 165,  33,  21, 120,
+      2, 111, 177, 167,  85,  76, 158,  37,
+     12,  80,  84,   0,   0,   0,   1,   1,
+      1,   1,   1,   1,   1,   1,   1,   1,
+      1,   1,   1,   1,   1,   1, 239, 131,
+     64, 160, 176,   8,  52, 112,  48,  32,
+     54,   0,  75, 207,  16,   0,   0,  26,
+      0,   0,   0, 252,   0,  67, 111, 108,
+    111, 114,  32,  76,  67,  68,  10,  32,
+     32,  32,   0,   0,   0,  16,   0,   0,
+      0,   0,   0,   0,   0,   0,   0,   0,
+      0,   0,   0,   0,   0,   0,   0,  16,
+      0,   0,   0,   0,   0,   0,   0,   0,
+      0,   0,   0,   0,   0,   0,   0, 222
+];
+
+// Set the last byte of `block` so it sums to zero.
+pub fn fix_checksum(block: &mut [u8]) {
+    let sum = block[..127].iter().fold(0u8, |sum, b| sum.wrapping_add(*b));
+    block[127] = sum.wrapping_neg();
+}
+
+// Format `bytes` as `xxd -p` would.
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.chunks(30)
+        .map(|line| line.iter().map(|b| format!("{:02x}", b)).collect::<String>() + "\n")
+        .collect()
+}
+
+// A CTA-861 extension block with byte 3 set to `flags`
+// and the given data blocks, as tags and payloads.
+pub fn cta_extension(flags: u8, data_blocks: &[(u8, &[u8])]) -> [u8; 128] {
+    let mut block = [0u8; 128];
+    block[..4].copy_from_slice(&[0x02, 0x03, 0x04, flags]);
+
+    let mut i = 4;
+    for &(tag, payload) in data_blocks {
+        block[i] = tag << 5 | payload.len() as u8;
+        block[i + 1..i + 1 + payload.len()].copy_from_slice(payload);
+        i += 1 + payload.len();
+    }
+    block[2] = i as u8;
+
+    fix_checksum(&mut block);
+    block
+}
+
+// The clock and the horizontal and vertical edges, as in a modeline.
+pub fn modeline(timing: &DetailedTiming) -> (u32, [u16; 4], [u16; 4]) {
+    let edges = |active: u16, front: u16, sync: u16, back: u16| {
+        [active, active + front, active + front + sync, active + front + sync + back]
+    };
+    (
+        timing.pixel_clock,
+        edges(timing.active.0, timing.front_porch.0, timing.sync_length.0, timing.back_porch.0),
+        edges(timing.active.1, timing.front_porch.1, timing.sync_length.1, timing.back_porch.1)
+    )
+}
@@ -0,0 +1,56 @@
+// These run in each build configuration of the crate:
+//
+//     cargo test                        # std
+//     cargo test --no-default-features  # alloc only, the crate is no_std
+//     cargo test --all-features         # std with the deprecated no_std
+//
+// A configuration without alloc is not supported yet.
+
+mod common;
+
+use common::SAMPLE;
+use edid_rs::{EDID, Reader};
+
+// Byte slices are a source in every configuration.
+#[test]
+fn parse_slice() {
+    let edid = EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap();
+    assert_eq!(edid.product.product_code, 40994);
+    assert!(edid.checksum_ok);
+
+    static BYTES: [u8; 128] = SAMPLE;
+    assert!(edid_rs::parse(&mut &BYTES[..]).is_ok());
+}
+
+#[test]
+fn parse_error() {
+    let err = EDID::parse(&mut Reader::new(&mut &SAMPLE[..64])).unwrap_err();
+    assert_eq!(err.to_string(), "Unexpectedly out of data at byte 64, 64 more bytes needed.");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn parse_io() {
+    use std::io::{BufReader, Cursor};
+
+    assert!(edid_rs::parse(&mut Cursor::new(SAMPLE)).is_ok());
+    assert!(edid_rs::parse(&mut BufReader::new(&SAMPLE[..])).is_ok());
+    assert!(edid_rs::parse(&mut edid_rs::IoReader(std::io::repeat(0))).is_err());
+}
+
+// The old no_std feature used to remove the std impls, which broke any
+// std user in the same build. It is enabled by --all-features.
+#[cfg(all(feature = "std", feature = "no_std"))]
+#[test]
+fn deprecated_no_std_keeps_std() {
+    assert!(edid_rs::parse(&mut std::io::Cursor::new(SAMPLE)).is_ok());
+}
+
+#[cfg(not(feature = "std"))]
+#[test]
+fn alloc_only() {
+    // The base block still parses without the optional decoding.
+    let edid = EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap();
+    assert_eq!(edid.timings.detailed_timings.len(), 1);
+    assert_eq!(edid.extensions, 0);
+}
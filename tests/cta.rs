@@ -0,0 +1,139 @@
+#![cfg(feature = "cta")]
+
+mod common;
+
+use common::{SAMPLE, cta_extension};
+use edid_rs::{CtaRule, EDID, ExtensionBlock, Reader, Severity, hdmi_vic_timing};
+
+fn sample() -> EDID {
+    EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap()
+}
+
+const SADS: &[u8] = &[0x09, 0x07, 0x07];
+const SVDS: &[u8] = &[0x90, 0x04, 0x05];
+const HDMI_VSDB: &[u8] = &[0x03, 0x0c, 0x00, 0x10, 0x00];
+const HF_VSDB: &[u8] = &[0xd8, 0x5d, 0xc4, 0x01, 0x78, 0x00];
+
+fn rules(edid: &EDID, flags: u8, data_blocks: &[(u8, &[u8])]) -> Vec<CtaRule> {
+    let extensions = [ExtensionBlock(cta_extension(flags, data_blocks))];
+    edid.check_cta(&extensions).into_iter().map(|issue| issue.rule).collect()
+}
+
+#[test]
+fn consistent() {
+    let edid = sample();
+    assert!(rules(&edid, 0x41, &[(2, SVDS), (1, SADS), (3, HDMI_VSDB), (3, HF_VSDB)]).is_empty());
+    assert!(edid.check_cta(&[]).is_empty());
+}
+
+#[test]
+fn audio() {
+    let edid = sample();
+    assert_eq!(rules(&edid, 0x40, &[(2, SVDS)]), [CtaRule::BasicAudioWithoutSads]);
+    assert_eq!(rules(&edid, 0x00, &[(2, SVDS), (1, SADS)]), [CtaRule::SadsWithoutBasicAudio]);
+    assert_eq!(CtaRule::BasicAudioWithoutSads.severity(), Severity::Warning);
+    assert_eq!(CtaRule::SadsWithoutBasicAudio.severity(), Severity::Error);
+}
+
+#[test]
+fn native_dtds() {
+    // The sample has one detailed timing in its base block.
+    let edid = sample();
+    assert!(rules(&edid, 0x01, &[(2, SVDS)]).is_empty());
+    assert_eq!(rules(&edid, 0x02, &[(2, SVDS)]), [CtaRule::NativeDtdCount]);
+}
+
+#[test]
+fn ycbcr() {
+    // The sample is a 1.4 EDID which only takes RGB.
+    let edid = sample();
+    assert_eq!(rules(&edid, 0x20, &[(2, SVDS)]), [CtaRule::YcbcrOnRgbSink]);
+    assert_eq!(rules(&edid, 0x10, &[(2, SVDS)]), [CtaRule::YcbcrOnRgbSink]);
+
+    let mut bytes = SAMPLE;
+    bytes[24] |= 0b0001_1000;
+    common::fix_checksum(&mut bytes);
+    let ycbcr = EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap();
+    assert!(rules(&ycbcr, 0x30, &[(2, SVDS)]).is_empty());
+}
+
+#[test]
+fn hdmi_vsdb() {
+    let edid = sample();
+    assert_eq!(rules(&edid, 0x00, &[(2, SVDS), (3, HF_VSDB)]), [CtaRule::MissingHdmiVsdb]);
+    assert_eq!(rules(&edid, 0x00, &[(2, SVDS), (7, &[0x79, 0x01, 0x00, 0x00])]), [CtaRule::MissingHdmiVsdb]);
+}
+
+#[test]
+fn vic_zero() {
+    let edid = sample();
+    assert_eq!(rules(&edid, 0x00, &[(2, &[0x10, 0x00])]), [CtaRule::SvdVicZero]);
+    assert_eq!(rules(&edid, 0x00, &[(2, &[0x80])]), [CtaRule::SvdVicZero]);
+}
+
+#[test]
+fn y420cmdb() {
+    let edid = sample();
+    assert!(rules(&edid, 0x00, &[(2, SVDS), (7, &[0x0f, 0b0000_0101])]).is_empty());
+    assert!(rules(&edid, 0x00, &[(2, SVDS), (7, &[0x0f])]).is_empty());
+    assert_eq!(rules(&edid, 0x00, &[(2, SVDS), (7, &[0x0f, 0b0000_1000])]), [CtaRule::Y420CmdbPastSvds]);
+    assert_eq!(rules(&edid, 0x00, &[(2, SVDS), (7, &[0x0f, 0x00, 0x01])]), [CtaRule::Y420CmdbPastSvds]);
+}
+
+#[test]
+fn block_index() {
+    let edid = sample();
+    let extensions = [
+        ExtensionBlock(cta_extension(0x00, &[(2, SVDS)])),
+        ExtensionBlock(cta_extension(0x00, &[(2, &[0x00])]))
+    ];
+    let issues = edid.check_cta(&extensions);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].block, 2);
+    assert_eq!(issues[0].rule.id(), "cta-svd-vic-zero");
+}
+
+// An HDMI VSDB with the latency fields flagged in `flags`, then the video
+// section with the HDMI_VICs.
+fn hdmi_video(flags: u8, vics: &[u8]) -> ExtensionBlock {
+    let mut vsdb = vec![0x03, 0x0c, 0x00, 0x10, 0x00, 0x00, 0x00, flags | 0x20];
+    let latencies = (flags >> 7) + (flags >> 6 & 1);
+    vsdb.extend((0..2 * latencies).map(|i| 0x10 + i));
+    vsdb.push(0x00);
+    vsdb.push((vics.len() as u8) << 5);
+    vsdb.extend_from_slice(vics);
+    ExtensionBlock(cta_extension(0, &[(3, &vsdb)]))
+}
+
+#[test]
+fn hdmi_vics_latency() {
+    // Without latency fields, with video and audio latency, and with the
+    // interlaced latencies as well.
+    for &flags in &[0x00, 0x80, 0xc0] {
+        assert_eq!(hdmi_video(flags, &[1, 3]).hdmi_vics(), [1, 3], "flags {:#x}", flags);
+    }
+}
+
+#[test]
+fn hdmi_vics_missing() {
+    // No HDMI_Video_present.
+    let vsdb = [0x03, 0x0c, 0x00, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x01, 0x02];
+    assert!(ExtensionBlock(cta_extension(0, &[(3, &vsdb)])).hdmi_vics().is_empty());
+    // The list runs past the end of the block.
+    let vsdb = [0x03, 0x0c, 0x00, 0x10, 0x00, 0x00, 0x00, 0x20, 0x00, 0x60, 0x01];
+    assert!(ExtensionBlock(cta_extension(0, &[(3, &vsdb)])).hdmi_vics().is_empty());
+    assert!(ExtensionBlock(cta_extension(0, &[(3, HDMI_VSDB)])).hdmi_vics().is_empty());
+}
+
+#[test]
+fn hdmi_vic_timings() {
+    for &(code, active, refresh) in &[(1, (3840, 2160), 30.0), (2, (3840, 2160), 25.0), (3, (3840, 2160), 24.0), (4, (4096, 2160), 24.0)] {
+        let timing = hdmi_vic_timing(code).unwrap();
+        assert_eq!(timing.active, active);
+        assert_eq!(timing.pixel_clock, 297000000);
+        assert_eq!(timing.refresh_rate(), refresh, "HDMI_VIC {}", code);
+    }
+    assert_eq!(hdmi_vic_timing(1).unwrap().total(), (4400, 2250));
+    assert!(hdmi_vic_timing(0).is_none());
+    assert!(hdmi_vic_timing(5).is_none());
+}
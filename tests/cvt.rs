@@ -0,0 +1,72 @@
+mod common;
+
+use common::modeline;
+use edid_rs::{RbVersion, cvt_reduced_timing};
+
+#[test]
+fn reduced_blanking_v1() {
+    // The CVT-RB modes of the VESA DMT.
+    let timing = cvt_reduced_timing((3840, 2160), 60.0, RbVersion::V1, false).unwrap();
+    assert_eq!(modeline(&timing), (533250000, [3840, 3888, 3920, 4000], [2160, 2163, 2168, 2222]));
+    assert_eq!(timing.reduced_blanking(), Some(RbVersion::V1));
+
+    let timing = cvt_reduced_timing((2560, 1440), 60.0, RbVersion::V1, false).unwrap();
+    assert_eq!(modeline(&timing), (241500000, [2560, 2608, 2640, 2720], [1440, 1443, 1448, 1481]));
+    assert_eq!(timing.reduced_blanking(), Some(RbVersion::V1));
+}
+
+#[test]
+fn reduced_blanking_v2() {
+    // The CVT 2.0 example of 3840x2160 at 60Hz.
+    let timing = cvt_reduced_timing((3840, 2160), 60.0, RbVersion::V2, false).unwrap();
+    assert_eq!(modeline(&timing), (522614000, [3840, 3848, 3880, 3920], [2160, 2208, 2216, 2222]));
+    assert_eq!(timing.reduced_blanking(), Some(RbVersion::V2));
+
+    // Slowed by 1000/1001 for 59.94Hz.
+    let timing = cvt_reduced_timing((3840, 2160), 60.0, RbVersion::V2, true).unwrap();
+    assert_eq!(timing.pixel_clock, 522092000);
+    assert!(timing.is_fractional_of(60));
+}
+
+#[test]
+fn reduced_blanking_v3() {
+    // The vertical sync starts right after the first line of blanking.
+    let timing = cvt_reduced_timing((3840, 2160), 60.0, RbVersion::V3, false).unwrap();
+    assert_eq!(modeline(&timing), (522614000, [3840, 3848, 3880, 3920], [2160, 2161, 2169, 2222]));
+    assert_eq!(timing.reduced_blanking(), Some(RbVersion::V3));
+
+    let timing = cvt_reduced_timing((2560, 1440), 144.0, RbVersion::V3, false).unwrap();
+    assert_eq!(timing.reduced_blanking(), Some(RbVersion::V3));
+}
+
+#[test]
+fn not_reduced_blanking() {
+    // The DMT 1920x1080 at 60Hz mode, which is the CEA one.
+    let mut timing = cvt_reduced_timing((1920, 1080), 60.0, RbVersion::V1, false).unwrap();
+    timing.front_porch = (88, 4);
+    timing.sync_length = (44, 5);
+    timing.back_porch = (148, 36);
+    assert_eq!(timing.reduced_blanking(), None);
+}
+
+#[test]
+fn invalid_inputs() {
+    assert!(cvt_reduced_timing((1920, 1080), 0.0, RbVersion::V2, false).is_err());
+    assert!(cvt_reduced_timing((1920, 1080), -60.0, RbVersion::V2, false).is_err());
+    assert!(cvt_reduced_timing((1920, 1080), f64::NAN, RbVersion::V2, false).is_err());
+    assert!(cvt_reduced_timing((0, 1080), 60.0, RbVersion::V2, false).is_err());
+    // Rounded down to a multiple of eight, this has no width left.
+    assert!(cvt_reduced_timing((7, 1080), 60.0, RbVersion::V1, false).is_err());
+}
+
+#[test]
+fn overflow() {
+    // No time is left for the minimum vertical blanking.
+    assert!(cvt_reduced_timing((1920, 1080), 5000.0, RbVersion::V2, false).is_err());
+    // The blanking needs more lines than fit in a u16.
+    assert!(cvt_reduced_timing((1920, 1080), 2173.0, RbVersion::V2, false).is_err());
+    assert!(cvt_reduced_timing((65535, 1080), 60.0, RbVersion::V2, false).is_err());
+    assert!(cvt_reduced_timing((1920, 65535), 60.0, RbVersion::V2, false).is_err());
+    // Over 4GHz.
+    assert!(cvt_reduced_timing((7680, 4320), 240.0, RbVersion::V2, false).is_err());
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "descriptors")]
+
+mod common;
+
+use common::{SAMPLE, fix_checksum};
+use edid_rs::{EDID, Error, MonitorDescriptor, Reader, SecondaryTiming};
+
+fn parse(bytes: &[u8; 128]) -> EDID {
+    EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap()
+}
+
+#[test]
+fn dummy_before_name() {
+    // The sample with its monitor name and first dummy descriptor swapped,
+    // so a dummy descriptor is in the second slot.
+    let mut bytes = SAMPLE;
+    let name = bytes[72..90].to_vec();
+    bytes.copy_within(90..108, 72);
+    bytes[90..108].copy_from_slice(&name);
+    fix_checksum(&mut bytes);
+    assert_eq!(bytes[75], 0x10);
+
+    let edid = parse(&bytes);
+    assert_eq!(edid.descriptors.0.len(), 1);
+    assert!(matches!(&edid.descriptors.0[0], MonitorDescriptor::MonitorName(name) if name == "Color LCD"));
+    assert_eq!(edid.timings.detailed_timings.len(), 1);
+}
+
+#[test]
+fn dummy_with_data() {
+    // Whatever is left in a dummy descriptor is skipped with it.
+    let mut bytes = SAMPLE;
+    let name = bytes[72..90].to_vec();
+    bytes[72..90].copy_from_slice(&[0, 0, 0, 0x10, 0, 0xff, 0xff, 0xfc, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    bytes[90..108].copy_from_slice(&name);
+    fix_checksum(&mut bytes);
+
+    let edid = parse(&bytes);
+    assert!(matches!(&edid.descriptors.0[..], [MonitorDescriptor::MonitorName(name)] if name == "Color LCD"));
+}
+
+#[test]
+fn inverted_range_limits() {
+    // Range limits in place of the first dummy descriptor, with
+    // 75-50Hz vertical and 80-30kHz horizontal rates.
+    let mut bytes = SAMPLE;
+    bytes[90..108].copy_from_slice(&[0, 0, 0, 0xfd, 0, 75, 50, 80, 30, 17, 0, 0x0a, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20]);
+    fix_checksum(&mut bytes);
+
+    let edid = parse(&bytes);
+    let limits = edid.descriptors.0.iter()
+        .find(|d| matches!(d, MonitorDescriptor::RangeLimits { .. }))
+        .unwrap();
+    assert!(matches!(limits, MonitorDescriptor::RangeLimits { vertical_rate: (75, 50), horizontal_rate: (80000, 30000), .. }));
+    assert!(!limits.is_consistent());
+
+    let (_, warnings) = EDID::parse_lenient(&mut Reader::new(&mut &bytes[..])).unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].field, "range limits");
+    assert_eq!(warnings[0].offset, 90);
+    assert_eq!(warnings[0].error, Error::BadDescriptor { tag: 0xfd, offset: 90 });
+
+    // Put the right way round, the same limits are fine.
+    bytes[95..99].copy_from_slice(&[50, 75, 30, 80]);
+    fix_checksum(&mut bytes);
+    let (edid, warnings) = EDID::parse_lenient(&mut Reader::new(&mut &bytes[..])).unwrap();
+    assert!(warnings.is_empty());
+    assert!(edid.descriptors.0.iter().all(|d| d.is_consistent()));
+}
+
+#[test]
+fn cvt_blanking() {
+    // CVT range limits preferring 16:9, with reduced blanking (bit 4)
+    // but not standard blanking (bit 3).
+    let mut bytes = SAMPLE;
+    bytes[90..108].copy_from_slice(&[0, 0, 0, 0xfd, 0, 50, 75, 30, 80, 17, 0x04, 0x11, 0, 0, 0xf0, 0x30, 0, 60]);
+    fix_checksum(&mut bytes);
+
+    let edid = parse(&bytes);
+    let cvt = edid.descriptors.0.iter().find_map(|d| match d {
+        MonitorDescriptor::RangeLimits { secondary_timing: cvt @ SecondaryTiming::CVT { .. }, .. } => Some(cvt),
+        _ => None
+    }).unwrap();
+    assert!(matches!(cvt, SecondaryTiming::CVT { reduced_blanking: true, standard_blanking: false, preferred_refresh: 60, .. }));
+
+    // And the other way round.
+    bytes[105] = 0x28;
+    fix_checksum(&mut bytes);
+    let edid = parse(&bytes);
+    assert!(edid.descriptors.0.iter().any(|d| matches!(d, MonitorDescriptor::RangeLimits {
+        secondary_timing: SecondaryTiming::CVT { reduced_blanking: false, standard_blanking: true, .. }, ..
+    })));
+}
+
+#[test]
+fn rate_offsets() {
+    // Range limits as 1080p 360Hz monitors give them: 48-360Hz vertical,
+    // 255-510kHz horizontal and 1470MHz, with 255 added to the vertical
+    // and horizontal maximums (0b10 in bits 1-0 and 3-2).
+    let mut bytes = SAMPLE;
+    bytes[90..108].copy_from_slice(&[0, 0, 0, 0xfd, 0x0a, 48, 105, 255, 255, 147, 0x01, 0x0a, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20]);
+    fix_checksum(&mut bytes);
+
+    let (edid, warnings) = EDID::parse_lenient(&mut Reader::new(&mut &bytes[..])).unwrap();
+    assert!(warnings.is_empty());
+    let limits = edid.range_limits().unwrap();
+    assert!(matches!(limits, MonitorDescriptor::RangeLimits {
+        vertical_rate: (48, 360), horizontal_rate: (255000, 510000), pixel_clock: 1470000000, ..
+    }), "{:?}", limits);
+    assert!(limits.is_consistent());
+
+    // Offsets on both ends, 256-300Hz.
+    bytes[94..97].copy_from_slice(&[0x03, 1, 45]);
+    fix_checksum(&mut bytes);
+    assert!(matches!(parse(&bytes).range_limits(), Some(MonitorDescriptor::RangeLimits { vertical_rate: (256, 300), horizontal_rate: (255000, 255000), .. })));
+
+    // EDID 1.3 has no offsets, the byte is reserved.
+    bytes[19] = 3;
+    fix_checksum(&mut bytes);
+    assert!(matches!(parse(&bytes).range_limits(), Some(MonitorDescriptor::RangeLimits { vertical_rate: (1, 45), .. })));
+}
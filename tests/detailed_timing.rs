@@ -0,0 +1,157 @@
+mod common;
+
+use common::{SAMPLE, fix_checksum};
+use edid_rs::{DetailedTiming, EDID, Error, Reader, RefreshStrategy, RgbTimings, SyncLine, SyncPolarity, SyncType};
+
+// The sample's preferred timing with `blanking` pixels of horizontal and
+// `v_blanking` lines of vertical blanking, as from a capture dongle which
+// gives less blanking than its porches and syncs.
+fn with_blanking(blanking: u8, v_blanking: u8) -> [u8; 128] {
+    let mut bytes = SAMPLE;
+    bytes[57] = blanking;
+    bytes[60] = v_blanking;
+    fix_checksum(&mut bytes);
+    bytes
+}
+
+#[test]
+fn short_blanking() {
+    // 48 pixels of front porch and 32 of sync in 60 pixels of blanking.
+    let bytes = with_blanking(60, 52);
+    assert_eq!(EDID::parse(&mut Reader::new(&mut &bytes[..])).err(), Some(Error::BadTiming { offset: 54 }));
+
+    let (edid, warnings) = EDID::parse_lenient(&mut Reader::new(&mut &bytes[..])).unwrap();
+    let timing = &edid.timings.detailed_timings[0];
+    assert_eq!(timing.back_porch, (0, 43));
+    assert_eq!(timing.front_porch, (48, 3));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].error, Error::BadTiming { offset: 54 });
+}
+
+#[test]
+fn short_vertical_blanking() {
+    // 3 lines of front porch and 6 of sync in 5 lines of blanking.
+    let bytes = with_blanking(160, 5);
+    assert_eq!(EDID::parse(&mut Reader::new(&mut &bytes[..])).err(), Some(Error::BadTiming { offset: 54 }));
+
+    let (edid, _) = EDID::parse_lenient(&mut Reader::new(&mut &bytes[..])).unwrap();
+    assert_eq!(edid.timings.detailed_timings[0].back_porch, (80, 0));
+}
+
+#[test]
+fn never_panics() {
+    // Every value of every byte of the sample.
+    for offset in 0..128 {
+        for value in 0..=255 {
+            let mut bytes = SAMPLE;
+            bytes[offset] = value;
+            let _ = EDID::parse(&mut Reader::new(&mut &bytes[..]));
+            let _ = EDID::parse_lenient(&mut Reader::new(&mut &bytes[..]));
+        }
+    }
+}
+
+#[test]
+fn with_refresh() {
+    let timing = EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap().timings.detailed_timings[0].clone();
+    // 3040 by 1852 in total.
+
+    let scaled = |hz| timing.with_refresh(hz, RefreshStrategy::ClockScaled).unwrap();
+    for &(hz, clock) in &[(50.0, 281500000), (48.0, 270240000)] {
+        let new = scaled(hz);
+        assert_eq!(new.pixel_clock, clock);
+        assert_eq!(new.back_porch, timing.back_porch);
+        assert!((new.refresh_rate() as f64 - hz).abs() < 0.01, "{}", new.refresh_rate());
+    }
+
+    let stretched = |hz| timing.with_refresh(hz, RefreshStrategy::VblankStretched).unwrap();
+    for &(hz, back_porch) in &[(50.0, 413), (48.0, 506)] {
+        let new = stretched(hz);
+        assert_eq!(new.pixel_clock, timing.pixel_clock);
+        assert_eq!(new.back_porch, (80, back_porch));
+        assert!((new.refresh_rate() as f64 - hz).abs() < 0.05, "{}", new.refresh_rate());
+    }
+}
+
+#[test]
+fn with_refresh_invalid() {
+    let timing = EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap().timings.detailed_timings[0].clone();
+    for &strategy in &[RefreshStrategy::ClockScaled, RefreshStrategy::VblankStretched] {
+        assert!(timing.with_refresh(0.0, strategy).is_err());
+        assert!(timing.with_refresh(f64::INFINITY, strategy).is_err());
+    }
+    // Fewer lines than the active area and the rest of the blanking.
+    assert!(timing.with_refresh(120.0, RefreshStrategy::VblankStretched).is_err());
+    // Over 655.35MHz.
+    assert!(timing.with_refresh(120.0, RefreshStrategy::ClockScaled).is_err());
+}
+
+// The CTA-861 1920x1080 progressive timing with `h_front` pixels of front
+// porch, at `clock` as a detailed timing would store it.
+fn cta_1080p(clock: u32, h_front: u16) -> DetailedTiming {
+    DetailedTiming {
+        pixel_clock: clock,
+        active: (1920, 1080),
+        front_porch: (h_front, 4),
+        sync_length: (44, 5),
+        back_porch: (148, 36),
+        ..EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap().timings.detailed_timings[0].clone()
+    }
+}
+
+#[test]
+fn fractional_rates() {
+    // 23.976, 29.97, 59.94 and 119.88Hz, with the clocks rounded to 10kHz.
+    for &(clock, integer_clock, h_front, base, millihz) in &[
+        (74180000, 74250000, 638, 24, 23977),
+        (74180000, 74250000, 88, 30, 29972),
+        (148350000, 148500000, 88, 60, 59939),
+        (296700000, 297000000, 88, 120, 119879)
+    ] {
+        let timing = cta_1080p(clock, h_front);
+        assert_eq!(timing.refresh_millihz(), millihz);
+        assert!(timing.is_fractional_of(base), "{}Hz", base);
+        assert!(!timing.is_fractional_of(base - 1), "{}Hz", base);
+        assert!(!timing.is_fractional_of(base + 1), "{}Hz", base);
+
+        // The integer rates are not fractional.
+        let integer = cta_1080p(integer_clock, h_front);
+        assert_eq!(integer.refresh_millihz(), base * 1000);
+        assert!(!integer.is_fractional_of(base), "{}Hz", base);
+    }
+}
+
+#[test]
+fn zero_total() {
+    let timing = DetailedTiming { active: (0, 0), front_porch: (0, 0), sync_length: (0, 0), back_porch: (0, 0), ..cta_1080p(148500000, 88) };
+    assert_eq!(timing.refresh_millihz(), 0);
+    assert!(!timing.is_fractional_of(60));
+    assert!(!cta_1080p(148500000, 88).is_fractional_of(0));
+}
+
+#[test]
+fn rgb_timings() {
+    let timing = EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap().timings.detailed_timings[0].clone();
+    let rgb = RgbTimings::from(&timing);
+    assert_eq!(rgb.pixel_clock, 337750000);
+    assert_eq!(rgb.active, (2880, 1800));
+    assert_eq!((rgb.front_porch, rgb.sync_length, rgb.back_porch), ((48, 3), (32, 6), (80, 43)));
+    assert_eq!(rgb.total(), (3040, 1852));
+    assert!(matches!(rgb.polarity, (SyncPolarity::Positive, SyncPolarity::Negative)));
+
+    // Borders are left out.
+    let bordered = DetailedTiming { border: (8, 8), ..timing.clone() };
+    assert_eq!(RgbTimings::from(&bordered).total(), rgb.total());
+}
+
+#[test]
+fn rgb_timings_composite() {
+    let timing = EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap().timings.detailed_timings[0].clone();
+
+    let digital = DetailedTiming { sync_type: SyncType::Composite { serrated: false, line: SyncLine::Digital(SyncPolarity::Positive) }, ..timing.clone() };
+    assert!(matches!(RgbTimings::from(&digital).polarity, (SyncPolarity::Positive, SyncPolarity::Positive)));
+
+    // Analog sync has no polarity.
+    let analog = DetailedTiming { sync_type: SyncType::Composite { serrated: true, line: SyncLine::Green }, ..timing };
+    assert!(matches!(RgbTimings::from(&analog).polarity, (SyncPolarity::Negative, SyncPolarity::Negative)));
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "dp-aux")]
+
+use edid_rs::linux::dp_aux::{AuxError, DpAux};
+use std::io;
+
+#[test]
+fn aux_errors() {
+    let of = |errno| AuxError::of(&io::Error::from_raw_os_error(errno));
+    assert_eq!(of(11), Some(AuxError::Defer));
+    assert_eq!(of(16), Some(AuxError::Defer));
+    assert_eq!(of(5), Some(AuxError::Nack));
+    assert_eq!(of(121), Some(AuxError::Nack));
+    assert_eq!(of(110), Some(AuxError::Timeout));
+    assert_eq!(of(2), None);
+    assert_eq!(AuxError::of(&io::Error::other("other")), None);
+}
+
+#[test]
+fn wrapped_aux_errors() {
+    let err = io::Error::new(io::ErrorKind::TimedOut, AuxError::Timeout);
+    assert_eq!(AuxError::of(&err), Some(AuxError::Timeout));
+    assert_eq!(err.to_string(), "AUX transaction timed out");
+}
+
+#[test]
+fn open_missing() {
+    let err = DpAux::open("/nonexistent/drm_dp_aux0").err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+}
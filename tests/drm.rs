@@ -0,0 +1,64 @@
+#![cfg(all(feature = "drm", target_os = "linux"))]
+
+mod common;
+
+use common::SAMPLE;
+use edid_rs::linux::drm::*;
+use edid_rs::{DetailedTiming, EDID, Reader, StereoType, SyncPolarity, SyncType};
+use std::convert::TryFrom;
+
+fn sample_timing() -> DetailedTiming {
+    EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap().timings.detailed_timings[0].clone()
+}
+
+fn name(mode: &ModeInfo) -> &str {
+    let len = mode.name.iter().position(|&b| b == 0).unwrap();
+    std::str::from_utf8(&mode.name[..len]).unwrap()
+}
+
+#[test]
+fn sample() {
+    let mode = ModeInfo::try_from(&sample_timing()).unwrap();
+    assert_eq!(mode.clock, 337750);
+    assert_eq!((mode.hdisplay, mode.hsync_start, mode.hsync_end, mode.htotal), (2880, 2928, 2960, 3040));
+    assert_eq!((mode.vdisplay, mode.vsync_start, mode.vsync_end, mode.vtotal), (1800, 1803, 1809, 1852));
+    assert_eq!(mode.vrefresh, 60);
+    assert_eq!(mode.flags, DRM_MODE_FLAG_PHSYNC | DRM_MODE_FLAG_NVSYNC);
+    assert_eq!(mode.type_, DRM_MODE_TYPE_DRIVER);
+    assert_eq!(name(&mode), "2880x1800");
+}
+
+#[test]
+fn interlaced() {
+    // CTA-861 VIC 5, 1920x1080i at 60Hz, given per field.
+    let timing = DetailedTiming {
+        pixel_clock: 74250000,
+        active: (1920, 540),
+        front_porch: (88, 2),
+        sync_length: (44, 5),
+        back_porch: (148, 15),
+        image_size: None,
+        border: (0, 0),
+        interlaced: true,
+        stereo: StereoType::None,
+        sync_type: SyncType::Seperate { horizontal: SyncPolarity::Positive, vertical: SyncPolarity::Positive }
+    };
+
+    let mode = ModeInfo::try_from(&timing).unwrap();
+    assert_eq!((mode.hdisplay, mode.hsync_start, mode.hsync_end, mode.htotal), (1920, 2008, 2052, 2200));
+    // Doubled to frames, with the odd half line of the total.
+    assert_eq!((mode.vdisplay, mode.vsync_start, mode.vsync_end, mode.vtotal), (1080, 1084, 1094, 1125));
+    // The field rate, not the frame rate.
+    assert_eq!(mode.vrefresh, 60);
+    assert_eq!(mode.flags, DRM_MODE_FLAG_PHSYNC | DRM_MODE_FLAG_PVSYNC | DRM_MODE_FLAG_INTERLACE);
+    assert_eq!(name(&mode), "1920x1080i");
+}
+
+#[test]
+fn overflow() {
+    let timing = DetailedTiming { active: (65535, 1800), ..sample_timing() };
+    assert!(ModeInfo::try_from(&timing).is_err());
+
+    let timing = DetailedTiming { active: (2880, 40000), interlaced: true, ..sample_timing() };
+    assert!(ModeInfo::try_from(&timing).is_err());
+}
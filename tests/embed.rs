@@ -0,0 +1,95 @@
+mod common;
+
+use common::{SAMPLE, cta_extension, fix_checksum};
+use edid_rs::embed::{check, preferred_active};
+
+#[test]
+fn hex() {
+    const EDID: [u8; 128] = edid_rs::edid!("
+        00ffffffffffff00061022a00000000004170104a5211578026fb1a7554c9e25
+        0c505400000001010101010101010101010101010101ef8340a0b00834703020
+        36004bcf1000001a000000fc00436f6c6f72204c43440a202020000000100000
+        00000000000000000000000000000010000000000000000000000000000000de
+    ");
+    assert_eq!(EDID, SAMPLE);
+}
+
+#[test]
+fn array() {
+    const EDID: [u8; 128] = edid_rs::edid!(SAMPLE);
+    assert_eq!(EDID, SAMPLE);
+}
+
+#[test]
+fn include_bytes() {
+    const EDID: [u8; 128] = edid_rs::edid!(include_bytes!("data/sample.bin"));
+    assert_eq!(EDID, SAMPLE);
+}
+
+#[test]
+fn active() {
+    const ACTIVE: (u16, u16) = preferred_active(&SAMPLE);
+    assert_eq!(ACTIVE, (2880, 1800));
+}
+
+#[test]
+fn with_extension() {
+    let mut bytes = SAMPLE.to_vec();
+    bytes[126] = 1;
+    fix_checksum(&mut bytes);
+    bytes.extend_from_slice(&cta_extension(0, &[]));
+    check(&bytes);
+}
+
+#[test]
+#[should_panic(expected = "shorter than one 128-byte block")]
+fn short() {
+    check(&SAMPLE[..127]);
+}
+
+#[test]
+#[should_panic(expected = "EDID header")]
+fn header() {
+    let mut bytes = SAMPLE;
+    bytes[7] = 0xff;
+    fix_checksum(&mut bytes);
+    check(&bytes);
+}
+
+#[test]
+#[should_panic(expected = "EDID version")]
+fn version() {
+    let mut bytes = SAMPLE;
+    bytes[18] = 2;
+    fix_checksum(&mut bytes);
+    check(&bytes);
+}
+
+#[test]
+#[should_panic(expected = "extension count")]
+fn count() {
+    let mut bytes = SAMPLE;
+    bytes[126] = 1;
+    fix_checksum(&mut bytes);
+    check(&bytes);
+}
+
+#[test]
+#[should_panic(expected = "of the base block is wrong")]
+fn checksum() {
+    let mut bytes = SAMPLE;
+    bytes[127] ^= 1;
+    check(&bytes);
+}
+
+#[test]
+#[should_panic(expected = "of an extension block is wrong")]
+fn extension_checksum() {
+    let mut bytes = SAMPLE.to_vec();
+    bytes[126] = 1;
+    fix_checksum(&mut bytes);
+    let mut extension = cta_extension(0, &[]);
+    extension[127] ^= 1;
+    bytes.extend_from_slice(&extension);
+    check(&bytes);
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use common::{SAMPLE, fix_checksum};
+use edid_rs::{EDID, Error, Reader, Warning};
+
+fn parse(bytes: &[u8]) -> Result<EDID, Error> {
+    EDID::parse(&mut Reader::new(&mut &bytes[..]))
+}
+
+fn parse_lenient(bytes: &[u8]) -> Result<(EDID, Vec<Warning>), Error> {
+    EDID::parse_lenient(&mut Reader::new(&mut &bytes[..]))
+}
+
+#[test]
+fn bad_checksum() {
+    let mut bytes = SAMPLE;
+    bytes[127] = 0x12;
+
+    // The data is still read, but flagged.
+    let edid = parse(&bytes).unwrap();
+    assert!(!edid.checksum_ok);
+    assert_eq!(edid.checksum, 0x12);
+
+    let (edid, _) = parse_lenient(&bytes).unwrap();
+    assert!(!edid.checksum_ok);
+
+    let (partial, error) = EDID::parse_partial(&mut Reader::new(&mut &bytes[..]));
+    assert_eq!(error, None);
+    assert_eq!(partial.checksum_ok, Some(false));
+
+    // A changed byte anywhere else is caught the same way.
+    let mut bytes = SAMPLE;
+    bytes[20] ^= 0x01;
+    assert!(!parse(&bytes).unwrap().checksum_ok);
+    fix_checksum(&mut bytes);
+    assert!(parse(&bytes).unwrap().checksum_ok);
+}
+
+#[test]
+fn missing_preferred_timing() {
+    // A dummy descriptor in place of the preferred timing.
+    let mut bytes = SAMPLE;
+    bytes[54..72].copy_from_slice(&[0, 0, 0, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    fix_checksum(&mut bytes);
+
+    assert_eq!(parse(&bytes).err(), Some(Error::MissingPreferredTiming { offset: 54 }));
+
+    let (edid, warnings) = parse_lenient(&bytes).unwrap();
+    assert!(edid.timings.detailed_timings.is_empty());
+    assert_eq!(warnings.len(), 1);
+    assert_eq!((warnings[0].field, warnings[0].offset), ("preferred timing", 54));
+    assert_eq!(warnings[0].error, Error::MissingPreferredTiming { offset: 54 });
+
+    // Without the preferred timing bit this is allowed.
+    bytes[24] &= !0x02;
+    fix_checksum(&mut bytes);
+    let (edid, warnings) = parse_lenient(&bytes).unwrap();
+    assert!(warnings.is_empty());
+    assert!(!edid.display.dpms.preferred_timing_mode);
+    assert!(parse(&bytes).is_ok());
+}
+
+#[test]
+fn unsupported_version() {
+    // A version 1 header with version 2 at byte 18.
+    let mut bytes = SAMPLE;
+    bytes[18] = 2;
+    fix_checksum(&mut bytes);
+    assert_eq!(parse(&bytes).err(), Some(Error::UnsupportedVersion { version: 2, revision: 4 }));
+    assert_eq!(parse_lenient(&bytes).err(), Some(Error::UnsupportedVersion { version: 2, revision: 4 }));
+
+    // The start of an EDID 2.0 structure: version, revision and the
+    // manufacturer ID.
+    let mut bytes = [0u8; 256];
+    bytes[..4].copy_from_slice(&[0x20, 0x00, 0x04, 0x10]);
+    assert_eq!(parse(&bytes).err(), Some(Error::UnsupportedVersion { version: 2, revision: 0 }));
+}
+
+#[test]
+fn displayid() {
+    // An empty DisplayID 1.2 section, which sums to zero over its five bytes.
+    let mut bytes = [0u8; 128];
+    bytes[..5].copy_from_slice(&[0x12, 0x00, 0x00, 0x00, 0xee]);
+    assert_eq!(parse(&bytes).err(), Some(Error::DisplayId { version: 0x12 }));
+
+    // A DisplayID 2.0 section with a 3-byte data block.
+    bytes[..10].copy_from_slice(&[0x20, 0x06, 0x03, 0x00, 0x2b, 0x00, 0x03, 0x00, 0x00, 0x00]);
+    let sum = bytes[..10].iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+    bytes[10] = sum.wrapping_neg();
+    assert_eq!(parse(&bytes).err(), Some(Error::DisplayId { version: 0x20 }));
+
+    // Not summing to zero, it is not taken for DisplayID.
+    bytes[10] ^= 1;
+    assert_eq!(parse(&bytes).err(), Some(Error::UnsupportedVersion { version: 2, revision: 0 }));
+}
+
+#[test]
+fn blank_block() {
+    assert_eq!(parse(&[0x00; 128]).err(), Some(Error::EmptyBlock { offset: 0 }));
+    assert_eq!(parse(&[0xff; 128]).err(), Some(Error::BusStuckHigh { offset: 0 }));
+
+    // A single set byte past the header means the block holds something.
+    let mut bytes = [0x00; 128];
+    bytes[64] = 1;
+    assert_eq!(parse(&bytes).err(), Some(Error::InvalidHeader { offset: 0 }));
+    let mut bytes = [0xff; 128];
+    bytes[127] = 0xfe;
+    assert_eq!(parse(&bytes).err(), Some(Error::InvalidHeader { offset: 0 }));
+
+    // Lenient parsing gives up on them all the same.
+    assert_eq!(parse_lenient(&[0x00; 128]).err(), Some(Error::EmptyBlock { offset: 0 }));
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "ffi")]
+
+mod common;
+
+use common::SAMPLE;
+use edid_rs::ffi::*;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::ptr;
+
+fn parse(bytes: &[u8]) -> (i32, *mut EdidHandle) {
+    let mut handle = ptr::null_mut();
+    let code = unsafe { edid_parse(bytes.as_ptr(), bytes.len(), &mut handle) };
+    (code, handle)
+}
+
+#[test]
+fn parse_and_free() {
+    let (code, handle) = parse(&SAMPLE);
+    assert_eq!(code, EDID_OK);
+    assert!(!handle.is_null());
+
+    let mut product = 0;
+    assert_eq!(unsafe { edid_product_code(handle, &mut product) }, EDID_OK);
+    assert_eq!(product, 40994);
+
+    unsafe { edid_free(handle) };
+}
+
+#[test]
+fn parse_errors() {
+    let (code, handle) = parse(&SAMPLE[..100]);
+    assert_eq!(code, EDID_ERROR_PARSE);
+    assert!(handle.is_null());
+
+    let mut handle = ptr::null_mut();
+    assert_eq!(unsafe { edid_parse(ptr::null(), 128, &mut handle) }, EDID_ERROR_NULL_POINTER);
+    assert_eq!(unsafe { edid_parse(SAMPLE.as_ptr(), SAMPLE.len(), ptr::null_mut()) }, EDID_ERROR_NULL_POINTER);
+}
+
+#[test]
+fn manufacturer() {
+    let (_, handle) = parse(&SAMPLE);
+
+    let mut buf = [0x7f as c_char; 8];
+    assert_eq!(unsafe { edid_manufacturer(handle, buf.as_mut_ptr(), buf.len()) }, EDID_OK);
+    assert_eq!(unsafe { CStr::from_ptr(buf.as_ptr()) }.to_str(), Ok("APP"));
+
+    // Three letters need four bytes with the terminator.
+    let mut small = [0x7f as c_char; 3];
+    assert_eq!(unsafe { edid_manufacturer(handle, small.as_mut_ptr(), small.len()) }, EDID_ERROR_BUFFER_TOO_SMALL);
+    assert_eq!(small, [0x7f; 3]);
+    let mut exact = [0x7f as c_char; 4];
+    assert_eq!(unsafe { edid_manufacturer(handle, exact.as_mut_ptr(), exact.len()) }, EDID_OK);
+    assert_eq!(exact[3], 0);
+
+    assert_eq!(unsafe { edid_manufacturer(handle, ptr::null_mut(), 8) }, EDID_ERROR_NULL_POINTER);
+    assert_eq!(unsafe { edid_manufacturer(ptr::null(), buf.as_mut_ptr(), buf.len()) }, EDID_ERROR_NULL_POINTER);
+
+    unsafe { edid_free(handle) };
+}
+
+#[test]
+fn preferred_mode() {
+    let (_, handle) = parse(&SAMPLE);
+
+    let (mut width, mut height, mut clock) = (0, 0, 0);
+    assert_eq!(unsafe { edid_preferred_mode(handle, &mut width, &mut height, &mut clock) }, EDID_OK);
+    assert_eq!((width, height, clock), (2880, 1800, 337750000));
+
+    // Each output is optional.
+    let mut height = 0;
+    assert_eq!(unsafe { edid_preferred_mode(handle, ptr::null_mut(), &mut height, ptr::null_mut()) }, EDID_OK);
+    assert_eq!(height, 1800);
+    assert_eq!(unsafe { edid_preferred_mode(handle, ptr::null_mut(), ptr::null_mut(), ptr::null_mut()) }, EDID_OK);
+
+    assert_eq!(unsafe { edid_preferred_mode(ptr::null(), &mut width, &mut height, &mut clock) }, EDID_ERROR_NULL_POINTER);
+
+    unsafe { edid_free(handle) };
+}
+
+#[test]
+fn monitor_name() {
+    let (_, handle) = parse(&SAMPLE);
+
+    let mut buf = [0 as c_char; 14];
+    assert_eq!(unsafe { edid_monitor_name(handle, buf.as_mut_ptr(), buf.len()) }, EDID_OK);
+    assert_eq!(unsafe { CStr::from_ptr(buf.as_ptr()) }.to_str(), Ok("Color LCD"));
+    assert_eq!(unsafe { edid_monitor_name(handle, buf.as_mut_ptr(), 9) }, EDID_ERROR_BUFFER_TOO_SMALL);
+
+    unsafe { edid_free(handle) };
+}
+
+#[test]
+fn free_null() {
+    unsafe { edid_free(ptr::null_mut()) };
+}
+
+#[test]
+fn error_messages() {
+    for &(code, message) in &[
+        (EDID_OK, "success"),
+        (EDID_ERROR_BUFFER_TOO_SMALL, "the buffer is too small"),
+        (-1, "unknown error code")
+    ] {
+        assert_eq!(unsafe { CStr::from_ptr(edid_error_message(code)) }.to_str(), Ok(message));
+    }
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::{SAMPLE, fix_checksum};
+use edid_rs::{Error, find_and_parse, find_edids};
+
+// `junk` bytes of 0x5b, which never make a block summing to zero.
+fn junk(len: usize) -> Vec<u8> {
+    vec![0x5b; len]
+}
+
+#[test]
+fn unaligned() {
+    let mut data = junk(37);
+    data.extend_from_slice(&SAMPLE);
+    data.extend(junk(50));
+
+    let found = find_edids(&data);
+    assert_eq!(found.len(), 1);
+    assert_eq!((found[0].offset, found[0].len, found[0].complete), (37, 128, true));
+    assert!(found[0].edid.is_ok());
+
+    let (offset, edid) = find_and_parse(&data).unwrap();
+    assert_eq!(offset, 37);
+    assert_eq!(edid.product.product_code, 40994);
+}
+
+#[test]
+fn false_header() {
+    // A copy of the header whose block does not sum to zero.
+    let mut broken = SAMPLE;
+    broken[127] ^= 1;
+
+    let mut data = junk(3);
+    data.extend_from_slice(&broken[..64]);
+    data.extend_from_slice(&SAMPLE);
+    data.extend(junk(64));
+
+    let found = find_edids(&data);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].offset, 67);
+    assert_eq!(find_and_parse(&data).unwrap().0, 67);
+
+    assert!(find_edids(&broken).is_empty());
+    assert_eq!(find_and_parse(&broken).err(), Some(Error::InvalidHeader { offset: 0 }));
+}
+
+#[test]
+fn missing_extension() {
+    let mut base = SAMPLE;
+    base[126] = 2;
+    fix_checksum(&mut base);
+
+    let mut extension = [0u8; 128];
+    extension[..4].copy_from_slice(&[0x02, 0x03, 0x04, 0x00]);
+    fix_checksum(&mut extension);
+
+    let mut data = base.to_vec();
+    data.extend_from_slice(&extension);
+    data.extend(junk(128));
+
+    let found = find_edids(&data);
+    assert_eq!(found.len(), 1);
+    assert_eq!((found[0].len, found[0].complete), (256, false));
+}
+
+#[cfg(feature = "cta")]
+#[test]
+fn eeodb() {
+    use common::cta_extension;
+
+    // One extension in the base block, three in the override.
+    let mut base = SAMPLE;
+    base[126] = 1;
+    fix_checksum(&mut base);
+
+    let mut data = junk(5);
+    data.extend_from_slice(&base);
+    data.extend_from_slice(&cta_extension(0, &[(7, &[0x78, 0x03])]));
+    data.extend_from_slice(&cta_extension(0, &[]));
+    data.extend_from_slice(&cta_extension(0, &[]));
+    data.extend_from_slice(&SAMPLE);
+
+    let found = find_edids(&data);
+    assert_eq!(found.len(), 2);
+    assert_eq!((found[0].offset, found[0].len, found[0].complete), (5, 512, true));
+    assert_eq!(found[1].offset, 517);
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "cta")]
+
+mod common;
+
+use common::{SAMPLE, cta_extension};
+use edid_rs::{DolbyVision, DolbyVisionCapability, EDID, ExtensionBlock, Hdr10PlusCapability, Reader};
+
+fn sample() -> EDID {
+    EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap()
+}
+
+// The HDR static metadata block of an OLED TV, with SDR, PQ and HLG,
+// static metadata type 1 and luminance codes 115, 90 and 1.
+const TV_HDR: &[u8] = &[0x06, 0x0d, 0x01, 0x73, 0x5a, 0x01];
+// Its colorimetry block, with BT.2020 YCC and RGB.
+const TV_COLORIMETRY: &[u8] = &[0x05, 0xc0, 0x00];
+
+fn close(value: f32, expected: f32, tolerance: f32) -> bool {
+    (value - expected).abs() <= tolerance
+}
+
+#[test]
+fn luminance() {
+    let block = ExtensionBlock(cta_extension(0, &[(7, TV_HDR)]));
+    let hdr = block.hdr_static_metadata().unwrap();
+
+    assert!(hdr.supports_pq());
+    assert!(close(hdr.max_luminance_nits().unwrap(), 603.67, 0.01));
+    assert!(close(hdr.max_frame_avg_nits().unwrap(), 351.25, 0.01));
+    assert!(close(hdr.min_luminance_nits().unwrap(), 0.0000928, 0.000001));
+
+    let hdr = ExtensionBlock(cta_extension(0, &[(7, &[0x06, 0x04, 0x01, 0xff])])).hdr_static_metadata().unwrap();
+    assert!(close(hdr.max_luminance_nits().unwrap(), 12525.7, 0.5));
+}
+
+#[test]
+fn optional_luminance() {
+    let block = ExtensionBlock(cta_extension(0, &[(7, &[0x06, 0x05, 0x01])]));
+    let hdr = block.hdr_static_metadata().unwrap();
+    assert_eq!(hdr.max_luminance_nits(), None);
+    assert_eq!(hdr.max_frame_avg_nits(), None);
+    assert_eq!(hdr.min_luminance_nits(), None);
+
+    // The minimum is relative to the maximum, so needs it.
+    let block = ExtensionBlock(cta_extension(0, &[(7, &[0x06, 0x05, 0x01, 0x60, 0x00])]));
+    let hdr = block.hdr_static_metadata().unwrap();
+    assert!(close(hdr.max_luminance_nits().unwrap(), 400.0, 0.01));
+    assert!(close(hdr.max_frame_avg_nits().unwrap(), 50.0, 0.01));
+    assert_eq!(hdr.min_luminance_nits(), None);
+}
+
+#[test]
+fn hdr10() {
+    let edid = sample();
+    let tv = vec![ExtensionBlock(cta_extension(0, &[(7, TV_HDR), (7, TV_COLORIMETRY)]))];
+    assert!(edid.hdr10_capable(&tv));
+
+    // Without BT.2020 colorimetry.
+    let no_bt2020 = vec![ExtensionBlock(cta_extension(0, &[(7, TV_HDR), (7, &[0x05, 0x03, 0x00])]))];
+    assert!(!edid.hdr10_capable(&no_bt2020));
+
+    // HLG only.
+    let hlg = vec![ExtensionBlock(cta_extension(0, &[(7, &[0x06, 0x09, 0x01]), (7, TV_COLORIMETRY)]))];
+    assert!(!edid.hdr10_capable(&hlg));
+
+    // PQ without static metadata type 1.
+    let no_metadata = vec![ExtensionBlock(cta_extension(0, &[(7, &[0x06, 0x05, 0x00]), (7, TV_COLORIMETRY)]))];
+    assert!(!edid.hdr10_capable(&no_metadata));
+
+    assert!(!edid.hdr10_capable(&[]));
+}
+
+fn dolby_vision(payload: &[u8]) -> DolbyVisionCapability {
+    match ExtensionBlock(cta_extension(0, &[(7, payload)])).dolby_vision() {
+        Some(DolbyVision::Capability(dv)) => dv,
+        other => panic!("{:?}", other)
+    }
+}
+
+#[test]
+fn dolby_vision_v2() {
+    // From an LG OLED TV.
+    let dv = dolby_vision(&[0x01, 0x46, 0xd0, 0x00, 0x48, 0x03, 0x76, 0x82, 0x5e, 0x6d, 0x95]);
+    assert_eq!(dv.version, 2);
+    assert_eq!(dv.dm_version, (4, 0));
+    assert!(!dv.backlight_control && !dv.global_dimming);
+    // PQ codes 0 and 2965.
+    assert_eq!(dv.target_luminance.0, 0.0);
+    assert!(close(dv.target_luminance.1, 775.0, 1.0), "{}", dv.target_luminance.1);
+    assert!(dv.interface.standard && dv.interface.low_latency && !dv.interface.low_latency_hdmi);
+    assert_eq!(dv.yuv444_bits, None);
+}
+
+#[test]
+fn dolby_vision_v1() {
+    let dv = dolby_vision(&[0x01, 0x46, 0xd0, 0x00, 0x22, 0x9b, 0x40, 0x01, 0xb0, 0x50, 0x4c, 0xa0, 0x26, 0x10]);
+    assert_eq!(dv.version, 1);
+    assert_eq!(dv.dm_version, (2, 0));
+    assert!(dv.supports_2160p60 && dv.global_dimming);
+    assert!(close(dv.target_luminance.0, 0.0635, 0.0001), "{}", dv.target_luminance.0);
+    assert_eq!(dv.target_luminance.1, 3950.0);
+    assert!(dv.interface.low_latency);
+    assert_eq!(dv.primaries, Some([(0.6875, 0.3125), (0.296875, 0.625), (0.1484375, 0.0625)]));
+}
+
+#[test]
+fn dolby_vision_unknown() {
+    let block = ExtensionBlock(cta_extension(0, &[(7, &[0x01, 0x46, 0xd0, 0x00, 0x60, 0x00, 0x00, 0x00, 0x00])]));
+    assert!(matches!(block.dolby_vision(), Some(DolbyVision::Unknown { version: 3, .. })));
+    assert!(ExtensionBlock(cta_extension(0, &[(7, TV_HDR)])).dolby_vision().is_none());
+}
+
+#[test]
+fn hdr10_plus() {
+    // Application version 1, as on LG and Samsung TVs.
+    let block = ExtensionBlock(cta_extension(0, &[(7, TV_HDR), (7, &[0x01, 0x8b, 0x84, 0x90, 0x01])]));
+    assert_eq!(block.hdr10_plus(), Some(Hdr10PlusCapability { application_version: 1, data: vec![0x01] }));
+
+    // Later fields are kept as they are.
+    let block = ExtensionBlock(cta_extension(0, &[(7, &[0x01, 0x8b, 0x84, 0x90, 0x4a, 0x12])]));
+    assert_eq!(block.hdr10_plus(), Some(Hdr10PlusCapability { application_version: 2, data: vec![0x4a, 0x12] }));
+
+    // Too short, and the Dolby Vision OUI.
+    assert_eq!(ExtensionBlock(cta_extension(0, &[(7, &[0x01, 0x8b, 0x84, 0x90])])).hdr10_plus(), None);
+    assert_eq!(ExtensionBlock(cta_extension(0, &[(7, &[0x01, 0x46, 0xd0, 0x00, 0x01])])).hdr10_plus(), None);
+}
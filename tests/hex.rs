@@ -0,0 +1,153 @@
+mod common;
+
+use common::{SAMPLE, to_hex};
+use edid_rs::{Error, decode_base64, decode_hex, parse_base64};
+
+// Format `bytes` as `xxd` would, with address and ASCII columns.
+fn to_xxd(bytes: &[u8]) -> String {
+    let mut text = String::new();
+    for (i, line) in bytes.chunks(16).enumerate() {
+        let hex: Vec<String> = line.chunks(2)
+            .map(|pair| pair.iter().map(|b| format!("{:02x}", b)).collect())
+            .collect();
+        let ascii: String = line.iter()
+            .map(|&b| if (0x20..0x7f).contains(&b) { b as char } else { '.' })
+            .collect();
+        text += &format!("{:08x}: {:<40} {}\n", i * 16, hex.join(" "), ascii);
+    }
+    text
+}
+
+// Format `bytes` as an `i2cdump` table.
+fn to_i2cdump(bytes: &[u8]) -> String {
+    let mut text = String::from("     0  1  2  3  4  5  6  7  8  9  a  b  c  d  e  f    0123456789abcdef\n");
+    for (i, line) in bytes.chunks(16).enumerate() {
+        let hex: Vec<String> = line.iter().map(|b| format!("{:02x}", b)).collect();
+        let ascii: String = line.iter()
+            .map(|&b| if (0x20..0x7f).contains(&b) { b as char } else { '.' })
+            .collect();
+        text += &format!("{:02x}: {:<47}    {}\n", i * 16, hex.join(" "), ascii);
+    }
+    text
+}
+
+// Encode `bytes` as padded base64, as `base64` would without wrapping.
+fn to_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut text = String::new();
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+// The top of `edid-decode` output, with the decoded text after the dashes.
+fn to_edid_decode(bytes: &[u8]) -> String {
+    let mut text = String::from("edid-decode (hex):\n\n");
+    for line in bytes.chunks(16) {
+        let hex: Vec<String> = line.iter().map(|b| format!("{:02x}", b)).collect();
+        text += &hex.join(" ");
+        text += "\n";
+    }
+    text + "\n----------------\n\nBlock 0, Base EDID:\n  EDID Structure Version & Revision: 1.4\n"
+}
+
+#[test]
+fn xxd() {
+    assert_eq!(decode_hex(&to_xxd(&SAMPLE)).unwrap(), &SAMPLE[..]);
+}
+
+#[test]
+fn xxd_ascii_colon() {
+    // The ASCII column of the first line ends with `:`.
+    let mut bytes = SAMPLE;
+    bytes[15] = b':';
+    let text = to_xxd(&bytes);
+    assert!(text.lines().next().unwrap().ends_with(':'));
+    assert_eq!(decode_hex(&text).unwrap(), &bytes[..]);
+}
+
+#[test]
+fn xxd_plain() {
+    assert_eq!(decode_hex(&to_hex(&SAMPLE)).unwrap(), &SAMPLE[..]);
+    assert_eq!(decode_hex(&to_hex(&SAMPLE).to_uppercase()).unwrap(), &SAMPLE[..]);
+}
+
+#[test]
+fn i2cdump() {
+    assert_eq!(decode_hex(&to_i2cdump(&SAMPLE)).unwrap(), &SAMPLE[..]);
+}
+
+#[test]
+fn edid_decode() {
+    assert_eq!(decode_hex(&to_edid_decode(&SAMPLE)).unwrap(), &SAMPLE[..]);
+}
+
+#[test]
+fn partial_line() {
+    // Each format ends with a line shorter than the others.
+    let bytes = &SAMPLE[..123];
+    assert_eq!(decode_hex(&to_xxd(bytes)).unwrap(), bytes);
+    assert_eq!(decode_hex(&to_hex(bytes)).unwrap(), bytes);
+    assert_eq!(decode_hex(&to_i2cdump(bytes)).unwrap(), bytes);
+    assert_eq!(decode_hex(&to_edid_decode(bytes)).unwrap(), bytes);
+}
+
+#[test]
+fn comments_and_prefixes() {
+    let text = "# EDID of a laptop panel\n0x00, 0xFF, 0xff\n\n0Xff";
+    assert_eq!(decode_hex(text).unwrap(), [0x00, 0xff, 0xff, 0xff]);
+}
+
+#[test]
+fn invalid_text() {
+    assert_eq!(decode_hex("00 ff f"), Err(Error::InvalidText { offset: 7 }));
+    assert_eq!(decode_hex("00 fg"), Err(Error::InvalidText { offset: 4 }));
+}
+
+#[test]
+fn base64_wrapped() {
+    // As in an `ioreg -a` plist, indented and wrapped.
+    let text: String = to_base64(&SAMPLE).as_bytes().chunks(68)
+        .map(|line| format!("\t\t\t{}\n", std::str::from_utf8(line).unwrap()))
+        .collect();
+    assert_eq!(decode_base64(&text).unwrap(), &SAMPLE[..]);
+    assert_eq!(parse_base64(&text).unwrap().product.product_code, 40994);
+}
+
+#[test]
+fn base64_padding() {
+    // 128 bytes leave two bytes over, so one `=` of padding.
+    let text = to_base64(&SAMPLE);
+    assert!(text.ends_with('=') && !text.ends_with("=="));
+    assert_eq!(decode_base64(text.trim_end_matches('=')).unwrap(), &SAMPLE[..]);
+
+    assert_eq!(decode_base64("TWE=").unwrap(), b"Ma");
+    assert_eq!(decode_base64("TWE").unwrap(), b"Ma");
+    assert_eq!(decode_base64("TQ==").unwrap(), b"M");
+    assert_eq!(decode_base64("TQ=").unwrap(), b"M");
+    assert_eq!(decode_base64("TQ").unwrap(), b"M");
+    assert_eq!(decode_base64("").unwrap(), b"");
+}
+
+#[test]
+fn base64_url_safe() {
+    assert_eq!(decode_base64("-_-_").unwrap(), decode_base64("+/+/").unwrap());
+}
+
+#[test]
+fn base64_invalid() {
+    assert_eq!(decode_base64("TW*h"), Err(Error::InvalidText { offset: 2 }));
+    // Data after padding.
+    assert_eq!(decode_base64("TQ==TWFu"), Err(Error::InvalidText { offset: 4 }));
+    // A single character is only six bits.
+    assert_eq!(decode_base64("TWFuT"), Err(Error::InvalidText { offset: 5 }));
+    assert!(parse_base64(&to_base64(&SAMPLE[..96])).is_err());
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "linux-sysfs")]
+
+mod common;
+
+use common::SAMPLE;
+use edid_rs::linux::{ConnectorStatus, enumerate_in};
+use std::fs;
+use std::path::Path;
+
+// Write a file, making its directory first.
+fn write(root: &Path, path: &str, contents: &[u8]) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+#[test]
+fn captured_tree() {
+    let root = std::env::temp_dir().join(format!("edid-rs-sysfs-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+
+    // The card itself has no edid file.
+    write(&root, "card0/dev", b"226:0\n");
+    write(&root, "card0-eDP-1/status", b"connected\n");
+    write(&root, "card0-eDP-1/edid", &SAMPLE);
+    write(&root, "card0-HDMI-A-1/status", b"disconnected\n");
+    write(&root, "card0-HDMI-A-1/edid", b"");
+    write(&root, "version", b"drm 1.1.0 20060810\n");
+
+    let connectors = enumerate_in(&root);
+    fs::remove_dir_all(&root).unwrap();
+    let connectors = connectors.unwrap();
+
+    assert_eq!(connectors.len(), 2);
+
+    let hdmi = &connectors[0];
+    assert_eq!(hdmi.connector, "card0-HDMI-A-1");
+    assert_eq!(hdmi.status, ConnectorStatus::Disconnected);
+    assert!(hdmi.raw.is_empty());
+    assert!(hdmi.edid.is_none());
+
+    let edp = &connectors[1];
+    assert_eq!(edp.connector, "card0-eDP-1");
+    assert_eq!(edp.status, ConnectorStatus::Connected);
+    assert_eq!(edp.raw, &SAMPLE[..]);
+    assert_eq!(edp.edid.as_ref().unwrap().as_ref().unwrap().product.product_code, 40994);
+}
+
+#[test]
+fn missing_root() {
+    let root = std::env::temp_dir().join(format!("edid-rs-sysfs-missing-{}", std::process::id()));
+    assert!(enumerate_in(root).is_err());
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::{SAMPLE, fix_checksum};
+use edid_rs::{EDID, Error, ManufactureDate, Reader};
+
+fn with_week(week: u8) -> [u8; 128] {
+    let mut bytes = SAMPLE;
+    bytes[16] = week;
+    fix_checksum(&mut bytes);
+    bytes
+}
+
+fn strict(bytes: &[u8]) -> Result<EDID, Error> {
+    EDID::parse(&mut Reader::new(&mut &bytes[..]))
+}
+
+fn lenient(bytes: &[u8]) -> (EDID, Vec<edid_rs::Warning>) {
+    EDID::parse_lenient(&mut Reader::new(&mut &bytes[..])).unwrap()
+}
+
+#[test]
+fn last_week() {
+    let bytes = with_week(54);
+    assert_eq!(strict(&bytes).unwrap().product.manufacture_date, ManufactureDate::WeekOfYear { week: 54, year: 2013 });
+    assert!(lenient(&bytes).1.is_empty());
+}
+
+#[test]
+fn invalid_weeks() {
+    for &week in &[55, 0xfe] {
+        let bytes = with_week(week);
+        assert_eq!(strict(&bytes).err(), Some(Error::InvalidWeek { offset: 16, week }));
+
+        // The raw byte is kept so the rest can still be looked at.
+        let (edid, warnings) = lenient(&bytes);
+        assert_eq!(edid.product.manufacture_date, ManufactureDate::WeekOfYear { week, year: 2013 });
+        assert_eq!(edid.product.manufacture_date.raw(), (week, 23));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].field, "manufacture week");
+        assert_eq!(warnings[0].offset, 16);
+        assert_eq!(warnings[0].error, Error::InvalidWeek { offset: 16, week });
+    }
+}
+
+#[test]
+fn model_year() {
+    let bytes = with_week(0xff);
+    assert_eq!(strict(&bytes).unwrap().product.manufacture_date, ManufactureDate::ModelYear(2013));
+    assert!(lenient(&bytes).1.is_empty());
+}
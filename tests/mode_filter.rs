@@ -0,0 +1,73 @@
+mod common;
+
+use common::{SAMPLE, fix_checksum};
+use edid_rs::{DetailedTiming, EDID, ModeFilter, RbVersion, Reader, cvt_reduced_timing};
+
+fn parse(bytes: &[u8]) -> EDID {
+    EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap()
+}
+
+// The sample with two more detailed timings in place of the dummy descriptors.
+fn with_timings(extra: &[DetailedTiming; 2]) -> EDID {
+    let mut bytes = SAMPLE;
+    for (slot, timing) in [90, 108].iter().zip(extra) {
+        bytes[*slot..*slot + 18].copy_from_slice(&edid_rs::patch::encode_detailed_timing(timing).unwrap());
+    }
+    fix_checksum(&mut bytes);
+    parse(&bytes)
+}
+
+fn actives(modes: &[&DetailedTiming]) -> Vec<(u16, u16)> {
+    modes.iter().map(|timing| timing.active).collect()
+}
+
+#[test]
+fn conditions() {
+    let edid = with_timings(&[
+        cvt_reduced_timing((1920, 1080), 144.0, RbVersion::V2, false).unwrap(),
+        DetailedTiming { interlaced: true, ..cvt_reduced_timing((1920, 1080), 59.94, RbVersion::V2, false).unwrap() }
+    ]);
+    // 2880x1800 at 60Hz, then 1920x1080 at 144Hz and interlaced at 59.94Hz.
+    assert_eq!(edid.filter_modes(&ModeFilter::new()).len(), 3);
+
+    assert_eq!(actives(&edid.filter_modes(&ModeFilter::new().max_pixel_clock_mhz(300))), [(1920, 1080)]);
+    assert_eq!(actives(&edid.filter_modes(&ModeFilter::new().min_refresh(100))), [(1920, 1080)]);
+    // 59.94Hz rounds up to 60Hz.
+    assert_eq!(edid.filter_modes(&ModeFilter::new().max_refresh(60)).len(), 2);
+    assert_eq!(actives(&edid.filter_modes(&ModeFilter::new().progressive_only())), [(2880, 1800), (1920, 1080)]);
+    assert_eq!(actives(&edid.filter_modes(&ModeFilter::new().aspect_ratio(16.0 / 10.0, 0.01))), [(2880, 1800)]);
+    assert!(edid.filter_modes(&ModeFilter::new().min_refresh(100).progressive_only())[0].refresh_rate() > 143.0);
+    assert!(edid.filter_modes(&ModeFilter::new().min_refresh(100).max_refresh(120)).is_empty());
+}
+
+#[cfg(feature = "descriptors")]
+#[test]
+fn range_limits() {
+    // Range limits of 50 to 75Hz in place of the second dummy descriptor.
+    let mut bytes = SAMPLE;
+    bytes[90..108].copy_from_slice(&edid_rs::patch::encode_detailed_timing(&cvt_reduced_timing((1920, 1080), 144.0, RbVersion::V2, false).unwrap()).unwrap());
+    bytes[108..126].copy_from_slice(&[0, 0, 0, 0xfd, 0, 50, 75, 30, 160, 60, 0x01, 0x0a, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20]);
+    fix_checksum(&mut bytes);
+    let edid = parse(&bytes);
+
+    assert_eq!(edid.filter_modes(&ModeFilter::new()).len(), 2);
+    assert_eq!(actives(&edid.filter_modes(&ModeFilter::new().within_range_limits(true))), [(2880, 1800)]);
+    // Without range limits this does nothing.
+    assert_eq!(parse(&SAMPLE).filter_modes(&ModeFilter::new().within_range_limits(true)).len(), 1);
+}
+
+#[test]
+fn standard_timings() {
+    // 1920x1080 at 60Hz as a standard timing only, which `filter_modes` skips.
+    let mut bytes = SAMPLE;
+    bytes[38..40].copy_from_slice(&[0xd1, 0xc0]);
+    fix_checksum(&mut bytes);
+    let edid = parse(&bytes);
+
+    let filter = ModeFilter::new().max_pixel_clock_mhz(200);
+    assert!(edid.filter_modes(&filter).is_empty());
+
+    let standard = edid.timings.standard_timings[0].to_detailed_timing();
+    assert_eq!(standard.active, (1920, 1080));
+    assert!(filter.matches(&standard, None));
+}
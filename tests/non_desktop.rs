@@ -0,0 +1,60 @@
+#![cfg(feature = "cta")]
+
+mod common;
+
+use common::{SAMPLE, cta_extension, fix_checksum};
+use edid_rs::{EDID, ExtensionBlock, Reader};
+
+fn parse(bytes: &[u8]) -> EDID {
+    EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap()
+}
+
+// A Microsoft vendor specific data block of `version`, with the desktop
+// usage bit and the virtual reality use case.
+fn microsoft_vsdb(version: u8, desktop_usage: bool) -> ExtensionBlock {
+    let mut payload = [0u8; 21];
+    payload[..5].copy_from_slice(&[0x5c, 0x12, 0xca, version, (desktop_usage as u8) << 6 | 0x07]);
+    payload[5..].copy_from_slice(&[0xab; 16]);
+    ExtensionBlock(cta_extension(0, &[(3, &payload)]))
+}
+
+// The sample as a Valve Index.
+fn valve_index() -> EDID {
+    let mut bytes = SAMPLE;
+    bytes[8..12].copy_from_slice(&[0x59, 0x96, 0xa8, 0x91]);
+    fix_checksum(&mut bytes);
+    parse(&bytes)
+}
+
+#[test]
+fn vsdb() {
+    let edid = parse(&SAMPLE);
+    assert!(!edid.non_desktop(&[]));
+
+    let vsdb = microsoft_vsdb(3, false).microsoft_vsdb().unwrap();
+    assert_eq!((vsdb.version, vsdb.desktop_usage, vsdb.primary_use_case), (3, false, 0x07));
+    assert_eq!(vsdb.container_id, [0xab; 16]);
+
+    // Versions 1 and 2 are always head mounted displays.
+    assert!(edid.non_desktop(&[microsoft_vsdb(1, false)]));
+    assert!(edid.non_desktop(&[microsoft_vsdb(2, true)]));
+    assert!(edid.non_desktop(&[microsoft_vsdb(3, false)]));
+    assert!(!edid.non_desktop(&[microsoft_vsdb(3, true)]));
+}
+
+#[test]
+fn vsdb_length() {
+    // The kernel ignores blocks of any other length.
+    let block = ExtensionBlock(cta_extension(0, &[(3, &[0x5c, 0x12, 0xca, 0x01, 0x07])]));
+    assert!(block.microsoft_vsdb().is_none());
+    assert!(!parse(&SAMPLE).non_desktop(&[block]));
+}
+
+#[test]
+fn quirk() {
+    let edid = valve_index();
+    assert_eq!(edid.product.product_code, 0x91a8);
+    assert!(edid.non_desktop(&[]));
+    // A data block allowing desktop use does not override the list.
+    assert!(edid.non_desktop(&[microsoft_vsdb(3, true)]));
+}
@@ -0,0 +1,34 @@
+mod common;
+
+use common::{SAMPLE, fix_checksum};
+use edid_rs::{EDID, Reader, panel_id, panel_id_name};
+
+// Panels from the kernel's panel-edp quirk table, with the
+// name the kernel prints for each.
+const PANELS: &[([u8; 4], u32, &str)] = &[
+    ([0x06, 0xaf, 0x5c, 0x40], 0x06af405c, "AUO 0x405c"),
+    ([0x09, 0xe5, 0x5d, 0x0a], 0x09e50a5d, "BOE 0x0a5d"),
+    ([0x4d, 0x10, 0x4c, 0x15], 0x4d10154c, "SHP 0x154c"),
+    ([0x26, 0xcf, 0x7d, 0x05], 0x26cf057d, "IVO 0x057d")
+];
+
+#[test]
+fn kernel_panels() {
+    for &(bytes, id, name) in PANELS {
+        let mut block = SAMPLE;
+        block[8..12].copy_from_slice(&bytes);
+        fix_checksum(&mut block);
+
+        assert_eq!(panel_id(&block), id);
+        assert_eq!(panel_id_name(id), name);
+
+        let edid = EDID::parse(&mut Reader::new(&mut &block[..])).unwrap();
+        assert_eq!(edid.panel_id(), id);
+    }
+}
+
+#[test]
+fn sample() {
+    assert_eq!(panel_id(&SAMPLE), 0x0610a022);
+    assert_eq!(panel_id_name(panel_id(&SAMPLE)), "APP 0xa022");
+}
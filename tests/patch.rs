@@ -0,0 +1,115 @@
+mod common;
+
+use common::SAMPLE;
+use edid_rs::patch::{SlotPolicy, encode_detailed_timing, insert_detailed_timing};
+use edid_rs::{DetailedTiming, EDID, Error, Reader, StereoType, SyncLine, SyncPolarity, SyncType};
+
+fn parse(bytes: &[u8]) -> EDID {
+    EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap()
+}
+
+// Put `dtd` in place of the sample's preferred timing and parse it back.
+fn round_trip(dtd: &DetailedTiming) -> DetailedTiming {
+    let mut bytes = SAMPLE;
+    bytes[54..72].copy_from_slice(&encode_detailed_timing(dtd).unwrap());
+    common::fix_checksum(&mut bytes);
+    parse(&bytes).timings.detailed_timings[0].clone()
+}
+
+#[test]
+fn round_trip_sample() {
+    let dtd = parse(&SAMPLE).timings.detailed_timings[0].clone();
+    assert_eq!(encode_detailed_timing(&dtd).unwrap()[..], SAMPLE[54..72]);
+}
+
+#[test]
+fn round_trip_flags() {
+    let sample = parse(&SAMPLE).timings.detailed_timings[0].clone();
+
+    let stereo = [
+        StereoType::None,
+        StereoType::SequentialRightSync,
+        StereoType::SequentialLeftSync,
+        StereoType::InterleavedLinesRightEven,
+        StereoType::InterleavedLinesLeftEven,
+        StereoType::Interleaved4Way,
+        StereoType::SideBySide
+    ];
+    let polarities = [SyncPolarity::Positive, SyncPolarity::Negative];
+    let mut sync = Vec::new();
+    for &serrated in &[false, true] {
+        sync.push(SyncType::Composite { serrated, line: SyncLine::RGB });
+        sync.push(SyncType::Composite { serrated, line: SyncLine::Green });
+        for &polarity in &polarities {
+            sync.push(SyncType::Composite { serrated, line: SyncLine::Digital(polarity) });
+        }
+    }
+    for &horizontal in &polarities {
+        for &vertical in &polarities {
+            sync.push(SyncType::Seperate { horizontal, vertical });
+        }
+    }
+
+    for &stereo in &stereo {
+        for &sync_type in &sync {
+            for &interlaced in &[false, true] {
+                let dtd = DetailedTiming { stereo, sync_type, interlaced, ..sample.clone() };
+                assert_eq!(format!("{:?}", round_trip(&dtd)), format!("{:?}", dtd));
+            }
+        }
+    }
+}
+
+#[test]
+fn round_trip_fields() {
+    let dtd = DetailedTiming {
+        pixel_clock: 594000000,
+        active: (3840, 2160),
+        front_porch: (176, 8),
+        sync_length: (88, 10),
+        back_porch: (296, 72),
+        image_size: None,
+        border: (2, 1),
+        ..parse(&SAMPLE).timings.detailed_timings[0].clone()
+    };
+    assert_eq!(format!("{:?}", round_trip(&dtd)), format!("{:?}", dtd));
+}
+
+#[test]
+fn unused_only() {
+    let dtd = parse(&SAMPLE).timings.detailed_timings[0].clone();
+
+    // The sample has the name and then two dummy descriptors.
+    let mut bytes = SAMPLE;
+    assert_eq!(insert_detailed_timing(&mut bytes, &dtd, SlotPolicy::UnusedOnly), Ok(90));
+    assert_eq!(insert_detailed_timing(&mut bytes, &dtd, SlotPolicy::UnusedOnly), Ok(108));
+    assert_eq!(parse(&bytes).timings.detailed_timings.len(), 3);
+
+    let full = bytes;
+    assert_eq!(insert_detailed_timing(&mut bytes, &dtd, SlotPolicy::UnusedOnly), Err(Error::NoSpace));
+    // The name is never replaced either.
+    assert_eq!(insert_detailed_timing(&mut bytes, &dtd, SlotPolicy::ReplaceLeastImportant), Err(Error::NoSpace));
+    assert_eq!(bytes[..], full[..]);
+}
+
+#[test]
+fn replace_least_important() {
+    let dtd = parse(&SAMPLE).timings.detailed_timings[0].clone();
+
+    // Text at 90 and a manufacturer descriptor at 108.
+    let mut bytes = SAMPLE;
+    bytes[93] = 0xfe;
+    bytes[111] = 0x01;
+    common::fix_checksum(&mut bytes);
+
+    assert_eq!(insert_detailed_timing(&mut bytes, &dtd, SlotPolicy::UnusedOnly), Err(Error::NoSpace));
+    assert_eq!(insert_detailed_timing(&mut bytes, &dtd, SlotPolicy::ReplaceLeastImportant), Ok(108));
+    assert_eq!(bytes[93], 0xfe);
+    assert!(EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap().checksum_ok);
+}
+
+#[test]
+fn zero_pixel_clock() {
+    let dtd = DetailedTiming { pixel_clock: 9999, ..parse(&SAMPLE).timings.detailed_timings[0].clone() };
+    assert!(encode_detailed_timing(&dtd).is_err());
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "descriptors")]
+
+mod common;
+
+use common::{SAMPLE, fix_checksum};
+use edid_rs::{DetailedTiming, EDID, MonitorDescriptor, RangeMargin, RbVersion, Reader, SecondaryTiming, cvt_reduced_timing, fallback_modes};
+
+fn limits(descriptor: &MonitorDescriptor) -> ((u16, u16), (u32, u32), u32) {
+    match *descriptor {
+        MonitorDescriptor::RangeLimits { vertical_rate, horizontal_rate, pixel_clock, secondary_timing: SecondaryTiming::None } => {
+            (vertical_rate, horizontal_rate, pixel_clock)
+        },
+        _ => panic!("not range limits without a secondary timing")
+    }
+}
+
+// The sample's 2880x1800 panel timing and the kernel's fallback modes.
+fn modes() -> Vec<DetailedTiming> {
+    let edid = EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap();
+    let mut modes = edid.timings.detailed_timings;
+    modes.extend(fallback_modes());
+    modes
+}
+
+#[test]
+fn tight() {
+    let modes = modes();
+    let derived = MonitorDescriptor::range_limits_from(&modes, &RangeMargin::default()).unwrap();
+    // 640x480 at 31.5kHz to the sample at 111.1kHz and 337.75MHz.
+    assert_eq!(limits(&derived), ((56, 60), (31000, 111000), 340000000));
+    assert!(modes.iter().all(|mode| mode.within_range_limits(&derived)));
+    assert!(derived.is_consistent());
+    assert_eq!(derived.range_limits_offsets(), Some(0));
+}
+
+#[test]
+fn margin() {
+    let modes = modes();
+    let margin = RangeMargin { vertical_rate: 10, horizontal_rate: 500, pixel_clock: 2250000 };
+    let derived = MonitorDescriptor::range_limits_from(&modes, &margin).unwrap();
+    assert_eq!(limits(&derived), ((46, 70), (30000, 112000), 340000000));
+    assert!(MonitorDescriptor::range_limits_from(&[], &margin).is_none());
+}
+
+// Write range limits into the sample's first dummy descriptor, as a 1.4 EDID
+// gives them, and read them back.
+fn round_trip(descriptor: &MonitorDescriptor) -> MonitorDescriptor {
+    let ((min_v, max_v), (min_h, max_h), clock) = limits(descriptor);
+    let flags = descriptor.range_limits_offsets().unwrap();
+    let offset = |flags: u8, rate: u32, max: bool| match flags & 0b11 {
+        0b11 => rate - 255,
+        0b10 if max => rate - 255,
+        _ => rate
+    };
+
+    let mut bytes = SAMPLE;
+    bytes[90..108].copy_from_slice(&[
+        0, 0, 0, 0xfd, flags,
+        offset(flags, min_v as u32, false) as u8, offset(flags, max_v as u32, true) as u8,
+        offset(flags >> 2, min_h / 1000, false) as u8, offset(flags >> 2, max_h / 1000, true) as u8,
+        (clock / 10000000) as u8, 0x00, 0x0a, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20
+    ]);
+    fix_checksum(&mut bytes);
+    let edid = EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap();
+    edid.range_limits().unwrap().clone()
+}
+
+#[test]
+fn high_refresh() {
+    // 1080p at 360Hz runs at 932MHz and 466kHz, so only the maxima need the offset.
+    let fast = cvt_reduced_timing((1920, 1080), 360.0, RbVersion::V2, false).unwrap();
+    let mut modes = modes();
+    modes.push(fast.clone());
+    let derived = MonitorDescriptor::range_limits_from(&modes, &RangeMargin::default()).unwrap();
+    assert_eq!(limits(&derived), ((56, 360), (31000, 466000), 940000000));
+    assert_eq!(derived.range_limits_offsets(), Some(0b1010));
+    assert!(modes.iter().all(|mode| mode.within_range_limits(&derived)));
+    assert_eq!(limits(&round_trip(&derived)), limits(&derived));
+
+    // On its own both limits are over 255.
+    let derived = MonitorDescriptor::range_limits_from(std::slice::from_ref(&fast), &RangeMargin::default()).unwrap();
+    assert_eq!(derived.range_limits_offsets(), Some(0b1111));
+    assert!(fast.within_range_limits(&derived));
+    assert_eq!(limits(&round_trip(&derived)), limits(&derived));
+}
+
+#[test]
+fn capped() {
+    // Huge margins are capped at what the descriptor can hold.
+    let margin = RangeMargin { vertical_rate: 255, horizontal_rate: u32::MAX, pixel_clock: u32::MAX };
+    let derived = MonitorDescriptor::range_limits_from(&modes(), &margin).unwrap();
+    assert_eq!(limits(&derived), ((0, 315), (0, 510000), 2550000000));
+    assert_eq!(derived.range_limits_offsets(), Some(0b1010));
+
+    let derived = MonitorDescriptor::range_limits_from(&[cvt_reduced_timing((640, 480), 480.0, RbVersion::V2, false).unwrap()], &margin).unwrap();
+    assert_eq!(limits(&derived).0, (225, 510));
+
+    assert_eq!(MonitorDescriptor::MonitorName("Color LCD".into()).range_limits_offsets(), None);
+}
@@ -0,0 +1,109 @@
+// A user type can implement `edid_rs::Read` in every configuration,
+// which the old blanket impl over `std::io::Read` prevented.
+
+mod common;
+
+use common::SAMPLE;
+
+// Hands out the data a few bytes at a time.
+struct Trickle {
+    data: &'static [u8],
+    reads: usize
+}
+
+impl edid_rs::Read for Trickle {
+    fn read(&mut self, buf: &mut [u8]) -> Option<usize> {
+        self.reads += 1;
+        let num = buf.len().min(self.data.len()).min(5);
+        buf[..num].copy_from_slice(&self.data[..num]);
+        self.data = &self.data[num..];
+        Some(num)
+    }
+}
+
+// A type with both traits, where they do different things.
+#[cfg(feature = "std")]
+struct Both(&'static [u8]);
+
+#[cfg(feature = "std")]
+impl std::io::Read for Both {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::other("not this one"))
+    }
+}
+
+#[cfg(feature = "std")]
+impl edid_rs::Read for Both {
+    fn read(&mut self, buf: &mut [u8]) -> Option<usize> {
+        edid_rs::Read::read(&mut self.0, buf)
+    }
+}
+
+static BYTES: [u8; 128] = SAMPLE;
+
+#[test]
+fn user_reader() {
+    let mut trickle = Trickle { data: &BYTES, reads: 0 };
+    let edid = edid_rs::parse(&mut trickle).unwrap();
+    assert_eq!(edid.product.product_code, 40994);
+    assert!(trickle.reads > 1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn user_reader_with_both() {
+    assert!(edid_rs::parse(&mut Both(&BYTES)).is_ok());
+    assert!(edid_rs::parse(&mut edid_rs::IoReader(Both(&BYTES))).is_err());
+}
+
+// Like a pipe or `/dev/i2c-*`: a few bytes per read, every other read
+// interrupted, and then an error once `fail_at` bytes have been read.
+#[cfg(feature = "std")]
+struct Flaky {
+    data: &'static [u8],
+    fail_at: usize,
+    read: usize,
+    interrupt: bool
+}
+
+#[cfg(feature = "std")]
+impl std::io::Read for Flaky {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(std::io::ErrorKind::Interrupted.into());
+        }
+        if self.read >= self.fail_at {
+            return Err(std::io::Error::other("bus error"));
+        }
+
+        let num = buf.len().min(self.data.len()).min(3).min(self.fail_at - self.read);
+        buf[..num].copy_from_slice(&self.data[..num]);
+        self.data = &self.data[num..];
+        self.read += num;
+        Ok(num)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn short_and_interrupted_reads() {
+    let flaky = |data, fail_at| Flaky { data, fail_at, read: 0, interrupt: false };
+
+    let edid = edid_rs::parse(&mut edid_rs::IoReader(flaky(&BYTES, usize::MAX))).unwrap();
+    assert_eq!(edid.product.product_code, 40994);
+    let edid = edid_rs::parse(&mut std::io::BufReader::with_capacity(7, flaky(&BYTES, usize::MAX))).unwrap();
+    assert_eq!(edid.product.product_code, 40994);
+
+    // The data ends early.
+    assert_eq!(
+        edid_rs::parse(&mut edid_rs::IoReader(flaky(&BYTES[..100], usize::MAX))).err(),
+        Some(edid_rs::Error::UnexpectedEof { offset: 100, needed: 28 })
+    );
+
+    // The source fails part way through.
+    assert_eq!(
+        edid_rs::parse(&mut edid_rs::IoReader(flaky(&BYTES, 64))).err(),
+        Some(edid_rs::Error::ReadFailed { offset: 64 })
+    );
+}
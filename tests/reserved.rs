@@ -0,0 +1,114 @@
+mod common;
+
+use common::{SAMPLE, cta_extension};
+use edid_rs::{ReservedViolation, check_reserved};
+
+const EEODB: &[u8] = &[0x78, 0x03];
+const SVDS: &[u8] = &[0x90, 0x04, 0x05];
+
+fn with_extensions(extensions: &[[u8; 128]]) -> Vec<u8> {
+    let mut bytes = SAMPLE.to_vec();
+    for extension in extensions {
+        bytes.extend_from_slice(extension);
+    }
+    bytes
+}
+
+fn fields(bytes: &[u8]) -> Vec<&'static str> {
+    check_reserved(bytes).into_iter().map(|violation| violation.field).collect()
+}
+
+#[test]
+fn sample() {
+    assert!(check_reserved(&SAMPLE).is_empty());
+    assert!(check_reserved(&SAMPLE[..100]).is_empty());
+}
+
+#[test]
+fn eeodb_first() {
+    let bytes = with_extensions(&[
+        cta_extension(0, &[(7, EEODB), (2, SVDS)]),
+        cta_extension(0, &[(2, SVDS)])
+    ]);
+    assert!(check_reserved(&bytes).is_empty());
+}
+
+#[test]
+fn eeodb_misplaced() {
+    // After another data block.
+    let bytes = with_extensions(&[cta_extension(0, &[(2, SVDS), (7, EEODB)])]);
+    assert_eq!(check_reserved(&bytes), [
+        ReservedViolation { offset: 132, mask: 0xff, expected: 0xe2, found: 0x43, field: "EEODB placement" },
+        ReservedViolation { offset: 136, mask: 0xff, expected: 0x00, found: 0xe2, field: "stray EEODB" }
+    ]);
+
+    // In the second extension.
+    let bytes = with_extensions(&[
+        cta_extension(0, &[(2, SVDS)]),
+        cta_extension(0, &[(7, EEODB), (2, SVDS)])
+    ]);
+    assert_eq!(fields(&bytes), ["EEODB placement", "stray EEODB"]);
+    assert_eq!(check_reserved(&bytes)[1].offset, 260);
+}
+
+#[test]
+fn eeodb_after_vcdb() {
+    // A Video Capability Data Block first has the same header as an EEODB,
+    // so only the stray EEODB itself shows where the problem is.
+    let bytes = with_extensions(&[cta_extension(0, &[(7, &[0x00, 0x0f]), (7, EEODB)])]);
+    assert_eq!(bytes[132], 0xe2);
+    assert_eq!(check_reserved(&bytes), [
+        ReservedViolation { offset: 135, mask: 0xff, expected: 0x00, found: 0xe2, field: "stray EEODB" }
+    ]);
+}
+
+#[test]
+fn dirtied_sample() {
+    let mut bytes = SAMPLE;
+    // A non-zero byte in the monitor name descriptor's header.
+    bytes[74] = 0x01;
+    // Text after the newline which ends the monitor name.
+    bytes[88] = b'x';
+    // A dummy descriptor which is not all zeros.
+    bytes[100] = 0x55;
+    // A colour management descriptor of the wrong version.
+    bytes[108..126].copy_from_slice(&[0, 0, 0, 0xf9, 0, 0x02, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+
+    let mut extension = cta_extension(0, &[(2, SVDS)]);
+    // Padding after the detailed timings of the extension.
+    extension[120] = 0xaa;
+    let bytes = [&bytes[..], &extension[..]].concat();
+
+    let violations: Vec<(usize, &str)> = check_reserved(&bytes).into_iter().map(|v| (v.offset, v.field)).collect();
+    assert_eq!(violations, [
+        (74, "display descriptor header"),
+        (88, "string descriptor padding"),
+        (100, "dummy descriptor"),
+        (113, "colour management descriptor"),
+        (248, "CTA-861 padding")
+    ]);
+}
+
+#[test]
+fn colour_management_data() {
+    // The DCM coefficients after the version may be anything.
+    let mut bytes = SAMPLE;
+    bytes[108..126].copy_from_slice(&[0, 0, 0, 0xf9, 0, 0x03, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+    assert!(check_reserved(&bytes).is_empty());
+}
+
+#[test]
+fn cvt_range_limits() {
+    // CVT support with reduced blanking only, in place of the first dummy descriptor.
+    let mut bytes = SAMPLE;
+    bytes[90..108].copy_from_slice(&[0, 0, 0, 0xfd, 0, 50, 75, 30, 80, 17, 0x04, 0x11, 0, 0, 0xf0, 0x30, 0, 60]);
+    assert!(check_reserved(&bytes).is_empty());
+
+    // Bits 2-0 of the aspect ratio and preference bytes are reserved.
+    bytes[104] |= 0b101;
+    bytes[105] |= 0b010;
+    assert_eq!(check_reserved(&bytes), [
+        ReservedViolation { offset: 104, mask: 0b111, expected: 0, found: 0b101, field: "range limits padding" },
+        ReservedViolation { offset: 105, mask: 0b111, expected: 0, found: 0b010, field: "range limits padding" }
+    ]);
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use common::{SAMPLE, fix_checksum};
+use edid_rs::{EDID, Reader, ScreenGeometry};
+
+fn geometry(revision: u8, width: u8, height: u8) -> ScreenGeometry {
+    let mut bytes = SAMPLE;
+    bytes[19] = revision;
+    bytes[21] = width;
+    bytes[22] = height;
+    fix_checksum(&mut bytes);
+    EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap().display.max_size
+}
+
+#[test]
+fn size() {
+    let geometry = geometry(4, 33, 21);
+    assert!(matches!(geometry, ScreenGeometry::Size(size) if size.width == 33.0 && size.height == 21.0));
+    assert!((geometry.aspect_ratio().unwrap() - 33.0 / 21.0).abs() < 1e-6);
+}
+
+#[test]
+fn landscape() {
+    // 16:9 is stored as 79 in the width byte.
+    let geometry = geometry(4, 79, 0);
+    assert!(matches!(geometry, ScreenGeometry::AspectRatio(ratio) if (ratio - 1.78).abs() < 1e-6));
+    assert!(geometry.size().is_none());
+}
+
+#[test]
+fn portrait() {
+    // 9:16 is stored as 79 in the height byte.
+    let geometry = geometry(4, 0, 79);
+    assert!(matches!(geometry, ScreenGeometry::AspectRatio(ratio) if (ratio - 100.0 / 178.0).abs() < 1e-6));
+    assert!(geometry.size().is_none());
+}
+
+#[test]
+fn before_1_4() {
+    assert!(matches!(geometry(3, 79, 0), ScreenGeometry::Unknown));
+    assert!(matches!(geometry(3, 0, 79), ScreenGeometry::Unknown));
+    assert!(matches!(geometry(4, 0, 0), ScreenGeometry::Unknown));
+    assert!(geometry(4, 0, 0).aspect_ratio().is_none());
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::{SAMPLE, fix_checksum};
+use edid_rs::{EDID, Reader, StandardTiming};
+
+// The sample with its standard timing slots set to `slots`.
+fn with_slots(slots: &[u8; 16]) -> EDID {
+    let mut bytes = SAMPLE;
+    bytes[38..54].copy_from_slice(slots);
+    fix_checksum(&mut bytes);
+    EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap()
+}
+
+fn modes(timings: &[StandardTiming]) -> Vec<(u16, u16, u8)> {
+    timings.iter().map(|t| (t.horizontal_resolution, t.vertical_resolution(), t.refresh_rate)).collect()
+}
+
+#[test]
+fn padding_01() {
+    let edid = with_slots(&[0x01; 16]);
+    assert!(edid.timings.standard_timings.is_empty());
+
+    let edid = with_slots(&[0xd1, 0xc0, 0x81, 0x80, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]);
+    assert_eq!(modes(&edid.timings.standard_timings), [(1920, 1080, 60), (1280, 1024, 60)]);
+}
+
+#[test]
+fn padding_00() {
+    let edid = with_slots(&[0x00; 16]);
+    assert!(edid.timings.standard_timings.is_empty());
+
+    let edid = with_slots(&[0xd1, 0xc0, 0x81, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(modes(&edid.timings.standard_timings), [(1920, 1080, 60), (1280, 1024, 60)]);
+}
+
+#[test]
+fn padding_20() {
+    let edid = with_slots(&[0x20; 16]);
+    assert!(edid.timings.standard_timings.is_empty());
+}
+
+#[cfg(feature = "descriptors")]
+#[test]
+fn descriptor_padding() {
+    // A standard timings descriptor in place of the first dummy one, with
+    // two timings and then 0x00 and 0x01 padding.
+    let mut bytes = SAMPLE;
+    bytes[90..108].copy_from_slice(&[0, 0, 0, 0xfa, 0, 0xd1, 0xc0, 0x81, 0x80, 0, 0, 0, 0, 1, 1, 1, 1, 0x0a]);
+    fix_checksum(&mut bytes);
+
+    let edid = EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap();
+    assert_eq!(modes(&edid.timings.standard_timings), [(1920, 1080, 60), (1280, 1024, 60)]);
+}
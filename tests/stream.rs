@@ -0,0 +1,71 @@
+#![cfg(feature = "extensions")]
+
+mod common;
+
+use common::{SAMPLE, fix_checksum};
+use edid_rs::{Error, parse_stream};
+
+// The sample with `count` extension blocks announced.
+fn base(count: u8) -> [u8; 128] {
+    let mut block = SAMPLE;
+    block[126] = count;
+    fix_checksum(&mut block);
+    block
+}
+
+#[test]
+fn ok_err_ok() {
+    let mut bad = base(1);
+    bad[1] = 0;
+
+    let mut bytes = SAMPLE.to_vec();
+    bytes.extend_from_slice(&bad);
+    bytes.extend_from_slice(&[0u8; 128]);
+    bytes.extend_from_slice(&SAMPLE);
+
+    let items: Vec<_> = parse_stream(&bytes[..]).collect();
+    assert_eq!(items.len(), 3);
+
+    assert_eq!(items[0].0, 0);
+    assert_eq!(items[0].1.as_ref().unwrap().1.len(), 0);
+    // The bad EDID's extension is skipped, so the next one is found.
+    assert_eq!(items[1].0, 128);
+    assert_eq!(items[1].1.as_ref().err(), Some(&Error::InvalidHeader { offset: 0 }));
+    assert_eq!(items[2].0, 384);
+    assert_eq!(items[2].1.as_ref().unwrap().0.product.product_code, 40994);
+}
+
+#[test]
+fn partial_record() {
+    let mut bytes = base(1).to_vec();
+    bytes.extend_from_slice(&[0u8; 100]);
+
+    let items: Vec<_> = parse_stream(&bytes[..]).collect();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].0, 0);
+    assert_eq!(items[0].1.as_ref().err(), Some(&Error::UnexpectedEof { offset: 228, needed: 28 }));
+}
+
+#[test]
+fn empty() {
+    assert_eq!(parse_stream(&[][..]).count(), 0);
+}
+
+#[cfg(feature = "cta")]
+#[test]
+fn eeodb_count() {
+    use common::cta_extension;
+
+    // One extension announced in the base block, three in the EEODB.
+    let mut bytes = base(1).to_vec();
+    bytes.extend_from_slice(&cta_extension(0, &[(7, &[0x78, 0x03])]));
+    bytes.extend_from_slice(&cta_extension(0, &[]));
+    bytes.extend_from_slice(&cta_extension(0, &[]));
+    bytes.extend_from_slice(&SAMPLE);
+
+    let items: Vec<_> = parse_stream(&bytes[..]).collect();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].1.as_ref().unwrap().1.len(), 3);
+    assert_eq!(items[1].0, 512);
+    assert!(items[1].1.is_ok());
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "cta")]
+
+mod common;
+
+use common::{SAMPLE, cta_extension};
+use edid_rs::{EDID, ExtensionBlock, Reader, ThreeDStructure};
+use ThreeDStructure::*;
+
+// 1080p60, 720p60 (native), 1080p24, 1080i60 and 720p50.
+const SVDS: &[u8] = &[16, 0x84, 32, 5, 19];
+
+// An HDMI VSDB with latency fields and the video section after them.
+fn hdmi_vsdb(three_d: u8, hdmi_vics: &[u8], fields: &[u8]) -> Vec<u8> {
+    let mut vsdb = vec![0x03, 0x0c, 0x00, 0x10, 0x00, 0x00, 0x00, 0xe0, 0x01, 0x02, 0x03, 0x04];
+    vsdb.push(three_d);
+    vsdb.push((hdmi_vics.len() as u8) << 5 | fields.len() as u8);
+    vsdb.extend_from_slice(hdmi_vics);
+    vsdb.extend_from_slice(fields);
+    vsdb
+}
+
+fn modes(svds: &[u8], vsdb: &[u8]) -> Vec<(u8, ThreeDStructure)> {
+    let edid = EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap();
+    edid.three_d_modes(&[ExtensionBlock(cta_extension(0, &[(2, svds), (3, vsdb)]))])
+}
+
+#[test]
+fn structure_codes() {
+    assert_eq!(ThreeDStructure::from_code(8), Some(SideBySideHalf));
+    assert_eq!(ThreeDStructure::from_code(7), None);
+    for &structure in &ThreeDStructure::ALL {
+        assert_eq!(ThreeDStructure::from_code(structure.code()), Some(structure));
+    }
+}
+
+#[test]
+fn mandatory() {
+    // Only for the formats which are among the SVDs, so not 1080i50.
+    assert_eq!(modes(SVDS, &hdmi_vsdb(0x80, &[], &[])), [
+        (32, FramePacking), (32, TopAndBottom),
+        (4, FramePacking), (4, TopAndBottom),
+        (19, FramePacking), (19, TopAndBottom),
+        (5, SideBySideHalf)
+    ]);
+    assert!(modes(SVDS, &hdmi_vsdb(0x00, &[], &[])).is_empty());
+}
+
+#[test]
+fn structure_all() {
+    // Side-by-side (half) and frame packing for every one of the first 16 SVDs.
+    let svds: Vec<u8> = (1..=17).collect();
+    let modes = modes(&svds, &hdmi_vsdb(0xa0, &[], &[0x01, 0x01]));
+    assert!(modes.contains(&(16, SideBySideHalf)));
+    assert!(modes.contains(&(1, FramePacking)));
+    assert!(!modes.contains(&(17, FramePacking)));
+    assert!(!modes.contains(&(17, SideBySideHalf)));
+    // Along with the mandatory formats.
+    assert!(modes.contains(&(4, TopAndBottom)));
+}
+
+#[test]
+fn mask() {
+    // Side-by-side (half) and frame packing for the first and third SVDs,
+    // 1080p60 and 1080p24, then one entry for 720p60 and 1080i60 each.
+    let modes = modes(SVDS, &hdmi_vsdb(0xc0, &[0x01], &[0x01, 0x01, 0x00, 0x05, 0x18, 0x60, 0x36]));
+    assert_eq!(modes, [
+        (32, FramePacking), (32, TopAndBottom),
+        (4, FramePacking), (4, TopAndBottom),
+        (19, FramePacking), (19, TopAndBottom),
+        (5, SideBySideHalf),
+        (16, FramePacking), (16, SideBySideHalf),
+        (32, SideBySideHalf),
+        (4, SideBySideHalf),
+        (5, TopAndBottom)
+    ]);
+
+    let edid = EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap();
+    let extensions = [ExtensionBlock(cta_extension(0, &[(2, SVDS), (3, &hdmi_vsdb(0xc0, &[0x01], &[0x01, 0x01, 0x00, 0x05]))]))];
+    assert!(edid.supports_3d(&extensions, 16, SideBySideHalf));
+    assert!(!edid.supports_3d(&extensions, 19, SideBySideHalf));
+    assert_eq!(extensions[0].hdmi_vics(), [0x01]);
+}
+
+#[test]
+fn detail() {
+    // Side-by-side (half) for 720p60 has a 3D_Detail byte, which would
+    // otherwise read as frame packing for 1080p24. Then top-and-bottom
+    // for 1080i60 and a reserved structure for 720p50.
+    assert_eq!(modes(SVDS, &hdmi_vsdb(0x00, &[], &[0x18, 0x20, 0x36, 0x47])), [(4, SideBySideHalf), (5, TopAndBottom)]);
+    // An entry past the SVDs is left out.
+    assert!(modes(SVDS, &hdmi_vsdb(0x00, &[], &[0x90])).is_empty());
+}
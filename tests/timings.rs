@@ -0,0 +1,95 @@
+#![cfg(feature = "descriptors")]
+
+mod common;
+
+use common::{SAMPLE, fix_checksum, modeline};
+use edid_rs::{DetailedTiming, EDID, Reader, SecondaryTiming, StandardTiming, SyncPolarity, SyncType, fallback_modes};
+
+// Whether the horizontal and vertical syncs are positive.
+fn polarity(timing: &DetailedTiming) -> (bool, bool) {
+    let positive = |polarity| matches!(polarity, SyncPolarity::Positive);
+    match timing.sync_type {
+        SyncType::Seperate { horizontal, vertical } => (positive(horizontal), positive(vertical)),
+        _ => panic!("not separate sync")
+    }
+}
+
+const SXGA: StandardTiming = StandardTiming { horizontal_resolution: 1280, aspect_ratio: 5.0 / 4.0, refresh_rate: 60 };
+
+// The sample with a range limits descriptor in place of the first dummy
+// one, giving a secondary GTF curve with C = 30, M = 300, K = 128 and
+// J = 20 from `start` kHz.
+fn secondary_gtf(start: u8) -> EDID {
+    let mut bytes = SAMPLE;
+    bytes[90..108].copy_from_slice(&[0, 0, 0, 0xfd, 0, 50, 90, 30, 120, 20, 0x02, 0, start / 2, 60, 0x2c, 0x01, 128, 40]);
+    fix_checksum(&mut bytes);
+    EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap()
+}
+
+#[test]
+fn default_gtf() {
+    let timing = SXGA.to_detailed_timing();
+    assert_eq!(modeline(&timing), (108883200, [1280, 1360, 1496, 1712], [1024, 1025, 1028, 1060]));
+    assert_eq!(polarity(&timing), (false, true));
+
+    let fhd = StandardTiming { horizontal_resolution: 1920, aspect_ratio: 16.0 / 9.0, refresh_rate: 60 };
+    assert_eq!(modeline(&fhd.to_detailed_timing()), (172798080, [1920, 2040, 2248, 2576], [1080, 1081, 1084, 1118]));
+
+    let sxga85 = StandardTiming { refresh_rate: 85, ..SXGA };
+    assert_eq!(modeline(&sxga85.to_detailed_timing()), (159358000, [1280, 1376, 1512, 1744], [1024, 1025, 1028, 1075]));
+}
+
+#[test]
+fn secondary_gtf_curve() {
+    // 1280x1024 at 60Hz has a line rate of 63.6kHz, so is on the secondary curve.
+    let edid = secondary_gtf(60);
+    let limits = edid.range_limits().unwrap();
+    let timing = SXGA.to_detailed_timing_with(limits);
+    assert_eq!(modeline(&timing), (104812800, [1280, 1336, 1464, 1648], [1024, 1025, 1028, 1060]));
+    assert_eq!(polarity(&timing), (true, false));
+
+    // Below the start frequency the default curve is used.
+    let edid = secondary_gtf(70);
+    let timing = SXGA.to_detailed_timing_with(edid.range_limits().unwrap());
+    assert_eq!(modeline(&timing), modeline(&SXGA.to_detailed_timing()));
+    assert_eq!(polarity(&timing), (false, true));
+}
+
+#[test]
+fn secondary_gtf_defaults() {
+    // The default coefficients as a secondary curve give the same mode.
+    let limits = edid_rs::MonitorDescriptor::RangeLimits {
+        vertical_rate: (50, 90),
+        horizontal_rate: (30000, 120000),
+        pixel_clock: 200000000,
+        secondary_timing: SecondaryTiming::GTF { start_horizontal_freq: 0, c: 40.0, m: 600.0, k: 128.0, j: 20.0 }
+    };
+    assert_eq!(modeline(&SXGA.to_detailed_timing_with(&limits)), modeline(&SXGA.to_detailed_timing()));
+
+    // As does a descriptor without a secondary curve.
+    let name = edid_rs::MonitorDescriptor::MonitorName("Color LCD".into());
+    assert_eq!(modeline(&SXGA.to_detailed_timing_with(&name)), modeline(&SXGA.to_detailed_timing()));
+}
+
+#[test]
+fn fallback() {
+    let modes = fallback_modes();
+    let summary: Vec<_> = modes.iter().map(|mode| (mode.active, (mode.refresh_rate() + 0.5) as u32)).collect();
+    assert_eq!(summary, [((640, 480), 60), ((800, 600), 56), ((800, 600), 60), ((848, 480), 60), ((1024, 768), 60)]);
+
+    // The sample has a detailed timing, so it is used as it is.
+    let edid = EDID::parse(&mut Reader::new(&mut &SAMPLE[..])).unwrap();
+    let own = edid.modes_or_fallback();
+    assert_eq!(own.len(), 1);
+    assert_eq!(own[0].active, (2880, 1800));
+
+    // Without it, and without claiming a preferred timing.
+    let mut bytes = SAMPLE;
+    bytes[54..72].copy_from_slice(&[0, 0, 0, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    bytes[24] &= !0x02;
+    fix_checksum(&mut bytes);
+    let edid = EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap();
+    assert!(edid.timings.detailed_timings.is_empty());
+    assert_eq!(modeline(&edid.modes_or_fallback()[0]), modeline(&modes[0]));
+    assert_eq!(edid.modes_or_fallback().len(), 5);
+}
@@ -0,0 +1,29 @@
+mod common;
+
+use common::SAMPLE;
+use edid_rs::{EDID, Error, Reader};
+
+fn parse(bytes: &[u8]) -> Result<EDID, Error> {
+    EDID::parse(&mut Reader::new(&mut &bytes[..]))
+}
+
+#[test]
+fn counts() {
+    for &len in &[0, 7, 64, 127] {
+        assert_eq!(parse(&SAMPLE[..len]).err(), Some(Error::UnexpectedEof { offset: len, needed: 128 - len }), "{} bytes", len);
+    }
+    assert!(parse(&SAMPLE).is_ok());
+}
+
+#[test]
+fn message() {
+    assert_eq!(parse(&SAMPLE[..96]).unwrap_err().to_string(), "Unexpectedly out of data at byte 96, 32 more bytes needed.");
+}
+
+#[test]
+fn partial() {
+    // What was read before the end is kept.
+    let (partial, error) = EDID::parse_partial(&mut Reader::new(&mut &SAMPLE[..64]));
+    assert_eq!(error, Some(Error::UnexpectedEof { offset: 64, needed: 64 }));
+    assert_eq!(partial.product.map(|product| product.product_code), Some(40994));
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "cta")]
+
+mod common;
+
+use common::{SAMPLE, cta_extension};
+use edid_rs::{UnparsedKind, unparsed};
+
+#[test]
+fn sample() {
+    assert!(unparsed(&SAMPLE).is_empty());
+}
+
+#[test]
+fn cvt_codes() {
+    // A CVT 3-byte code descriptor in place of a dummy one is decoded.
+    let mut base = SAMPLE;
+    base[90..108].copy_from_slice(&[0, 0, 0, 0xf8, 0, 0x01, 0x7f, 0x1c, 0x38, 0x00, 0x00, 0, 0, 0, 0, 0, 0, 0]);
+    assert!(unparsed(&base).is_empty());
+
+    // Colour management data is not.
+    base[93] = 0xf9;
+    let kinds: Vec<UnparsedKind> = unparsed(&base).into_iter().map(|region| region.kind).collect();
+    assert_eq!(kinds, [UnparsedKind::DescriptorTag(0xf9)]);
+}
+
+#[test]
+fn one_of_each() {
+    let mut base = SAMPLE;
+    // A display descriptor with an undefined tag in place of a dummy one.
+    base[93] = 0x20;
+
+    let cta = cta_extension(0, &[
+        // The HDMI vendor block, which is known.
+        (3, &[0x03, 0x0c, 0x00, 0x10, 0x00]),
+        // A vendor block with an unknown OUI.
+        (3, &[0x44, 0x33, 0x22, 0x01]),
+        // Dolby Vision, which is known.
+        (7, &[0x01, 0x46, 0xd0, 0x00, 0x00]),
+        // A vendor video block with an unknown OUI.
+        (7, &[0x01, 0x66, 0x55, 0x44, 0x00]),
+        // A vendor video block too short to have an OUI.
+        (7, &[0x01, 0x46]),
+        // The HDR static metadata block, which is known.
+        (7, &[0x06, 0x05, 0x01]),
+        // An extended tag which is not decoded.
+        (7, &[0x0d, 0x01])
+    ]);
+    // A DisplayID extension.
+    let mut displayid = [0u8; 128];
+    displayid[0] = 0x70;
+
+    let bytes = [&base[..], &cta[..], &displayid[..]].concat();
+    let kinds: Vec<(usize, UnparsedKind)> = unparsed(&bytes).into_iter().map(|region| (region.block, region.kind)).collect();
+    assert_eq!(kinds, [
+        (0, UnparsedKind::DescriptorTag(0x20)),
+        (1, UnparsedKind::VendorOui(0x223344)),
+        (1, UnparsedKind::VendorOui(0x445566)),
+        (1, UnparsedKind::ExtendedTag(0x01)),
+        (1, UnparsedKind::ExtendedTag(0x0d)),
+        (2, UnparsedKind::ExtensionTag(0x70))
+    ]);
+
+    let regions = unparsed(&bytes);
+    assert_eq!(regions[0].range, 90..108);
+    assert_eq!(regions[2].data, [0xe5, 0x01, 0x66, 0x55, 0x44, 0x00]);
+    assert_eq!(regions[5].range, 256..384);
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::SAMPLE;
+use edid_rs::{EDID, EdidRef, Error, Reader};
+use std::borrow::Cow;
+
+fn parse(bytes: &[u8; 128]) -> EDID {
+    EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap()
+}
+
+#[test]
+fn matches_parse() {
+    let view = EdidRef::new(&SAMPLE).unwrap();
+    let edid = parse(&SAMPLE);
+
+    assert_eq!(view.product().unwrap().product_code, edid.product.product_code);
+    assert_eq!(view.manufacturer_id().unwrap().raw(), edid.product.manufacturer_id.raw());
+    assert_eq!(format!("{:?}", view.version().unwrap()), format!("{:?}", edid.version));
+    assert_eq!(format!("{:?}", view.display().unwrap()), format!("{:?}", edid.display));
+    assert_eq!(
+        format!("{:?}", view.detailed_timings().collect::<Vec<_>>()),
+        format!("{:?}", edid.timings.detailed_timings)
+    );
+    assert_eq!(view.preferred_timing().unwrap().active, (2880, 1800));
+    assert_eq!(view.extensions(), edid.extensions);
+    assert_eq!(format!("{:?}", view.to_edid().unwrap()), format!("{:?}", edid));
+}
+
+#[test]
+fn ascii_name() {
+    let view = EdidRef::new(&SAMPLE).unwrap();
+    assert!(matches!(view.monitor_name(), Some(Cow::Borrowed("Color LCD"))));
+    assert_eq!(view.serial_string(), None);
+}
+
+#[cfg(feature = "descriptors")]
+#[test]
+fn cp437_name() {
+    use common::fix_checksum;
+    use edid_rs::MonitorDescriptor;
+
+    // "Écran" with a code page 437 capital E acute.
+    let mut bytes = SAMPLE;
+    bytes[77..90].copy_from_slice(&[0x90, b'c', b'r', b'a', b'n', 0x0a, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20]);
+    fix_checksum(&mut bytes);
+
+    let view = EdidRef::new(&bytes).unwrap();
+    assert!(matches!(view.monitor_name(), Some(Cow::Owned(ref name)) if name == "Écran"));
+
+    let edid = parse(&bytes);
+    assert!(matches!(&edid.descriptors.0[0], MonitorDescriptor::MonitorName(name) if name == "Écran"));
+}
+
+#[test]
+fn checked() {
+    let mut bytes = SAMPLE;
+    bytes[127] ^= 1;
+    assert_eq!(EdidRef::new(&bytes).err(), Some(Error::InvalidChecksum { offset: 0 }));
+
+    let mut bytes = SAMPLE;
+    bytes[0] = 1;
+    assert_eq!(EdidRef::new(&bytes).err(), Some(Error::InvalidHeader { offset: 0 }));
+}
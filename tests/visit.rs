@@ -0,0 +1,70 @@
+mod common;
+
+use common::SAMPLE;
+use edid_rs::{DetailedTiming, EdidVisitor, ProductInformation, Reader, parse_with_visitor};
+
+// Keeps only the preferred mode, as a small firmware might.
+#[derive(Default)]
+struct PreferredMode {
+    calls: usize,
+    preferred: Option<DetailedTiming>
+}
+
+impl EdidVisitor for PreferredMode {
+    fn detailed_timing(&mut self, index: usize, timing: &DetailedTiming) {
+        self.calls += 1;
+        if index == 0 {
+            self.preferred = Some(timing.clone());
+        }
+    }
+}
+
+// Records the rest of what is handed out.
+#[derive(Default)]
+struct Everything {
+    product_code: Option<u16>,
+    descriptors: Vec<(usize, u8)>,
+    extensions: usize
+}
+
+impl EdidVisitor for Everything {
+    fn product(&mut self, product: &ProductInformation) {
+        self.product_code = Some(product.product_code);
+    }
+
+    fn descriptor(&mut self, slot: usize, tag: u8, _data: &[u8; 13]) {
+        self.descriptors.push((slot, tag));
+    }
+
+    fn extension(&mut self, _tag: u8, _block: &[u8; 128]) {
+        self.extensions += 1;
+    }
+}
+
+#[test]
+fn preferred_mode() {
+    let mut visitor = PreferredMode::default();
+    parse_with_visitor(&mut Reader::new(&mut &SAMPLE[..]), &mut visitor).unwrap();
+
+    assert_eq!(visitor.calls, 1);
+    let preferred = visitor.preferred.unwrap();
+    assert_eq!(preferred.active, (2880, 1800));
+    assert_eq!(preferred.pixel_clock, 337750000);
+}
+
+#[test]
+fn descriptors() {
+    let mut visitor = Everything::default();
+    parse_with_visitor(&mut Reader::new(&mut &SAMPLE[..]), &mut visitor).unwrap();
+
+    assert_eq!(visitor.product_code, Some(40994));
+    assert_eq!(visitor.descriptors, [(1, 0xfc), (2, 0x10), (3, 0x10)]);
+    assert_eq!(visitor.extensions, 0);
+}
+
+#[test]
+fn truncated() {
+    let mut visitor = PreferredMode::default();
+    assert!(parse_with_visitor(&mut Reader::new(&mut &SAMPLE[..80]), &mut visitor).is_err());
+    assert_eq!(visitor.calls, 1);
+}
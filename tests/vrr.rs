@@ -0,0 +1,207 @@
+#![cfg(feature = "cta")]
+
+mod common;
+
+use common::{SAMPLE, cta_extension, fix_checksum};
+use edid_rs::{AdaptiveSyncDescriptor, AmdVsdb, EDID, ExtensionBlock, Reader, VrrRange, VrrSource};
+
+fn parse(bytes: &[u8]) -> EDID {
+    EDID::parse(&mut Reader::new(&mut &bytes[..])).unwrap()
+}
+
+// The sample with range limits of 40 to 120Hz and, if `continuous`,
+// the feature bit for continuous frequencies set.
+fn with_range_limits(continuous: bool) -> EDID {
+    let mut bytes = SAMPLE;
+    bytes[90..108].copy_from_slice(&[
+        0, 0, 0, 0xfd, 0, 40, 120, 30, 160, 30, 0x01, 0x0a, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20
+    ]);
+    bytes[24] |= continuous as u8;
+    fix_checksum(&mut bytes);
+    parse(&bytes)
+}
+
+// An HDMI Forum vendor specific data block with VRRmin and VRRmax.
+fn hf_vsdb(min: u8, max: u16) -> Vec<u8> {
+    vec![0xd8, 0x5d, 0xc4, 0x01, 0x78, 0x00, 0x00, 0x00, min | ((max >> 8) as u8) << 6, max as u8]
+}
+
+// An AMD vendor specific data block with the OUI and then `data`.
+fn amd_vsdb(data: &[u8]) -> Vec<u8> {
+    let mut vsdb = vec![0x1a, 0x00, 0x00];
+    vsdb.extend_from_slice(data);
+    vsdb
+}
+
+// FreeSync 1.1 at 48 to 144Hz, with no range switching over MCCS.
+const FREESYNC_1: &[u8] = &[0x01, 0x01, 0x30, 0x90, 0x00];
+// FreeSync 2 at 48 to 75Hz, as on a 1440p office monitor.
+const FREESYNC_2: &[u8] = &[0x02, 0x00, 0x30, 0x4b, 0x00, 0x07, 0x00, 0x00];
+
+fn cta(data_blocks: &[(u8, &[u8])]) -> ExtensionBlock {
+    ExtensionBlock(cta_extension(0, data_blocks))
+}
+
+#[test]
+fn freesync_1() {
+    let vsdb = cta(&[(3, &amd_vsdb(FREESYNC_1))]).amd_vsdb().unwrap();
+    match vsdb {
+        AmdVsdb::FreeSync { version, min_refresh, max_refresh, flags, flags_2 } => {
+            assert_eq!(version, (1, 1));
+            assert_eq!((min_refresh, max_refresh), (48, 144));
+            assert_eq!(flags, 0);
+            assert_eq!(flags_2, None);
+        },
+        other => panic!("{:?}", other)
+    }
+    assert_eq!(vsdb.refresh_range(), Some((48, 144)));
+    assert!(vsdb.supports_lfc());
+}
+
+#[test]
+fn freesync_2() {
+    let vsdb = cta(&[(3, &amd_vsdb(FREESYNC_2))]).amd_vsdb().unwrap();
+    match vsdb {
+        AmdVsdb::FreeSync { version, flags_2, .. } => {
+            assert_eq!(version, (2, 0));
+            assert_eq!(flags_2, Some(0x07));
+        },
+        other => panic!("{:?}", other)
+    }
+    assert_eq!(vsdb.refresh_range(), Some((48, 75)));
+    // 75Hz is less than twice 48Hz.
+    assert!(!vsdb.supports_lfc());
+}
+
+#[test]
+fn freesync_unknown() {
+    let vsdb = cta(&[(3, &amd_vsdb(&[0x04, 0x00, 0x30, 0x90, 0x00]))]).amd_vsdb().unwrap();
+    assert!(matches!(vsdb, AmdVsdb::Unknown { version: 4, .. }));
+    assert_eq!(vsdb.refresh_range(), None);
+    assert!(!vsdb.supports_lfc());
+
+    // Too short for the range.
+    let vsdb = cta(&[(3, &amd_vsdb(&[0x01, 0x01, 0x30]))]).amd_vsdb().unwrap();
+    assert!(matches!(vsdb, AmdVsdb::Unknown { version: 1, .. }));
+
+    assert!(cta(&[]).amd_vsdb().is_none());
+}
+
+fn vrr_range(edid: &EDID, data_blocks: &[(u8, &[u8])]) -> Option<VrrRange> {
+    edid.vrr_range(&[cta(data_blocks)])
+}
+
+#[test]
+fn vrr_hdmi_only() {
+    let edid = parse(&SAMPLE);
+    assert_eq!(vrr_range(&edid, &[(3, &hf_vsdb(48, 300))]), Some(VrrRange { min: 48, max: 300, source: VrrSource::HdmiForum, conflict: false }));
+    // A VRRmin of zero means no support.
+    assert_eq!(vrr_range(&edid, &[(3, &hf_vsdb(0, 144))]), None);
+    assert_eq!(vrr_range(&edid, &[]), None);
+}
+
+#[test]
+fn vrr_hdmi_max_from_range_limits() {
+    // Without VRRmax the range limits give the maximum.
+    let edid = with_range_limits(false);
+    assert_eq!(vrr_range(&edid, &[(3, &hf_vsdb(24, 0))]), Some(VrrRange { min: 24, max: 120, source: VrrSource::HdmiForum, conflict: false }));
+}
+
+#[test]
+fn vrr_freesync_only() {
+    let edid = with_range_limits(true);
+    assert_eq!(vrr_range(&edid, &[(3, &amd_vsdb(FREESYNC_1))]), Some(VrrRange { min: 48, max: 144, source: VrrSource::FreeSync, conflict: false }));
+}
+
+#[test]
+fn vrr_range_limits_only() {
+    assert_eq!(vrr_range(&with_range_limits(true), &[]), Some(VrrRange { min: 40, max: 120, source: VrrSource::RangeLimits, conflict: false }));
+    // Range limits alone do not mean variable refresh.
+    assert_eq!(vrr_range(&with_range_limits(false), &[]), None);
+}
+
+#[test]
+fn vrr_hdmi_and_freesync() {
+    let edid = parse(&SAMPLE);
+    let freesync = amd_vsdb(FREESYNC_1);
+
+    // The same range from both.
+    assert_eq!(vrr_range(&edid, &[(3, &hf_vsdb(48, 144)), (3, &freesync)]), Some(VrrRange { min: 48, max: 144, source: VrrSource::HdmiForum, conflict: false }));
+    // Where they overlap.
+    assert_eq!(vrr_range(&edid, &[(3, &hf_vsdb(40, 120)), (3, &freesync)]), Some(VrrRange { min: 48, max: 120, source: VrrSource::HdmiForum, conflict: true }));
+    // No overlap, so the HDMI Forum range.
+    assert_eq!(vrr_range(&edid, &[(3, &hf_vsdb(24, 40)), (3, &freesync)]), Some(VrrRange { min: 24, max: 40, source: VrrSource::HdmiForum, conflict: true }));
+}
+
+// A DisplayID 2.0 extension with an Adaptive-Sync data block
+// of `revision` holding the `descriptors`.
+fn displayid_adaptive_sync(revision: u8, descriptors: &[&[u8]]) -> ExtensionBlock {
+    let payload: Vec<u8> = descriptors.concat();
+    let mut block = [0u8; 128];
+    block[..5].copy_from_slice(&[0x70, 0x20, 3 + payload.len() as u8, 0x00, 0x00]);
+    block[5..8].copy_from_slice(&[0x2b, revision, payload.len() as u8]);
+    block[8..8 + payload.len()].copy_from_slice(&payload);
+    fix_checksum(&mut block);
+    ExtensionBlock(block)
+}
+
+// The native 48 to 144Hz range of a panel, with seamless changes of up to
+// a millisecond either way.
+const NATIVE: &[u8] = &[0x11, 0x04, 48, 0x8f, 0x00, 0x04];
+// A 1 to 240Hz range reached with a fixed average vertical total.
+const DOUBLED: &[u8] = &[0x00, 0x00, 1, 0xef, 0x00, 0x00];
+// A reserved vertical total type.
+const RESERVED: &[u8] = &[0x09, 0x04, 48, 0x8f, 0x00, 0x04];
+
+#[test]
+fn adaptive_sync() {
+    let block = displayid_adaptive_sync(0x00, &[NATIVE, DOUBLED, RESERVED]);
+    assert_eq!(block.adaptive_sync(), [
+        AdaptiveSyncDescriptor::Range {
+            native_panel: true, adaptive_vtotal: false, seamless_transition: true,
+            max_increase: 4, max_decrease: 4, min_refresh: 48, max_refresh: 144, extra: vec![]
+        },
+        AdaptiveSyncDescriptor::Range {
+            native_panel: false, adaptive_vtotal: false, seamless_transition: false,
+            max_increase: 0, max_decrease: 0, min_refresh: 1, max_refresh: 240, extra: vec![]
+        },
+        AdaptiveSyncDescriptor::Unknown(RESERVED.to_vec())
+    ]);
+}
+
+#[test]
+fn adaptive_sync_revision() {
+    // Revision 1 adds a byte to each descriptor.
+    let block = displayid_adaptive_sync(0x10, &[&[NATIVE, &[0xaa]].concat()]);
+    match &block.adaptive_sync()[..] {
+        [AdaptiveSyncDescriptor::Range { max_refresh: 144, extra, .. }] => assert_eq!(extra, &[0xaa]),
+        other => panic!("{:?}", other)
+    }
+}
+
+#[test]
+fn adaptive_sync_missing() {
+    assert!(cta(&[]).adaptive_sync().is_empty());
+    // DisplayID 1.3.
+    let mut block = displayid_adaptive_sync(0x00, &[NATIVE]);
+    block.0[1] = 0x13;
+    assert!(block.adaptive_sync().is_empty());
+}
+
+#[test]
+fn vrr_adaptive_sync() {
+    let edid = parse(&SAMPLE);
+
+    // Only a native range counts.
+    let native = displayid_adaptive_sync(0x00, &[DOUBLED, NATIVE]);
+    assert_eq!(edid.vrr_range(std::slice::from_ref(&native)), Some(VrrRange { min: 48, max: 144, source: VrrSource::AdaptiveSync, conflict: false }));
+    assert_eq!(edid.vrr_range(&[displayid_adaptive_sync(0x00, &[DOUBLED, RESERVED])]), None);
+
+    // The HDMI Forum range comes first.
+    let extensions = [cta(&[(3, &hf_vsdb(40, 120))]), native.clone()];
+    assert_eq!(edid.vrr_range(&extensions), Some(VrrRange { min: 40, max: 120, source: VrrSource::HdmiForum, conflict: false }));
+
+    // And the FreeSync block is checked against it.
+    let extensions = [cta(&[(3, &amd_vsdb(&[0x01, 0x01, 0x30, 0x78, 0x00]))]), native];
+    assert_eq!(edid.vrr_range(&extensions), Some(VrrRange { min: 48, max: 120, source: VrrSource::AdaptiveSync, conflict: true }));
+}